@@ -0,0 +1,22 @@
+// =============================================================================
+// Fuzz: Framing
+// =============================================================================
+
+// Reads frames from an arbitrary byte stream, as received from an untrusted
+// peer, until the stream ends or an invalid frame is found.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use midi_2_protocol::framing;
+
+fuzz_target!(|data: &[u8]| {
+    let mut reader = data;
+    let mut words = Vec::new();
+
+    while let Ok(true) = framing::read_frame(&mut reader, &mut words) {
+        let mut written = Vec::new();
+
+        framing::write_frame(&mut written, &words).expect("read frames are valid");
+    }
+});
@@ -0,0 +1,48 @@
+// =============================================================================
+// Fuzz: Message
+// =============================================================================
+
+// Parses arbitrary buffers of UMP words as a stream of messages, exercising
+// message boundary detection, message parsing, change detection, and reserved
+// bit validation.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use midi_2_protocol::{
+    message::Message,
+    packet::{
+        self,
+        Packet,
+    },
+};
+
+fuzz_target!(|data: &[u8]| {
+    let mut words: Vec<u32> = data
+        .chunks_exact(4)
+        .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect();
+
+    let max_words = data.first().map_or(0, |byte| usize::from(*byte));
+    let (prefix, _) = packet::split_at_message(&words, max_words);
+
+    assert!(prefix.len() <= max_words);
+
+    let mut previous = Packet::default();
+    let mut rest = &words[..];
+
+    while let Ok(mut packet) = Packet::try_read(rest) {
+        rest = &rest[packet.len()..];
+
+        if let Ok(message) = packet.try_message() {
+            let _ = message.changed_fields(&previous);
+            let _ = message.reserved_violations();
+        }
+
+        let _ = Message::try_from_strict(&mut packet[..]);
+
+        previous = packet;
+    }
+
+    let _ = Message::try_from(&mut words[..]);
+});
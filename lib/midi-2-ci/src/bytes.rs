@@ -0,0 +1,131 @@
+// =============================================================================
+// Bytes
+// =============================================================================
+
+use crate::Error;
+
+// -----------------------------------------------------------------------------
+
+// Reader
+
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub const fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, position: 0 }
+    }
+
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let needed = self.position + len;
+
+        match self.bytes.get(self.position..needed) {
+            Some(bytes) => {
+                self.position = needed;
+
+                Ok(bytes)
+            }
+            None => Err(Error::truncated(needed, self.bytes.len())),
+        }
+    }
+
+    pub fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let mut array = [0; N];
+
+        array.copy_from_slice(self.take(N)?);
+
+        Ok(array)
+    }
+
+    // Takes the given number of bytes, each of which must be a 7-bit value.
+
+    fn seven_bit(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let bytes = self.take(len)?;
+
+        match bytes.iter().find(|&&byte| byte > 0x7f) {
+            Some(&byte) => Err(Error::overflow(usize::from(byte), 7)),
+            None => Ok(bytes),
+        }
+    }
+
+    pub fn u7(&mut self) -> Result<u8, Error> {
+        Ok(self.seven_bit(1)?[0])
+    }
+
+    pub fn u14(&mut self) -> Result<u16, Error> {
+        let bytes = self.seven_bit(2)?;
+
+        Ok(u16::from(bytes[0]) | u16::from(bytes[1]) << 7)
+    }
+
+    pub fn u28(&mut self) -> Result<u32, Error> {
+        Ok(self
+            .seven_bit(4)?
+            .iter()
+            .rev()
+            .fold(0, |value, &byte| value << 7 | u32::from(byte)))
+    }
+
+    pub fn optional_u7(&mut self) -> Result<u8, Error> {
+        if self.is_empty() {
+            Ok(0)
+        } else {
+            self.u7()
+        }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.position >= self.bytes.len()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Writer
+
+pub struct Writer<'a> {
+    bytes: &'a mut Vec<u8>,
+}
+
+impl<'a> Writer<'a> {
+    pub fn new(bytes: &'a mut Vec<u8>) -> Self {
+        Self { bytes }
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) -> Result<&mut Self, Error> {
+        if let Some(&byte) = bytes.iter().find(|&&byte| byte > 0x7f) {
+            return Err(Error::overflow(usize::from(byte), 7));
+        }
+
+        self.bytes.extend_from_slice(bytes);
+
+        Ok(self)
+    }
+
+    pub fn u7(&mut self, value: u8) -> Result<&mut Self, Error> {
+        self.bytes(&[value])
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn u14(&mut self, value: usize) -> Result<&mut Self, Error> {
+        match value {
+            0..=0x3fff => self.bytes(&[(value & 0x7f) as u8, (value >> 7) as u8]),
+            _ => Err(Error::overflow(value, 14)),
+        }
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn u28(&mut self, value: usize) -> Result<&mut Self, Error> {
+        match value {
+            0..=0x0fff_ffff => self.bytes(&[
+                (value & 0x7f) as u8,
+                (value >> 7 & 0x7f) as u8,
+                (value >> 14 & 0x7f) as u8,
+                (value >> 21) as u8,
+            ]),
+            _ => Err(Error::overflow(value, 28)),
+        }
+    }
+}
@@ -0,0 +1,270 @@
+// =============================================================================
+// Discovery
+// =============================================================================
+
+//! Discovery and management message bodies.
+//!
+//! The [`discovery`](crate::discovery) module contains the bodies of the
+//! MIDI-CI Discovery messages **([M2-101-UM])**, by which Devices find each
+//! other and exchange their identities and supported categories, and of the
+//! ACK and NAK messages which reply to other MIDI-CI messages.
+
+use crate::{
+    bytes::{
+        Reader,
+        Writer,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Fields
+
+// Identity
+
+/// The identity of a Device, as given in Discovery messages (and in the Device
+/// Identity Notification message of the UMP Stream).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Identity {
+    /// The System Exclusive ID of the manufacturer (one byte IDs are given as
+    /// the first byte, followed by two zero bytes).
+    pub manufacturer: [u8; 3],
+    /// The 14-bit Device Family.
+    pub family: u16,
+    /// The 14-bit Device Family Model Number.
+    pub model: u16,
+    /// The Software Revision Level.
+    pub version: [u8; 4],
+}
+
+impl Identity {
+    fn try_read(reader: &mut Reader<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            manufacturer: reader.array()?,
+            family: reader.u14()?,
+            model: reader.u14()?,
+            version: reader.array()?,
+        })
+    }
+
+    fn write(&self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        writer
+            .bytes(&self.manufacturer)?
+            .u14(usize::from(self.family))?
+            .u14(usize::from(self.model))?
+            .bytes(&self.version)
+            .map(|_| ())
+    }
+}
+
+// Categories
+
+/// The MIDI-CI categories supported by a Device.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_ci::discovery::*;
+/// #
+/// let categories = Categories::default()
+///     .set_profile_configuration(true)
+///     .set_property_exchange(true);
+///
+/// assert_eq!(u8::from(categories), 0x0c);
+/// assert_eq!(Categories::from(0x0c), categories);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Categories {
+    profile_configuration: bool,
+    property_exchange: bool,
+    process_inquiry: bool,
+}
+
+impl Categories {
+    /// Returns `true` if Profile Configuration is supported.
+    #[must_use]
+    pub const fn profile_configuration(self) -> bool {
+        self.profile_configuration
+    }
+
+    /// Returns `true` if Property Exchange is supported.
+    #[must_use]
+    pub const fn property_exchange(self) -> bool {
+        self.property_exchange
+    }
+
+    /// Returns `true` if Process Inquiry is supported.
+    #[must_use]
+    pub const fn process_inquiry(self) -> bool {
+        self.process_inquiry
+    }
+
+    /// Sets whether Profile Configuration is supported.
+    #[must_use]
+    pub const fn set_profile_configuration(mut self, supported: bool) -> Self {
+        self.profile_configuration = supported;
+        self
+    }
+
+    /// Sets whether Property Exchange is supported.
+    #[must_use]
+    pub const fn set_property_exchange(mut self, supported: bool) -> Self {
+        self.property_exchange = supported;
+        self
+    }
+
+    /// Sets whether Process Inquiry is supported.
+    #[must_use]
+    pub const fn set_process_inquiry(mut self, supported: bool) -> Self {
+        self.process_inquiry = supported;
+        self
+    }
+}
+
+impl From<u8> for Categories {
+    fn from(value: u8) -> Self {
+        Self {
+            profile_configuration: value & 0x04 != 0,
+            property_exchange: value & 0x08 != 0,
+            process_inquiry: value & 0x10 != 0,
+        }
+    }
+}
+
+impl From<Categories> for u8 {
+    fn from(categories: Categories) -> Self {
+        Self::from(categories.profile_configuration) << 2
+            | Self::from(categories.property_exchange) << 3
+            | Self::from(categories.process_inquiry) << 4
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Bodies
+
+// Discovery
+
+/// The body of a Discovery message.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Discovery {
+    /// The identity of the sender.
+    pub identity: Identity,
+    /// The categories supported by the sender.
+    pub categories: Categories,
+    /// The largest System Exclusive message the sender can receive (in bytes).
+    pub max_sysex_size: u32,
+    /// The Output Path ID of the sender (0 for MIDI-CI Message Version 1).
+    pub output_path: u8,
+}
+
+impl Discovery {
+    pub(crate) fn try_read(reader: &mut Reader<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            identity: Identity::try_read(reader)?,
+            categories: Categories::from(reader.u7()?),
+            max_sysex_size: reader.u28()?,
+            output_path: reader.optional_u7()?,
+        })
+    }
+
+    pub(crate) fn write(&self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        self.identity.write(writer)?;
+
+        writer
+            .u7(self.categories.into())?
+            .u28(self.max_sysex_size as usize)?
+            .u7(self.output_path)
+            .map(|_| ())
+    }
+}
+
+// Discovery Reply
+
+/// The body of a Reply to Discovery message.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DiscoveryReply {
+    /// The identity of the sender.
+    pub identity: Identity,
+    /// The categories supported by the sender.
+    pub categories: Categories,
+    /// The largest System Exclusive message the sender can receive (in bytes).
+    pub max_sysex_size: u32,
+    /// The Output Path ID given in the Discovery message being replied to.
+    pub output_path: u8,
+    /// The Function Block of the sender (`0x7f` if not known).
+    pub function_block: u8,
+}
+
+impl DiscoveryReply {
+    pub(crate) fn try_read(reader: &mut Reader<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            identity: Identity::try_read(reader)?,
+            categories: Categories::from(reader.u7()?),
+            max_sysex_size: reader.u28()?,
+            output_path: reader.optional_u7()?,
+            function_block: reader.optional_u7()?,
+        })
+    }
+
+    pub(crate) fn write(&self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        self.identity.write(writer)?;
+
+        writer
+            .u7(self.categories.into())?
+            .u28(self.max_sysex_size as usize)?
+            .u7(self.output_path)?
+            .u7(self.function_block)
+            .map(|_| ())
+    }
+}
+
+// Acknowledgement
+
+/// The body of an ACK or NAK message.
+///
+/// A NAK message of MIDI-CI Message Version 1 has no body, and is read as the
+/// default (empty) acknowledgement.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Acknowledgement {
+    /// The Sub-ID#2 of the message being acknowledged.
+    pub original_sub_id: u8,
+    /// The status code of the acknowledgement.
+    pub status_code: u8,
+    /// The status data of the acknowledgement.
+    pub status_data: u8,
+    /// Details of the acknowledgement, specific to the message acknowledged.
+    pub details: [u8; 5],
+    /// A message for display to the user (ASCII text, or empty).
+    pub text: Vec<u8>,
+}
+
+impl Acknowledgement {
+    pub(crate) fn try_read(reader: &mut Reader<'_>) -> Result<Self, Error> {
+        if reader.is_empty() {
+            return Ok(Self::default());
+        }
+
+        let [original_sub_id, status_code, status_data] = reader.array()?;
+        let details = reader.array()?;
+        let len = reader.u14()?;
+
+        Ok(Self {
+            original_sub_id,
+            status_code,
+            status_data,
+            details,
+            text: reader.take(usize::from(len))?.to_vec(),
+        })
+    }
+
+    pub(crate) fn write(&self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        writer
+            .bytes(&[self.original_sub_id, self.status_code, self.status_data])?
+            .bytes(&self.details)?
+            .u14(self.text.len())?
+            .bytes(&self.text)
+            .map(|_| ())
+    }
+}
@@ -1 +1,58 @@
+mod bytes;
 
+pub mod discovery;
+pub mod message;
+pub mod profile;
+pub mod property;
+
+use thiserror::Error;
+
+// =============================================================================
+// MIDI 2 CI
+// =============================================================================
+
+// Errors
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("Conversion: Attempted to convert from {0}, not a valid variant.")]
+    Conversion(u8),
+    #[error("Limit: Required {needed} bytes, exceeding the limit of {limit} bytes.")]
+    Limit { limit: usize, needed: usize },
+    #[error("Overflow: Attempted to store value {0} in a {1} bit field.")]
+    Overflow(usize, u8),
+    #[error("Protocol: {0}")]
+    Protocol(#[from] midi_2_protocol::Error),
+    #[error("Sequence: Found chunk {0} of a property out of sequence.")]
+    Sequence(u16),
+    #[error("Size: A message of {needed} bytes exceeds the maximum of {maximum} bytes.")]
+    Size { needed: usize, maximum: usize },
+    #[error("Truncated: Expected a message of {needed} bytes, but found {available} bytes.")]
+    Truncated { needed: usize, available: usize },
+}
+
+impl Error {
+    pub(crate) const fn conversion(value: u8) -> Self {
+        Self::Conversion(value)
+    }
+
+    pub(crate) const fn limit(limit: usize, needed: usize) -> Self {
+        Self::Limit { limit, needed }
+    }
+
+    pub(crate) const fn overflow(value: usize, size: u8) -> Self {
+        Self::Overflow(value, size)
+    }
+
+    pub(crate) const fn sequence(chunk: u16) -> Self {
+        Self::Sequence(chunk)
+    }
+
+    pub(crate) const fn size(needed: usize, maximum: usize) -> Self {
+        Self::Size { needed, maximum }
+    }
+
+    pub(crate) const fn truncated(needed: usize, available: usize) -> Self {
+        Self::Truncated { needed, available }
+    }
+}
@@ -0,0 +1,483 @@
+// =============================================================================
+// Message
+// =============================================================================
+
+//! MIDI-CI messages and common field types.
+//!
+//! The [`message`](crate::message) module contains the [`Message`] type, which
+//! represents a single MIDI-CI message **([M2-101-UM])** -- a Universal System
+//! Exclusive message with the MIDI-CI Sub-ID#1 -- as the common header fields
+//! and a [`Body`] specific to the kind of message.
+//!
+//! Messages are encoded to and decoded from System Exclusive payloads (which
+//! exclude the `0xf0` and `0xf7` Start and End bytes), and can be sent and
+//! received as UMPs using the [`sysex`](midi_2_protocol::sysex) module of the
+//! `midi-2-protocol` crate.
+
+use midi_2_protocol::{
+    message::{
+        Channel,
+        Group,
+    },
+    sysex::{
+        self,
+        Sysex,
+    },
+};
+
+use crate::{
+    bytes::{
+        Reader,
+        Writer,
+    },
+    discovery::{
+        Acknowledgement,
+        Discovery,
+        DiscoveryReply,
+    },
+    profile::{
+        ProfileChannels,
+        ProfileData,
+        ProfileId,
+        Profiles,
+    },
+    property::{
+        Capabilities,
+        PropertyChunk,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const UNIVERSAL_NON_REAL_TIME: u8 = 0x7e;
+const MIDI_CI: u8 = 0x0d;
+
+/// The MIDI-CI Message Version written by default (`0x02`, for MIDI-CI 1.2).
+pub const VERSION: u8 = 0x02;
+
+// -----------------------------------------------------------------------------
+
+// Fields
+
+// MUID
+
+/// A MIDI Unique Identifier (MUID), the 28-bit address of a MIDI-CI Device.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_ci::*;
+/// # use midi_2_ci::message::*;
+/// #
+/// let muid = Muid::try_new(0x0123_4567)?;
+///
+/// assert_eq!(u32::from(muid), 0x0123_4567);
+/// assert!(Muid::try_new(0x1000_0000).is_err());
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Muid(u32);
+
+impl Muid {
+    /// The Broadcast MUID, addressing all MIDI-CI Devices.
+    pub const BROADCAST: Self = Self(0x0fff_ffff);
+
+    /// Creates a new [`Muid`] from a 28-bit value.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the value is larger than 28 bits.
+    pub const fn try_new(value: u32) -> Result<Self, Error> {
+        match value {
+            0..=0x0fff_ffff => Ok(Self(value)),
+            _ => Err(Error::overflow(value as usize, 28)),
+        }
+    }
+
+    /// Returns `true` if this is the Broadcast MUID.
+    #[must_use]
+    pub const fn is_broadcast(self) -> bool {
+        self.0 == Self::BROADCAST.0
+    }
+
+    pub(crate) fn try_read(reader: &mut Reader<'_>) -> Result<Self, Error> {
+        reader.u28().map(Self)
+    }
+
+    pub(crate) fn write(self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        writer.u28(self.0 as usize).map(|_| ())
+    }
+}
+
+impl From<Muid> for u32 {
+    fn from(muid: Muid) -> Self {
+        muid.0
+    }
+}
+
+// Destination
+
+/// The destination of a MIDI-CI message, given by its Device ID byte.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Destination {
+    /// A single Channel of a Group (`0x00` to `0x0f`).
+    Channel(Channel),
+    /// A whole Group (`0x7e`).
+    Group,
+    /// A whole Function Block (`0x7f`).
+    #[default]
+    FunctionBlock,
+}
+
+impl From<Destination> for u8 {
+    fn from(destination: Destination) -> Self {
+        match destination {
+            Destination::Channel(channel) => channel.into(),
+            Destination::Group => 0x7e,
+            Destination::FunctionBlock => 0x7f,
+        }
+    }
+}
+
+impl TryFrom<u8> for Destination {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0x00..=0x0f => Ok(Self::Channel(Channel::try_from(value)?)),
+            0x7e => Ok(Self::Group),
+            0x7f => Ok(Self::FunctionBlock),
+            _ => Err(Error::conversion(value)),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Body
+
+/// The body of a MIDI-CI message, identified by the Sub-ID#2 of the message.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Body {
+    /// Discovery (`0x70`).
+    Discovery(Discovery),
+    /// Reply to Discovery (`0x71`).
+    DiscoveryReply(DiscoveryReply),
+    /// Invalidate MUID (`0x7e`), carrying the MUID to invalidate.
+    InvalidateMuid(Muid),
+    /// ACK (`0x7d`).
+    Ack(Acknowledgement),
+    /// NAK (`0x7f`).
+    Nak(Acknowledgement),
+    /// Profile Inquiry (`0x20`).
+    ProfileInquiry,
+    /// Reply to Profile Inquiry (`0x21`).
+    ProfileInquiryReply(Profiles),
+    /// Set Profile On (`0x22`).
+    SetProfileOn(ProfileChannels),
+    /// Set Profile Off (`0x23`).
+    SetProfileOff(ProfileChannels),
+    /// Profile Enabled Report (`0x24`).
+    ProfileEnabled(ProfileChannels),
+    /// Profile Disabled Report (`0x25`).
+    ProfileDisabled(ProfileChannels),
+    /// Profile Added Report (`0x26`).
+    ProfileAdded(ProfileId),
+    /// Profile Removed Report (`0x27`).
+    ProfileRemoved(ProfileId),
+    /// Profile Specific Data (`0x2f`).
+    ProfileSpecificData(ProfileData),
+    /// Inquiry: Property Exchange Capabilities (`0x30`).
+    PropertyExchangeCapabilities(Capabilities),
+    /// Reply to Property Exchange Capabilities (`0x31`).
+    PropertyExchangeCapabilitiesReply(Capabilities),
+    /// Inquiry: Get Property Data (`0x34`).
+    GetPropertyData(PropertyChunk),
+    /// Reply to Get Property Data (`0x35`).
+    GetPropertyDataReply(PropertyChunk),
+    /// Inquiry: Set Property Data (`0x36`).
+    SetPropertyData(PropertyChunk),
+    /// Reply to Set Property Data (`0x37`).
+    SetPropertyDataReply(PropertyChunk),
+    /// Subscription (`0x38`).
+    Subscription(PropertyChunk),
+    /// Reply to Subscription (`0x39`).
+    SubscriptionReply(PropertyChunk),
+    /// Notify (`0x3f`).
+    Notify(PropertyChunk),
+}
+
+impl Body {
+    /// Returns the Sub-ID#2 of the message.
+    #[must_use]
+    pub const fn sub_id(&self) -> u8 {
+        match self {
+            Self::ProfileInquiry => 0x20,
+            Self::ProfileInquiryReply(_) => 0x21,
+            Self::SetProfileOn(_) => 0x22,
+            Self::SetProfileOff(_) => 0x23,
+            Self::ProfileEnabled(_) => 0x24,
+            Self::ProfileDisabled(_) => 0x25,
+            Self::ProfileAdded(_) => 0x26,
+            Self::ProfileRemoved(_) => 0x27,
+            Self::ProfileSpecificData(_) => 0x2f,
+            Self::PropertyExchangeCapabilities(_) => 0x30,
+            Self::PropertyExchangeCapabilitiesReply(_) => 0x31,
+            Self::GetPropertyData(_) => 0x34,
+            Self::GetPropertyDataReply(_) => 0x35,
+            Self::SetPropertyData(_) => 0x36,
+            Self::SetPropertyDataReply(_) => 0x37,
+            Self::Subscription(_) => 0x38,
+            Self::SubscriptionReply(_) => 0x39,
+            Self::Notify(_) => 0x3f,
+            Self::Discovery(_) => 0x70,
+            Self::DiscoveryReply(_) => 0x71,
+            Self::Ack(_) => 0x7d,
+            Self::InvalidateMuid(_) => 0x7e,
+            Self::Nak(_) => 0x7f,
+        }
+    }
+
+    fn try_read(sub_id: u8, reader: &mut Reader<'_>) -> Result<Self, Error> {
+        Ok(match sub_id {
+            0x20 => Self::ProfileInquiry,
+            0x21 => Self::ProfileInquiryReply(Profiles::try_read(reader)?),
+            0x22 => Self::SetProfileOn(ProfileChannels::try_read(reader)?),
+            0x23 => Self::SetProfileOff(ProfileChannels::try_read(reader)?),
+            0x24 => Self::ProfileEnabled(ProfileChannels::try_read(reader)?),
+            0x25 => Self::ProfileDisabled(ProfileChannels::try_read(reader)?),
+            0x26 => Self::ProfileAdded(ProfileId::try_read(reader)?),
+            0x27 => Self::ProfileRemoved(ProfileId::try_read(reader)?),
+            0x2f => Self::ProfileSpecificData(ProfileData::try_read(reader)?),
+            0x30 => Self::PropertyExchangeCapabilities(Capabilities::try_read(reader)?),
+            0x31 => Self::PropertyExchangeCapabilitiesReply(Capabilities::try_read(reader)?),
+            0x34 => Self::GetPropertyData(PropertyChunk::try_read(reader)?),
+            0x35 => Self::GetPropertyDataReply(PropertyChunk::try_read(reader)?),
+            0x36 => Self::SetPropertyData(PropertyChunk::try_read(reader)?),
+            0x37 => Self::SetPropertyDataReply(PropertyChunk::try_read(reader)?),
+            0x38 => Self::Subscription(PropertyChunk::try_read(reader)?),
+            0x39 => Self::SubscriptionReply(PropertyChunk::try_read(reader)?),
+            0x3f => Self::Notify(PropertyChunk::try_read(reader)?),
+            0x70 => Self::Discovery(Discovery::try_read(reader)?),
+            0x71 => Self::DiscoveryReply(DiscoveryReply::try_read(reader)?),
+            0x7d => Self::Ack(Acknowledgement::try_read(reader)?),
+            0x7e => Self::InvalidateMuid(Muid::try_read(reader)?),
+            0x7f => Self::Nak(Acknowledgement::try_read(reader)?),
+            sub_id => return Err(Error::conversion(sub_id)),
+        })
+    }
+
+    fn write(&self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        match self {
+            Self::ProfileInquiry => Ok(()),
+            Self::ProfileInquiryReply(profiles) => profiles.write(writer),
+            Self::SetProfileOn(profile)
+            | Self::SetProfileOff(profile)
+            | Self::ProfileEnabled(profile)
+            | Self::ProfileDisabled(profile) => profile.write(writer),
+            Self::ProfileAdded(profile) | Self::ProfileRemoved(profile) => profile.write(writer),
+            Self::ProfileSpecificData(data) => data.write(writer),
+            Self::PropertyExchangeCapabilities(capabilities)
+            | Self::PropertyExchangeCapabilitiesReply(capabilities) => capabilities.write(writer),
+            Self::GetPropertyData(chunk)
+            | Self::GetPropertyDataReply(chunk)
+            | Self::SetPropertyData(chunk)
+            | Self::SetPropertyDataReply(chunk)
+            | Self::Subscription(chunk)
+            | Self::SubscriptionReply(chunk)
+            | Self::Notify(chunk) => chunk.write(writer),
+            Self::Discovery(discovery) => discovery.write(writer),
+            Self::DiscoveryReply(reply) => reply.write(writer),
+            Self::Ack(acknowledgement) | Self::Nak(acknowledgement) => {
+                acknowledgement.write(writer)
+            }
+            Self::InvalidateMuid(muid) => muid.write(writer),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Message
+
+/// A MIDI-CI message.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_ci::*;
+/// # use midi_2_ci::discovery::*;
+/// # use midi_2_ci::message::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::sysex::*;
+/// #
+/// let discovery = Discovery {
+///     identity: Identity {
+///         manufacturer: [0x00, 0x21, 0x09],
+///         family: 0x0001,
+///         model: 0x0002,
+///         version: [0x01, 0x00, 0x00, 0x00],
+///     },
+///     categories: Categories::default().set_property_exchange(true),
+///     max_sysex_size: 512,
+///     output_path: 0,
+/// };
+///
+/// let source = Muid::try_new(0x0123_4567)?;
+/// let message = Message::new(source, Muid::BROADCAST, Body::Discovery(discovery));
+///
+/// // send as System Exclusive (7-Bit) UMPs...
+/// let mut words = Vec::new();
+///
+/// message.write_words(Group::G1, &mut words)?;
+///
+/// // ...and receive them
+/// let mut assembler = Assembler::new();
+/// let mut received = None;
+///
+/// for packet in words.chunks(2) {
+///     if let Some(sysex) = assembler.push(packet)? {
+///         received = Some(Message::try_read(&sysex.data)?);
+///     }
+/// }
+///
+/// assert_eq!(received, Some(message));
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Message {
+    /// The destination of the message within the receiving Function Block.
+    pub destination: Destination,
+    /// The MIDI-CI Message Version of the message.
+    pub version: u8,
+    /// The MUID of the sender.
+    pub source: Muid,
+    /// The MUID of the receiver (or [`Muid::BROADCAST`]).
+    pub target: Muid,
+    /// The body of the message.
+    pub body: Body,
+}
+
+impl Message {
+    /// Creates a new [`Message`] to the whole Function Block, with the
+    /// current [`VERSION`].
+    #[must_use]
+    pub const fn new(source: Muid, target: Muid, body: Body) -> Self {
+        Self {
+            destination: Destination::FunctionBlock,
+            version: VERSION,
+            source,
+            target,
+            body,
+        }
+    }
+
+    /// Reads a message from a System Exclusive payload (excluding the `0xf0`
+    /// and `0xf7` bytes).
+    ///
+    /// Any bytes following the fields of the message (for example, fields
+    /// added in later MIDI-CI Message Versions) are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the payload is not a MIDI-CI message, is of an
+    /// unsupported kind, is truncated, or has a numeric field containing a
+    /// byte which is not a 7-bit value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_ci::*;
+    /// # use midi_2_ci::message::*;
+    /// #
+    /// let source = Muid::try_new(0x0123_4567)?;
+    /// let message = Message::new(source, Muid::BROADCAST, Body::InvalidateMuid(source));
+    /// let mut bytes = Vec::new();
+    ///
+    /// message.write(&mut bytes)?;
+    ///
+    /// assert_eq!(Message::try_read(&bytes)?, message);
+    ///
+    /// // the first byte of the source MUID is not a 7-bit value
+    /// bytes[5] = 0x80;
+    ///
+    /// assert!(matches!(
+    ///     Message::try_read(&bytes),
+    ///     Err(Error::Overflow(0x80, 7))
+    /// ));
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn try_read(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+        let [universal, destination, ci, sub_id, version] = reader.array()?;
+
+        if universal != UNIVERSAL_NON_REAL_TIME {
+            return Err(Error::conversion(universal));
+        }
+
+        if ci != MIDI_CI {
+            return Err(Error::conversion(ci));
+        }
+
+        Ok(Self {
+            destination: Destination::try_from(destination)?,
+            version,
+            source: Muid::try_read(&mut reader)?,
+            target: Muid::try_read(&mut reader)?,
+            body: Body::try_read(sub_id, &mut reader)?,
+        })
+    }
+
+    /// Appends the message to the buffer of bytes, as a System Exclusive
+    /// payload (excluding the `0xf0` and `0xf7` bytes).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] (leaving the buffer in an unspecified state) if a
+    /// field of the message is out of range, such as a data byte which is not
+    /// a 7-bit value.
+    pub fn write(&self, bytes: &mut Vec<u8>) -> Result<(), Error> {
+        let mut writer = Writer::new(bytes);
+
+        writer.bytes(&[
+            UNIVERSAL_NON_REAL_TIME,
+            self.destination.into(),
+            MIDI_CI,
+            self.body.sub_id(),
+            self.version,
+        ])?;
+
+        self.source.write(&mut writer)?;
+        self.target.write(&mut writer)?;
+        self.body.write(&mut writer)
+    }
+
+    /// Appends the message to the buffer of words, as System Exclusive
+    /// (7-Bit) messages on the given group.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] (leaving the buffer unchanged) if a field of the
+    /// message is out of range.
+    pub fn write_words(&self, group: Group, words: &mut Vec<u32>) -> Result<(), Error> {
+        let mut bytes = Vec::new();
+
+        self.write(&mut bytes)?;
+
+        Ok(sysex::write_sysex7(group, &bytes, words)?)
+    }
+}
+
+impl TryFrom<&Sysex> for Message {
+    type Error = Error;
+
+    fn try_from(sysex: &Sysex) -> Result<Self, Self::Error> {
+        Self::try_read(&sysex.data)
+    }
+}
@@ -0,0 +1,167 @@
+// =============================================================================
+// Profile
+// =============================================================================
+
+//! Profile Configuration message bodies.
+//!
+//! The [`profile`](crate::profile) module contains the bodies of the MIDI-CI
+//! Profile Configuration messages **([M2-101-UM])**, by which Devices report
+//! the Profiles they support, and enable and disable them.
+
+use crate::{
+    bytes::{
+        Reader,
+        Writer,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Fields
+
+// Profile ID
+
+/// The 5-byte ID of a Profile.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ProfileId(pub [u8; 5]);
+
+impl ProfileId {
+    pub(crate) fn try_read(reader: &mut Reader<'_>) -> Result<Self, Error> {
+        reader.array().map(Self)
+    }
+
+    pub(crate) fn write(self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        writer.bytes(&self.0).map(|_| ())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Bodies
+
+// Profiles
+
+/// The body of a Reply to Profile Inquiry message, listing the enabled and
+/// disabled Profiles of the destination.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_ci::*;
+/// # use midi_2_ci::message::*;
+/// # use midi_2_ci::profile::*;
+/// #
+/// let profiles = Profiles {
+///     enabled: vec![ProfileId([0x7e, 0x00, 0x00, 0x01, 0x01])],
+///     disabled: Vec::new(),
+/// };
+///
+/// let message = Message::new(
+///     Muid::try_new(0x01)?,
+///     Muid::try_new(0x02)?,
+///     Body::ProfileInquiryReply(profiles),
+/// );
+///
+/// let mut bytes = Vec::new();
+///
+/// message.write(&mut bytes)?;
+///
+/// assert_eq!(&bytes[..5], [0x7e, 0x7f, 0x0d, 0x21, 0x02]);
+/// assert_eq!(&bytes[13..], [0x01, 0x00, 0x7e, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00]);
+/// assert_eq!(Message::try_read(&bytes)?, message);
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Profiles {
+    /// The enabled Profiles.
+    pub enabled: Vec<ProfileId>,
+    /// The disabled Profiles.
+    pub disabled: Vec<ProfileId>,
+}
+
+impl Profiles {
+    pub(crate) fn try_read(reader: &mut Reader<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            enabled: Self::try_read_list(reader)?,
+            disabled: Self::try_read_list(reader)?,
+        })
+    }
+
+    pub(crate) fn write(&self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        Self::write_list(&self.enabled, writer)?;
+        Self::write_list(&self.disabled, writer)
+    }
+
+    fn try_read_list(reader: &mut Reader<'_>) -> Result<Vec<ProfileId>, Error> {
+        (0..reader.u14()?)
+            .map(|_| ProfileId::try_read(reader))
+            .collect()
+    }
+
+    fn write_list(profiles: &[ProfileId], writer: &mut Writer<'_>) -> Result<(), Error> {
+        writer.u14(profiles.len())?;
+        profiles
+            .iter()
+            .try_for_each(|profile| profile.write(writer))
+    }
+}
+
+// Profile Channels
+
+/// The body of the Set Profile On, Set Profile Off, Profile Enabled Report and
+/// Profile Disabled Report messages.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ProfileChannels {
+    /// The Profile.
+    pub profile: ProfileId,
+    /// The number of Channels on which the Profile is (to be) enabled or
+    /// disabled, for Profiles at the Group or Function Block level (otherwise
+    /// 0, and always 0 for Set Profile Off).
+    pub channels: u16,
+}
+
+impl ProfileChannels {
+    pub(crate) fn try_read(reader: &mut Reader<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            profile: ProfileId::try_read(reader)?,
+            channels: reader.u14()?,
+        })
+    }
+
+    pub(crate) fn write(self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        self.profile.write(writer)?;
+
+        writer.u14(usize::from(self.channels)).map(|_| ())
+    }
+}
+
+// Profile Data
+
+/// The body of a Profile Specific Data message.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProfileData {
+    /// The Profile.
+    pub profile: ProfileId,
+    /// The data, as defined by the Profile.
+    pub data: Vec<u8>,
+}
+
+impl ProfileData {
+    pub(crate) fn try_read(reader: &mut Reader<'_>) -> Result<Self, Error> {
+        let profile = ProfileId::try_read(reader)?;
+        let len = reader.u28()?;
+
+        Ok(Self {
+            profile,
+            data: reader.take(len as usize)?.to_vec(),
+        })
+    }
+
+    pub(crate) fn write(&self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        self.profile.write(writer)?;
+
+        writer.u28(self.data.len())?.bytes(&self.data).map(|_| ())
+    }
+}
@@ -0,0 +1,378 @@
+// =============================================================================
+// Property
+// =============================================================================
+
+//! Property Exchange message bodies and chunking.
+//!
+//! The [`property`](crate::property) module contains the bodies of the MIDI-CI
+//! Property Exchange messages **([M2-101-UM])**, by which Devices get, set and
+//! subscribe to Properties. Property Exchange messages carry a header and
+//! Property data (both usually JSON), which may be larger than the receiver
+//! can accept in a single System Exclusive message -- [`PropertyChunk::split`]
+//! splits a header and data into a sequence of chunks, and an [`Assembler`]
+//! joins received chunks into a complete [`Property`].
+
+use crate::{
+    bytes::{
+        Reader,
+        Writer,
+    },
+    message::Muid,
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+// The bytes of a Property Exchange message other than the header and Property
+// data: the Start and End bytes, the MIDI-CI header (13), and the Request ID,
+// Header Length, Number of Chunks, Number of This Chunk and Property Data
+// Length fields (9).
+const OVERHEAD: usize = 24;
+
+// -----------------------------------------------------------------------------
+
+// Bodies
+
+// Capabilities
+
+/// The body of the Inquiry: Property Exchange Capabilities message and its
+/// reply.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Capabilities {
+    /// The number of simultaneous Property Exchange requests supported.
+    pub simultaneous_requests: u8,
+    /// The major version of Property Exchange supported.
+    pub major_version: u8,
+    /// The minor version of Property Exchange supported.
+    pub minor_version: u8,
+}
+
+impl Capabilities {
+    pub(crate) fn try_read(reader: &mut Reader<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            simultaneous_requests: reader.u7()?,
+            major_version: reader.optional_u7()?,
+            minor_version: reader.optional_u7()?,
+        })
+    }
+
+    pub(crate) fn write(self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        writer
+            .bytes(&[
+                self.simultaneous_requests,
+                self.major_version,
+                self.minor_version,
+            ])
+            .map(|_| ())
+    }
+}
+
+// Property Chunk
+
+/// The body of a Property Exchange message (other than the capabilities
+/// messages), carrying one chunk of a header and Property data.
+///
+/// The header is sent in the first chunk only, and is empty in the following
+/// chunks.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PropertyChunk {
+    /// The ID of the request, shared by the chunks of a request, and by the
+    /// reply to the request.
+    pub request_id: u8,
+    /// The header (or part of the header) of the request.
+    pub header: Vec<u8>,
+    /// The number of chunks of the Property data.
+    pub chunk_count: u16,
+    /// The number of this chunk (from 1).
+    pub chunk_number: u16,
+    /// The Property data of this chunk.
+    pub data: Vec<u8>,
+}
+
+impl PropertyChunk {
+    /// Splits a header and Property data into chunks, such that each Property
+    /// Exchange message is no larger than the given maximum System Exclusive
+    /// message size (including the Start and End bytes), as given by the
+    /// receiver in its Discovery messages.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the maximum size is too small to send the header
+    /// and at least one byte of data in a message, or if more than 16383
+    /// chunks would be needed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_ci::*;
+    /// # use midi_2_ci::message::*;
+    /// # use midi_2_ci::property::*;
+    /// #
+    /// let header = br#"{"resource":"DeviceInfo"}"#;
+    /// let data = [0x20; 100];
+    ///
+    /// let chunks = PropertyChunk::split(1, header, &data, 128)?;
+    ///
+    /// assert_eq!(chunks.len(), 2);
+    /// assert_eq!(chunks[0].header, header);
+    /// assert!(chunks[1].header.is_empty());
+    ///
+    /// let source = Muid::try_new(0x01)?;
+    /// let mut assembler = Assembler::new();
+    ///
+    /// assert_eq!(assembler.push(source, &chunks[0])?, None);
+    ///
+    /// let property = assembler.push(source, &chunks[1])?.unwrap();
+    ///
+    /// assert_eq!(property.header, header);
+    /// assert_eq!(property.data, data);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn split(
+        request_id: u8,
+        header: &[u8],
+        data: &[u8],
+        max_sysex_size: usize,
+    ) -> Result<Vec<Self>, Error> {
+        let size = match max_sysex_size.checked_sub(OVERHEAD + header.len()) {
+            Some(size) if size > 0 => size,
+            _ => return Err(Error::size(OVERHEAD + header.len() + 1, max_sysex_size)),
+        };
+
+        let count = ((data.len() + size - 1) / size).max(1);
+        let chunk_count = match u16::try_from(count) {
+            Ok(chunk_count) if chunk_count <= 0x3fff => chunk_count,
+            _ => return Err(Error::overflow(count, 14)),
+        };
+
+        Ok((0..chunk_count)
+            .map(|index| {
+                let start = usize::from(index) * size;
+
+                Self {
+                    request_id,
+                    header: match index {
+                        0 => header.to_vec(),
+                        _ => Vec::new(),
+                    },
+                    chunk_count,
+                    chunk_number: index + 1,
+                    data: data[start..(start + size).min(data.len())].to_vec(),
+                }
+            })
+            .collect())
+    }
+
+    pub(crate) fn try_read(reader: &mut Reader<'_>) -> Result<Self, Error> {
+        let request_id = reader.u7()?;
+        let header_len = reader.u14()?;
+        let header = reader.take(usize::from(header_len))?.to_vec();
+        let chunk_count = reader.u14()?;
+        let chunk_number = reader.u14()?;
+        let data_len = reader.u14()?;
+
+        Ok(Self {
+            request_id,
+            header,
+            chunk_count,
+            chunk_number,
+            data: reader.take(usize::from(data_len))?.to_vec(),
+        })
+    }
+
+    pub(crate) fn write(&self, writer: &mut Writer<'_>) -> Result<(), Error> {
+        writer
+            .u7(self.request_id)?
+            .u14(self.header.len())?
+            .bytes(&self.header)?
+            .u14(usize::from(self.chunk_count))?
+            .u14(usize::from(self.chunk_number))?
+            .u14(self.data.len())?
+            .bytes(&self.data)
+            .map(|_| ())
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Property
+
+/// A complete header and Property data, joined from a sequence of chunks.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Property {
+    /// The ID of the request.
+    pub request_id: u8,
+    /// The header of the request.
+    pub header: Vec<u8>,
+    /// The Property data.
+    pub data: Vec<u8>,
+}
+
+impl Property {
+    fn len(&self) -> usize {
+        self.header.len() + self.data.len()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Assembler
+
+/// Joins received chunks into complete Properties.
+///
+/// Chunks are joined independently for each source MUID and Request ID, so
+/// that interleaved requests are handled correctly. Each chunk must follow the
+/// previous chunk of the same request.
+///
+/// By default, Properties of any size are assembled. Where chunks are received
+/// from untrusted (or unreliable) sources, an assembler created with
+/// [`with_limits`](Assembler::with_limits) bounds the size of each Property,
+/// and the total size of the incomplete Properties held for each source, so
+/// that a source which never completes its requests cannot exhaust memory, and
+/// a source flooding the assembler cannot starve the others.
+#[derive(Clone, Debug)]
+pub struct Assembler {
+    pending: Vec<(Muid, u16, Property)>,
+    max_len: usize,
+    budget: usize,
+}
+
+impl Assembler {
+    /// Creates a new [`Assembler`], with no incomplete Properties, and no
+    /// limits on the size of Properties.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_limits(usize::MAX, usize::MAX)
+    }
+
+    /// Creates a new [`Assembler`], with no incomplete Properties, which
+    /// accepts Properties of at most the given maximum length (in bytes of
+    /// header and Property data), and holds at most the given budget of bytes
+    /// in the incomplete Properties of each source (across all of its Request
+    /// IDs).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_ci::*;
+    /// # use midi_2_ci::message::*;
+    /// # use midi_2_ci::property::*;
+    /// #
+    /// let chunks = PropertyChunk::split(1, b"{}", &[0x20; 100], 64)?;
+    ///
+    /// let source = Muid::try_new(0x01)?;
+    /// let mut assembler = Assembler::with_limits(64, 1024);
+    ///
+    /// assert_eq!(assembler.push(source, &chunks[0])?, None);
+    ///
+    /// // the next chunk would exceed the maximum length
+    /// assert!(matches!(
+    ///     assembler.push(source, &chunks[1]),
+    ///     Err(Error::Limit { limit: 64, needed: 78 })
+    /// ));
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[must_use]
+    pub const fn with_limits(max_len: usize, budget: usize) -> Self {
+        Self {
+            pending: Vec::new(),
+            max_len,
+            budget,
+        }
+    }
+
+    /// Pushes a chunk received from the given source to the assembler,
+    /// returning the complete Property if the chunk is the last chunk.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the chunk is out of sequence (a first chunk
+    /// while the request is incomplete, a chunk which does not follow the
+    /// previous chunk, or a chunk number larger than the number of chunks), or
+    /// if the chunk would exceed the maximum Property length or the budget of
+    /// the assembler. On error, the chunk and any incomplete Property for the
+    /// same source and Request ID are discarded.
+    pub fn push(&mut self, source: Muid, chunk: &PropertyChunk) -> Result<Option<Property>, Error> {
+        let pending = self.pending.iter().position(|(muid, _, property)| {
+            *muid == source && property.request_id == chunk.request_id
+        });
+
+        let sequence = Error::sequence(chunk.chunk_number);
+
+        if chunk.chunk_number == 0 || chunk.chunk_number > chunk.chunk_count {
+            return Err(self.discard(pending, sequence));
+        }
+
+        let len = match pending {
+            Some(index) => self.pending[index].2.len(),
+            None => 0,
+        } + chunk.header.len()
+            + chunk.data.len();
+
+        if len > self.max_len {
+            return Err(self.discard(pending, Error::limit(self.max_len, len)));
+        }
+
+        if chunk.chunk_number < chunk.chunk_count {
+            let retained = self.retained(source) + chunk.header.len() + chunk.data.len();
+
+            if retained > self.budget {
+                return Err(self.discard(pending, Error::limit(self.budget, retained)));
+            }
+        }
+
+        let mut property = match (chunk.chunk_number, pending) {
+            (1, None) => Property {
+                request_id: chunk.request_id,
+                header: chunk.header.clone(),
+                data: Vec::new(),
+            },
+            (number, Some(index)) if number == self.pending[index].1 => {
+                self.pending.swap_remove(index).2
+            }
+            _ => return Err(self.discard(pending, sequence)),
+        };
+
+        property.data.extend_from_slice(&chunk.data);
+
+        if chunk.chunk_number == chunk.chunk_count {
+            return Ok(Some(property));
+        }
+
+        self.pending
+            .push((source, chunk.chunk_number + 1, property));
+
+        Ok(None)
+    }
+
+    /// Discards all incomplete Properties.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    fn retained(&self, source: Muid) -> usize {
+        self.pending
+            .iter()
+            .filter(|(muid, _, _)| *muid == source)
+            .map(|(_, _, property)| property.len())
+            .sum()
+    }
+
+    fn discard(&mut self, pending: Option<usize>, error: Error) -> Error {
+        if let Some(index) = pending {
+            self.pending.swap_remove(index);
+        }
+
+        error
+    }
+}
+
+impl Default for Assembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
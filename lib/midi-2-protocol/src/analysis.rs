@@ -0,0 +1,272 @@
+// =============================================================================
+// Analysis
+// =============================================================================
+
+//! Analysis of note streams.
+//!
+//! The [`analysis`](crate::analysis) module provides chord detection over a
+//! stream of timed Note On events, grouping notes played (near) simultaneously
+//! on the same group and channel, and naming the resulting chords.
+//!
+//! Chord types are numbered as in the Chord Type field of the Set Chord Name
+//! Flex Data message **([M2-104-UM 7.5])**, and chord names are spelled in the
+//! context of a [`KeySignature`](crate::notation::KeySignature).
+
+#[cfg(feature = "fmt")]
+use alloc::{
+    format,
+    string::String,
+};
+use alloc::vec::Vec;
+
+#[cfg(feature = "fmt")]
+use crate::notation::KeySignature;
+use crate::{
+    message::{
+        voice::{
+            Channel,
+            Note,
+            Voice,
+        },
+        Group,
+        Message,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const TEMPLATES: [(ChordType, &[u8]); 17] = [
+    (ChordType::Major, &[0, 4, 7]),
+    (ChordType::Minor, &[0, 3, 7]),
+    (ChordType::Dominant, &[0, 4, 7, 10]),
+    (ChordType::Major7, &[0, 4, 7, 11]),
+    (ChordType::Minor7, &[0, 3, 7, 10]),
+    (ChordType::Major6, &[0, 4, 7, 9]),
+    (ChordType::Minor6, &[0, 3, 7, 9]),
+    (ChordType::Augmented, &[0, 4, 8]),
+    (ChordType::Augmented7, &[0, 4, 8, 10]),
+    (ChordType::Diminished, &[0, 3, 6]),
+    (ChordType::Diminished7, &[0, 3, 6, 9]),
+    (ChordType::HalfDiminished, &[0, 3, 6, 10]),
+    (ChordType::MajorMinor, &[0, 3, 7, 11]),
+    (ChordType::Power, &[0, 7]),
+    (ChordType::Suspended2, &[0, 2, 7]),
+    (ChordType::Suspended4, &[0, 5, 7]),
+    (ChordType::SeventhSuspended4, &[0, 5, 7, 10]),
+];
+
+// -----------------------------------------------------------------------------
+
+// Chord Type
+
+/// The type of a detected chord, with values as given for the Chord Type field
+/// of the Set Chord Name message.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum ChordType {
+    Major = 0x01,
+    Major6 = 0x02,
+    Major7 = 0x03,
+    Minor = 0x07,
+    Minor6 = 0x08,
+    Minor7 = 0x09,
+    Dominant = 0x0d,
+    Augmented = 0x11,
+    Augmented7 = 0x12,
+    Diminished = 0x13,
+    Diminished7 = 0x14,
+    HalfDiminished = 0x15,
+    MajorMinor = 0x16,
+    Power = 0x18,
+    Suspended2 = 0x19,
+    Suspended4 = 0x1a,
+    SeventhSuspended4 = 0x1b,
+}
+
+impl ChordType {
+    #[cfg(feature = "fmt")]
+    const fn suffix(self) -> &'static str {
+        match self {
+            Self::Major => "",
+            Self::Major6 => "6",
+            Self::Major7 => "maj7",
+            Self::Minor => "m",
+            Self::Minor6 => "m6",
+            Self::Minor7 => "m7",
+            Self::Dominant => "7",
+            Self::Augmented => "+",
+            Self::Augmented7 => "+7",
+            Self::Diminished => "dim",
+            Self::Diminished7 => "dim7",
+            Self::HalfDiminished => "m7b5",
+            Self::MajorMinor => "m(maj7)",
+            Self::Power => "5",
+            Self::Suspended2 => "sus2",
+            Self::Suspended4 => "sus4",
+            Self::SeventhSuspended4 => "7sus4",
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Chord
+
+/// A chord detected in a stream of Note On events.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Chord {
+    /// The time of the first note of the chord.
+    pub time: u64,
+    pub group: Group,
+    pub channel: Channel,
+    /// The notes of the chord, in ascending order.
+    pub notes: Vec<Note>,
+    /// The root of the chord, as a pitch class (0 for C, to 11 for B).
+    pub root: u8,
+    /// The lowest note of the chord, as a pitch class.
+    pub bass: u8,
+    pub chord_type: ChordType,
+}
+
+impl Chord {
+    /// Returns the name of the chord (e.g. `"Bbm7"`, or `"C/E"` for an
+    /// inversion), spelled in the given key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::analysis::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::voice::*;
+    /// # use midi_2_protocol::notation::*;
+    /// #
+    /// let events = [
+    ///     (0, Group::G1, Channel::C1, Note::new(64)),
+    ///     (2, Group::G1, Channel::C1, Note::new(67)),
+    ///     (3, Group::G1, Channel::C1, Note::new(72)),
+    ///     (1000, Group::G1, Channel::C1, Note::new(58)),
+    ///     (1000, Group::G1, Channel::C1, Note::new(61)),
+    ///     (1001, Group::G1, Channel::C1, Note::new(65)),
+    ///     (1001, Group::G1, Channel::C1, Note::new(68)),
+    /// ];
+    ///
+    /// let key = KeySignature::try_new(-2, Some(Letter::B))?;
+    /// let chords = chords(events, 10);
+    ///
+    /// assert_eq!(chords[0].name(&key), "C/E");
+    /// assert_eq!(chords[1].name(&key), "Bbm7");
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[cfg(feature = "fmt")]
+    #[must_use]
+    pub fn name(&self, key: &KeySignature) -> String {
+        let root = key.spell(Note::new(self.root));
+        let suffix = self.chord_type.suffix();
+
+        if self.bass == self.root {
+            format!("{root}{suffix}")
+        } else {
+            format!("{root}{suffix}/{}", key.spell(Note::new(self.bass)))
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+/// Detects chords in a stream of timed Note On events, given as the time (in
+/// any unit), group, channel and note of each Note On, in time order.
+///
+/// Notes on the same group and channel which begin within the given window of
+/// the first note of a chord (in the same unit as the event times) are treated
+/// as simultaneous. Groups of simultaneous notes which do not form a known
+/// chord type are ignored. Detected chords are returned in time order.
+pub fn chords<I>(events: I, window: u64) -> Vec<Chord>
+where
+    I: IntoIterator<Item = (u64, Group, Channel, Note)>,
+{
+    let mut open: Vec<(u64, Group, Channel, Vec<Note>)> = Vec::new();
+    let mut chords = Vec::new();
+
+    for (time, group, channel, note) in events {
+        if let Some(position) = open.iter().position(|(start, g, c, _)| {
+            (*g, *c) == (group, channel) && time > start.saturating_add(window)
+        }) {
+            let (start, group, channel, notes) = open.remove(position);
+
+            chords.extend(detect(start, group, channel, notes));
+        }
+
+        match open
+            .iter_mut()
+            .find(|(_, g, c, _)| (*g, *c) == (group, channel))
+        {
+            Some((_, _, _, notes)) => notes.push(note),
+            None => open.push((time, group, channel, alloc::vec![note])),
+        }
+    }
+
+    for (start, group, channel, notes) in open {
+        chords.extend(detect(start, group, channel, notes));
+    }
+
+    chords.sort_by_key(|chord| chord.time);
+    chords
+}
+
+/// Returns the group, channel and note of a Note On message, or `None` for
+/// any other message, for use in building an event stream for [`chords`].
+///
+/// # Errors
+///
+/// Returns an [`Error`](crate::Error) if the fields of the message cannot be
+/// read.
+pub fn try_note_on(message: &Message<'_>) -> Result<Option<(Group, Channel, Note)>, Error> {
+    match message {
+        Message::Voice(Voice::NoteOn(note_on)) => Ok(Some((
+            note_on.group()?,
+            note_on.channel()?,
+            note_on.note()?,
+        ))),
+        _ => Ok(None),
+    }
+}
+
+fn detect(time: u64, group: Group, channel: Channel, mut notes: Vec<Note>) -> Option<Chord> {
+    notes.sort_unstable();
+    notes.dedup();
+
+    let bass = u8::from(*notes.first()?) % 12;
+    let classes = notes
+        .iter()
+        .fold(0u16, |classes, note| classes | 1 << (u8::from(*note) % 12));
+
+    // Roots are tried starting from the bass, so that ambiguous sets (such as
+    // C6 and Am7) are named in root position where possible.
+
+    (0..12)
+        .map(|offset| (bass + offset) % 12)
+        .filter(|root| classes & 1 << root != 0)
+        .find_map(|root| {
+            let intervals = (classes >> root | classes << (12 - root)) & 0x0fff;
+
+            TEMPLATES
+                .iter()
+                .find(|(_, template)| intervals == template.iter().fold(0, |set, i| set | 1 << i))
+                .map(|(chord_type, _)| Chord {
+                    time,
+                    group,
+                    channel,
+                    notes: notes.clone(),
+                    root,
+                    bass,
+                    chord_type: *chord_type,
+                })
+        })
+}
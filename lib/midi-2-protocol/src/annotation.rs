@@ -0,0 +1,155 @@
+// =============================================================================
+// Annotation
+// =============================================================================
+
+//! Annotation of messages with tooling metadata.
+//!
+//! The [`annotation`](crate::annotation) module provides an [`Annotated`]
+//! wrapper, which carries provenance metadata (the source of a message, the
+//! time at which it was received, and the transport over which it arrived)
+//! alongside a message or packet as it is passed between components, without
+//! altering the wire representation of the message itself.
+
+use core::ops::{
+    Deref,
+    DerefMut,
+};
+
+// -----------------------------------------------------------------------------
+
+// Values
+
+// Source
+
+/// An application-defined identifier for the source of a message (e.g. an
+/// index into a list of connected devices or ports).
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Source(pub u32);
+
+// Transport
+
+/// The transport over which a message was received.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum Transport {
+    #[default]
+    Unknown,
+    Usb,
+    Network,
+    Serial,
+    Virtual,
+    Other(&'static str),
+}
+
+// -----------------------------------------------------------------------------
+
+// Annotated
+
+/// A value (typically a [`Packet`](crate::packet::Packet)) annotated with
+/// provenance metadata.
+///
+/// An [`Annotated`] value dereferences to the wrapped value, so it can be used
+/// in place of the value in most contexts. Annotations are created with no
+/// metadata, which may be added using builder-style `set_*` functions.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # #[cfg(feature = "system")]
+/// # {
+/// # use midi_2_protocol::annotation::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::packet::*;
+/// #
+/// let packet = Packet::try_read(&[0x10f80000])?;
+/// let mut annotated = Annotated::new(packet)
+///     .set_source(Source(2))
+///     .set_received(1_000_000)
+///     .set_transport(Transport::Usb);
+///
+/// // the annotated packet can be used as a packet...
+/// assert!(matches!(annotated.try_message()?, Message::System(_)));
+///
+/// // ...and the annotations are available to monitors
+/// assert_eq!(annotated.source, Source(2));
+/// assert_eq!(annotated.received, Some(1_000_000));
+/// # }
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Annotated<T> {
+    pub value: T,
+    pub source: Source,
+    pub received: Option<u64>,
+    pub transport: Transport,
+}
+
+impl<T> Annotated<T> {
+    /// Creates a new [`Annotated`] value with no metadata.
+    #[must_use]
+    pub const fn new(value: T) -> Self {
+        Self {
+            value,
+            source: Source(0),
+            received: None,
+            transport: Transport::Unknown,
+        }
+    }
+
+    /// Sets the (application-defined) source from which the value was
+    /// received.
+    #[must_use]
+    pub const fn set_source(mut self, source: Source) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Sets the host time (in nanoseconds, from any monotonic clock) at which
+    /// the value was received.
+    #[must_use]
+    pub const fn set_received(mut self, received: u64) -> Self {
+        self.received = Some(received);
+        self
+    }
+
+    /// Sets the transport over which the value was received.
+    #[must_use]
+    pub const fn set_transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Maps the wrapped value (e.g. when translating or filtering a message)
+    /// while retaining the metadata.
+    pub fn map<U, F>(self, f: F) -> Annotated<U>
+    where
+        F: FnOnce(T) -> U,
+    {
+        Annotated {
+            value: f(self.value),
+            source: self.source,
+            received: self.received,
+            transport: self.transport,
+        }
+    }
+
+    /// Discards the metadata, returning the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for Annotated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for Annotated<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
@@ -0,0 +1,298 @@
+// =============================================================================
+// Archive
+// =============================================================================
+
+//! Immutable storage of large captured streams.
+//!
+//! The [`archive`](crate::archive) module provides a [`PacketArchive`], which
+//! stores a captured stream of UMP words in immutable, reference-counted
+//! chunks, so that an archive (or any slice of it) can be cloned in constant
+//! time and handed to other threads without copying the captured data, and an
+//! [`ArchiveBuilder`], which builds an archive from words as they are
+//! captured.
+
+use alloc::{
+    sync::Arc,
+    vec::Vec,
+};
+use core::{
+    mem,
+    ops::Range,
+};
+
+use crate::{
+    message::{
+        MessageIter,
+        MessageView,
+    },
+    packet,
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const DEFAULT_CHUNK_LEN: usize = 0x10000;
+
+// -----------------------------------------------------------------------------
+
+// Chunk
+
+#[derive(Clone, Debug)]
+struct Chunk {
+    offset: usize,
+    words: Arc<[u32]>,
+}
+
+// -----------------------------------------------------------------------------
+
+// Archive Builder
+
+/// Builds a [`PacketArchive`] from words as they are captured.
+///
+/// Words are collected into chunks of (at least) the configured length, which
+/// are sealed at message boundaries, so that no message is split between
+/// chunks. Words may be pushed in any number of parts, and a message may be
+/// split between pushes.
+#[derive(Clone, Debug)]
+pub struct ArchiveBuilder {
+    chunk_len: usize,
+    chunks: Vec<Chunk>,
+    current: Vec<u32>,
+    boundary: usize,
+    sealed: usize,
+}
+
+impl ArchiveBuilder {
+    /// Creates a new [`ArchiveBuilder`], sealing chunks once they hold at
+    /// least the given number of words (a length of 0 is treated as 1).
+    #[must_use]
+    pub const fn new(chunk_len: usize) -> Self {
+        Self {
+            chunk_len: if chunk_len == 0 { 1 } else { chunk_len },
+            chunks: Vec::new(),
+            current: Vec::new(),
+            boundary: 0,
+            sealed: 0,
+        }
+    }
+
+    /// Appends the given words to the archive being built.
+    pub fn push(&mut self, words: &[u32]) {
+        self.current.extend_from_slice(words);
+
+        while let Some(&word) = self.current.get(self.boundary) {
+            let len = packet::word_count(word);
+
+            if self.boundary + len > self.current.len() {
+                break;
+            }
+
+            self.boundary += len;
+
+            if self.boundary >= self.chunk_len {
+                self.seal();
+            }
+        }
+    }
+
+    /// Finishes the archive, including any trailing incomplete message (which
+    /// is stored as captured, and reported as truncated when iterated).
+    #[must_use]
+    pub fn finish(mut self) -> PacketArchive {
+        if !self.current.is_empty() {
+            self.boundary = self.current.len();
+            self.seal();
+        }
+
+        PacketArchive {
+            chunks: self.chunks.into(),
+            range: 0..self.sealed,
+        }
+    }
+
+    fn seal(&mut self) {
+        let rest = self.current.split_off(self.boundary);
+        let words = mem::replace(&mut self.current, rest);
+
+        self.chunks.push(Chunk {
+            offset: self.sealed,
+            words: words.into(),
+        });
+        self.sealed += self.boundary;
+        self.boundary = 0;
+    }
+}
+
+impl Default for ArchiveBuilder {
+    fn default() -> Self {
+        Self::new(DEFAULT_CHUNK_LEN)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Packet Archive
+
+/// An immutable archive of captured UMP words, with cheap cloning and slicing.
+///
+/// The words of an archive are held in reference-counted chunks, which are
+/// shared (rather than copied) by clones and slices of the archive, so that
+/// views of a capture can be handed to worker threads in constant time. An
+/// archive is built with an [`ArchiveBuilder`], or from a `Vec<u32>` (using
+/// the default chunk length).
+///
+/// Slices are given as ranges of word offsets within the archive (or slice)
+/// being sliced. Messages are only parsed correctly by
+/// [`messages`](PacketArchive::messages) when a slice begins on a message
+/// boundary -- the offsets of message boundaries are given by the
+/// [`packet::word_count`] of each message.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(all(feature = "system", feature = "voice"))]
+/// # {
+/// # use std::thread;
+/// # use midi_2_protocol::archive::*;
+/// #
+/// let mut builder = ArchiveBuilder::new(4);
+///
+/// for _ in 0..1000 {
+///     builder.push(&[0x40903c00, 0xffff0000, 0x10f80000]);
+/// }
+///
+/// let archive = builder.finish();
+///
+/// assert_eq!(archive.len(), 3000);
+///
+/// // hand each half of the capture to a worker thread, without copying
+/// let (first, second) = archive.split_at(1500);
+/// let workers = [first, second].map(|half| {
+///     thread::spawn(move || half.messages().filter(Result::is_ok).count())
+/// });
+/// let counts = workers.map(|worker| worker.join().unwrap());
+///
+/// assert_eq!(counts, [1000, 1000]);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct PacketArchive {
+    chunks: Arc<[Chunk]>,
+    range: Range<usize>,
+}
+
+impl PacketArchive {
+    /// Returns the number of words in the archive.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.range.end - self.range.start
+    }
+
+    /// Returns `true` if the archive contains no words.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the word at the given offset, or `None` if the offset is out of
+    /// bounds.
+    #[must_use]
+    pub fn get(&self, offset: usize) -> Option<u32> {
+        let offset = self.range.start.checked_add(offset)?;
+
+        if offset >= self.range.end {
+            return None;
+        }
+
+        let chunk = &self.chunks[self.chunk_index(offset)];
+
+        chunk.words.get(offset - chunk.offset).copied()
+    }
+
+    /// Returns a slice of the archive over the given range of word offsets, or
+    /// `None` if the range is out of bounds. The slice shares the chunks of
+    /// the archive.
+    #[must_use]
+    pub fn slice(&self, range: Range<usize>) -> Option<Self> {
+        if range.start > range.end || range.end > self.len() {
+            return None;
+        }
+
+        Some(Self {
+            chunks: Arc::clone(&self.chunks),
+            range: self.range.start + range.start..self.range.start + range.end,
+        })
+    }
+
+    /// Divides the archive into two slices at the given word offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the offset is greater than the length of the archive.
+    #[must_use]
+    pub fn split_at(&self, mid: usize) -> (Self, Self) {
+        match (self.slice(0..mid), self.slice(mid..self.len())) {
+            (Some(first), Some(second)) => (first, second),
+            _ => panic!(
+                "offset {mid} out of bounds for archive of {} words",
+                self.len()
+            ),
+        }
+    }
+
+    /// Returns an iterator over the contiguous parts of the archive (the parts
+    /// of each chunk within the archive), in order.
+    pub fn chunks(&self) -> impl Iterator<Item = &[u32]> + '_ {
+        let first = self.chunk_index(self.range.start);
+
+        self.chunks[first..]
+            .iter()
+            .take_while(move |chunk| chunk.offset < self.range.end)
+            .map(move |chunk| {
+                let start = self.range.start.saturating_sub(chunk.offset);
+                let end = (self.range.end - chunk.offset).min(chunk.words.len());
+
+                &chunk.words[start..end]
+            })
+            .filter(|words| !words.is_empty())
+    }
+
+    /// Returns an iterator over the messages of the archive, yielding a
+    /// read-only [`MessageView`] for each message, or an error for each
+    /// message which cannot be parsed (as for [`MessageIter`]).
+    pub fn messages(&self) -> impl Iterator<Item = Result<MessageView<'_>, Error>> {
+        self.chunks().flat_map(MessageIter::new)
+    }
+
+    /// Copies the words of the archive into a new `Vec<u32>`.
+    #[must_use]
+    pub fn to_vec(&self) -> Vec<u32> {
+        self.chunks().flatten().copied().collect()
+    }
+
+    fn chunk_index(&self, offset: usize) -> usize {
+        self.chunks
+            .partition_point(|chunk| chunk.offset <= offset)
+            .saturating_sub(1)
+    }
+}
+
+impl Default for PacketArchive {
+    fn default() -> Self {
+        Self {
+            chunks: Vec::new().into(),
+            range: 0..0,
+        }
+    }
+}
+
+impl From<Vec<u32>> for PacketArchive {
+    fn from(words: Vec<u32>) -> Self {
+        let mut builder = ArchiveBuilder::default();
+
+        builder.push(&words);
+        builder.finish()
+    }
+}
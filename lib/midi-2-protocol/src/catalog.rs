@@ -0,0 +1,252 @@
+// =============================================================================
+// Catalog
+// =============================================================================
+
+//! Metadata for standard controllers.
+//!
+//! The [`catalog`](crate::catalog) module provides a table of the standard
+//! Control Change numbers, Registered Controllers and Registered Per-Note
+//! Controllers, giving the name, default value and polarity of each (and
+//! whether a Control Change is also available as a Registered Per-Note
+//! Controller), so that user interfaces can label controls and state trackers
+//! can initialize controllers to sensible values.
+//!
+//! Default values are given as 32-bit controller values, upscaled from the
+//! usual MIDI 1.0 reset values where applicable (e.g. a Channel Volume of 100
+//! is `0xc9249249`).
+
+use crate::{
+    learn::Descriptor,
+    message::voice::{
+        Bank,
+        Controller,
+        Index,
+        PerNoteController,
+    },
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const ZERO: u32 = 0x0000_0000;
+const CENTER: u32 = 0x8000_0000;
+const MAX: u32 = 0xffff_ffff;
+
+static CONTROL_CHANGES: [(u8, ControllerInfo); 23] = [
+    (1, unipolar("Modulation Wheel", ZERO).per_note()),
+    (2, unipolar("Breath Controller", ZERO).per_note()),
+    (4, unipolar("Foot Controller", ZERO)),
+    (5, unipolar("Portamento Time", ZERO)),
+    (7, unipolar("Channel Volume", 0xc924_9249).per_note()),
+    (8, bipolar("Balance").per_note()),
+    (10, bipolar("Pan").per_note()),
+    (11, unipolar("Expression", MAX).per_note()),
+    (64, unipolar("Sustain", ZERO)),
+    (65, unipolar("Portamento", ZERO)),
+    (66, unipolar("Sostenuto", ZERO)),
+    (67, unipolar("Soft Pedal", ZERO)),
+    (71, bipolar("Resonance").per_note()),
+    (72, bipolar("Release Time").per_note()),
+    (73, bipolar("Attack Time").per_note()),
+    (74, bipolar("Brightness").per_note()),
+    (75, bipolar("Decay Time").per_note()),
+    (76, bipolar("Vibrato Rate").per_note()),
+    (77, bipolar("Vibrato Depth").per_note()),
+    (78, bipolar("Vibrato Delay").per_note()),
+    (84, unipolar("Portamento Control", ZERO)),
+    (91, unipolar("Reverb Send Level", 0x5000_0000).per_note()),
+    (93, unipolar("Chorus Send Level", ZERO).per_note()),
+];
+
+static REGISTERED_CONTROLLERS: [((u8, u8), ControllerInfo); 6] = [
+    ((0, 0), unipolar("Pitch Bend Sensitivity", 0x0400_0000)),
+    ((0, 1), bipolar("Channel Fine Tuning")),
+    ((0, 2), bipolar("Channel Coarse Tuning")),
+    ((0, 3), unipolar("Tuning Program Change", ZERO)),
+    ((0, 4), unipolar("Tuning Bank Select", ZERO)),
+    ((0, 5), unipolar("Modulation Depth Range", 0x0100_0000)),
+];
+
+// Registered Per-Note Controllers which have no equivalent Control Change.
+// The default pitch of a note is the note number itself, so the default of
+// Pitch 7.25 depends on the note, and is given as zero.
+
+static PER_NOTE_CONTROLLERS: [(u8, ControllerInfo); 1] =
+    [(3, unipolar("Pitch 7.25", ZERO).per_note())];
+
+// -----------------------------------------------------------------------------
+
+// Polarity
+
+/// The polarity of a controller.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Polarity {
+    /// Values range from zero (no effect) upwards.
+    Unipolar,
+    /// Values range either side of a center value (no effect), which is the
+    /// default value of the controller.
+    Bipolar,
+}
+
+// -----------------------------------------------------------------------------
+
+// Controller Info
+
+/// Metadata for a standard controller.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ControllerInfo {
+    pub name: &'static str,
+    /// The default value, as a 32-bit controller value (or zero where the
+    /// default depends on the note, as for Pitch 7.25).
+    pub default: u32,
+    pub polarity: Polarity,
+    /// Whether the controller is also available as a Registered Per-Note
+    /// Controller (with the same number as the Control Change).
+    pub per_note: bool,
+}
+
+impl ControllerInfo {
+    const fn per_note(mut self) -> Self {
+        self.per_note = true;
+        self
+    }
+}
+
+const fn unipolar(name: &'static str, default: u32) -> ControllerInfo {
+    ControllerInfo {
+        name,
+        default,
+        polarity: Polarity::Unipolar,
+        per_note: false,
+    }
+}
+
+const fn bipolar(name: &'static str) -> ControllerInfo {
+    ControllerInfo {
+        name,
+        default: CENTER,
+        polarity: Polarity::Bipolar,
+        per_note: false,
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+/// Returns the metadata for the given Control Change index, or `None` if the
+/// index is not a standard controller.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::catalog::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// let pan = control_change(Index::new(10)).unwrap();
+///
+/// assert_eq!(pan.name, "Pan");
+/// assert_eq!(pan.polarity, Polarity::Bipolar);
+/// assert_eq!(pan.default, 0x80000000);
+///
+/// assert_eq!(control_change(Index::new(3)), None);
+/// ```
+#[must_use]
+pub fn control_change(index: Index) -> Option<&'static ControllerInfo> {
+    let index = u8::from(index);
+
+    CONTROL_CHANGES
+        .iter()
+        .find(|(number, _)| *number == index)
+        .map(|(_, info)| info)
+}
+
+/// Returns the metadata for the given Registered Controller (RPN), or `None`
+/// if the bank and controller are not a standard Registered Controller.
+#[must_use]
+pub fn registered_controller(
+    bank: Bank,
+    controller: Controller,
+) -> Option<&'static ControllerInfo> {
+    let key = (u8::from(bank), u8::from(controller));
+
+    REGISTERED_CONTROLLERS
+        .iter()
+        .find(|(number, _)| *number == key)
+        .map(|(_, info)| info)
+}
+
+/// Returns the metadata for the given Registered Per-Note Controller, or
+/// `None` if it is not a standard controller.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::catalog::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// let pitch = registered_per_note_controller(PerNoteController::new(3)).unwrap();
+///
+/// assert_eq!(pitch.name, "Pitch 7.25");
+/// assert_eq!(pitch.polarity, Polarity::Unipolar);
+///
+/// // sustain is not available per note
+/// assert_eq!(registered_per_note_controller(PerNoteController::new(64)), None);
+/// ```
+#[must_use]
+pub fn registered_per_note_controller(
+    controller: PerNoteController,
+) -> Option<&'static ControllerInfo> {
+    let controller = u8::from(controller);
+
+    CONTROL_CHANGES
+        .iter()
+        .chain(&PER_NOTE_CONTROLLERS)
+        .find(|(number, info)| *number == controller && info.per_note)
+        .map(|(_, info)| info)
+}
+
+/// Returns the metadata for the control described by the given
+/// [`Descriptor`], or `None` if it is not a standard controller.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::catalog::*;
+/// # use midi_2_protocol::learn::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// let descriptor = Descriptor::RegisteredController(
+///     Group::G1,
+///     Channel::C1,
+///     Bank::new(0),
+///     Controller::new(0),
+/// );
+///
+/// assert_eq!(describe(&descriptor).unwrap().name, "Pitch Bend Sensitivity");
+///
+/// // the default Modulation Depth Range is 50 cents (a 14-bit value of 64)
+/// let descriptor = Descriptor::RegisteredController(
+///     Group::G1,
+///     Channel::C1,
+///     Bank::new(0),
+///     Controller::new(5),
+/// );
+///
+/// assert_eq!(describe(&descriptor).unwrap().default, 0x01000000);
+/// ```
+#[must_use]
+pub fn describe(descriptor: &Descriptor) -> Option<&'static ControllerInfo> {
+    match *descriptor {
+        Descriptor::ControlChange(_, _, index) => control_change(index),
+        Descriptor::RegisteredController(_, _, bank, controller) => {
+            registered_controller(bank, controller)
+        }
+        Descriptor::RegisteredPerNoteController(_, _, controller) => {
+            registered_per_note_controller(controller)
+        }
+        _ => None,
+    }
+}
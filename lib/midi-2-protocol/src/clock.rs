@@ -0,0 +1,139 @@
+// =============================================================================
+// Clock
+// =============================================================================
+
+//! Sources of host time.
+//!
+//! Time-dependent types in this crate (such as the
+//! [`EchoSuppressor`](crate::echo::EchoSuppressor) and the
+//! [`Probe`](crate::latency::Probe)) take host times in nanoseconds as
+//! arguments, rather than reading a clock themselves. The [`Clock`] trait in
+//! the [`clock`](crate::clock) module gives applications a common source for
+//! those times, with a [`MonotonicClock`] for real use, and a deterministic
+//! [`MockClock`] which only advances when told to, so that time-dependent
+//! behaviour can be tested without sleeping.
+
+use std::{
+    cell::Cell,
+    time::Instant,
+};
+
+// -----------------------------------------------------------------------------
+
+// Clock
+
+/// A monotonic source of host time.
+pub trait Clock {
+    /// Returns the current host time, in nanoseconds since an arbitrary
+    /// (fixed) epoch. Successive values never decrease.
+    fn now(&self) -> u64;
+
+    /// Returns the resolution of the clock, in nanoseconds.
+    fn resolution(&self) -> u64;
+}
+
+// -----------------------------------------------------------------------------
+
+// Monotonic Clock
+
+/// A [`Clock`] backed by [`Instant`], with an epoch of the time the clock was
+/// created.
+#[derive(Clone, Copy, Debug)]
+pub struct MonotonicClock {
+    epoch: Instant,
+}
+
+impl MonotonicClock {
+    /// Creates a new [`MonotonicClock`], starting at zero.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+        }
+    }
+}
+
+impl Default for MonotonicClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MonotonicClock {
+    #[allow(clippy::cast_possible_truncation)]
+    fn now(&self) -> u64 {
+        self.epoch.elapsed().as_nanos() as u64
+    }
+
+    fn resolution(&self) -> u64 {
+        1
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Mock Clock
+
+/// A deterministic [`Clock`] for tests, which only advances when told to.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::clock::*;
+/// # use midi_2_protocol::echo::*;
+/// # use midi_2_protocol::packet::*;
+/// #
+/// let clock = MockClock::new(0);
+/// let mut suppressor = EchoSuppressor::new(5_000_000);
+///
+/// suppressor.sent(Packet::from([0x40903c00, 0xffff0000]), clock.now());
+/// clock.advance(10_000_000);
+///
+/// assert_eq!(clock.now(), 10_000_000);
+/// assert!(!suppressor.suppress(&Packet::from([0x40903c00, 0xffff0000]), clock.now()));
+/// ```
+#[derive(Debug, Default)]
+pub struct MockClock {
+    now: Cell<u64>,
+    resolution: u64,
+}
+
+impl MockClock {
+    /// Creates a new [`MockClock`] at the given time (in nanoseconds), with a
+    /// resolution of one nanosecond.
+    #[must_use]
+    pub const fn new(now: u64) -> Self {
+        Self::with_resolution(now, 1)
+    }
+
+    /// Creates a new [`MockClock`] at the given time, reporting the given
+    /// resolution (both in nanoseconds).
+    #[must_use]
+    pub const fn with_resolution(now: u64, resolution: u64) -> Self {
+        Self {
+            now: Cell::new(now),
+            resolution,
+        }
+    }
+
+    /// Advances the clock by the given duration (in nanoseconds).
+    pub fn advance(&self, nanos: u64) {
+        self.now.set(self.now.get().saturating_add(nanos));
+    }
+
+    /// Sets the clock to the given time (in nanoseconds). Times earlier than
+    /// the current time are ignored, so the clock remains monotonic.
+    pub fn set(&self, now: u64) {
+        self.now.set(self.now.get().max(now));
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> u64 {
+        self.now.get()
+    }
+
+    fn resolution(&self) -> u64 {
+        self.resolution
+    }
+}
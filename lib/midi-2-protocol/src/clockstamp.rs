@@ -0,0 +1,235 @@
+// =============================================================================
+// Clockstamp
+// =============================================================================
+
+//! Validation of Delta Clockstamps against the negotiated stream configuration.
+//!
+//! The [`clockstamp`](crate::clockstamp) module provides a
+//! [`ClockstampValidator`], which follows the configuration of a stream -- the
+//! JR Timestamp settings of the latest Stream Configuration Notification, and
+//! the ticks per quarter note of the latest Delta Clockstamp Ticks Per Quarter
+//! Note message -- and reports a [`Warning`] for each timing message which is
+//! inconsistent with that configuration, to help diagnose sequencer interop
+//! issues.
+
+use crate::{
+    message::{
+        stream::{
+            JitterReduction,
+            StreamView,
+        },
+        utility::UtilityView,
+        MessageView,
+    },
+    packet,
+};
+
+// -----------------------------------------------------------------------------
+
+// Warning
+
+/// An inconsistency between a timing message and the configuration of a
+/// stream.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Warning {
+    /// A Delta Clockstamp was received before any Delta Clockstamp Ticks Per
+    /// Quarter Note message, so the duration of its ticks is undefined.
+    MissingTicksPerQuarterNote,
+    /// A Delta Clockstamp Ticks Per Quarter Note message gave zero ticks per
+    /// quarter note.
+    ZeroTicksPerQuarterNote,
+    /// A Delta Clockstamp Ticks Per Quarter Note message gave a number of
+    /// ticks per quarter note other than the number expected.
+    TicksPerQuarterNote {
+        /// The expected number of ticks per quarter note.
+        expected: u16,
+        /// The received number of ticks per quarter note.
+        received: u16,
+    },
+    /// A JR Clock or JR Timestamp message was received, although the latest
+    /// Stream Configuration Notification disabled transmission of JR
+    /// Timestamps.
+    UnexpectedJitterReduction,
+}
+
+// -----------------------------------------------------------------------------
+
+// Clockstamp Validator
+
+/// Checks the timing messages of a received stream against the negotiated
+/// stream configuration.
+///
+/// The validator records the JR Timestamp settings of each Stream
+/// Configuration Notification, and the ticks per quarter note of each Delta
+/// Clockstamp Ticks Per Quarter Note message, and reports a [`Warning`] when:
+///
+/// - A Delta Clockstamp is received before the ticks per quarter note are
+///   known.
+/// - The ticks per quarter note are zero, or differ from the number expected
+///   (where the number was agreed out of band, for example by the file or
+///   session being played).
+/// - A JR Clock or JR Timestamp is received after a Stream Configuration
+///   Notification with JR Timestamp transmission disabled. JR messages are not
+///   checked until a Stream Configuration Notification has been received.
+///
+/// Each warning is counted, so that the number of warnings can be reported
+/// alongside other stream statistics.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::clockstamp::*;
+/// #
+/// let mut validator = ClockstampValidator::new().with_expected_ticks_per_quarter_note(96);
+///
+/// let words = [
+///     0x00400010,                                     // delta clockstamp
+///     0xf0060200, 0x00000000, 0x00000000, 0x00000000, // configuration, no jr
+///     0x00300060,                                     // 96 ticks per quarter note
+///     0x00400010,                                     // delta clockstamp
+///     0x00201234,                                     // jr timestamp
+///     0x003001e0,                                     // 480 ticks per quarter note
+/// ];
+///
+/// let mut warnings = Vec::new();
+///
+/// validator.scan(&words, |offset, warning| warnings.push((offset, warning)));
+///
+/// assert_eq!(
+///     warnings,
+///     [
+///         (0, Warning::MissingTicksPerQuarterNote),
+///         (7, Warning::UnexpectedJitterReduction),
+///         (8, Warning::TicksPerQuarterNote { expected: 96, received: 480 }),
+///     ]
+/// );
+/// assert_eq!(validator.ticks_per_quarter_note(), Some(480));
+/// assert_eq!(validator.warning_count(), 3);
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ClockstampValidator {
+    expected: Option<u16>,
+    ticks_per_quarter_note: Option<u16>,
+    jitter_reduction: Option<JitterReduction>,
+    warnings: u64,
+}
+
+impl ClockstampValidator {
+    /// Creates a new [`ClockstampValidator`], with no expected number of ticks
+    /// per quarter note, and no configuration received.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            expected: None,
+            ticks_per_quarter_note: None,
+            jitter_reduction: None,
+            warnings: 0,
+        }
+    }
+
+    /// Sets the number of ticks per quarter note which Delta Clockstamp Ticks
+    /// Per Quarter Note messages are expected to give.
+    #[must_use]
+    pub const fn with_expected_ticks_per_quarter_note(mut self, expected: u16) -> Self {
+        self.expected = Some(expected);
+        self
+    }
+
+    /// Returns the ticks per quarter note given by the latest Delta Clockstamp
+    /// Ticks Per Quarter Note message, if any.
+    #[must_use]
+    pub const fn ticks_per_quarter_note(&self) -> Option<u16> {
+        self.ticks_per_quarter_note
+    }
+
+    /// Returns the JR Timestamp settings given by the latest Stream
+    /// Configuration Notification, if any.
+    #[must_use]
+    pub const fn jitter_reduction(&self) -> Option<JitterReduction> {
+        self.jitter_reduction
+    }
+
+    /// Returns the number of warnings reported since the validator was created
+    /// or reset.
+    #[must_use]
+    pub const fn warning_count(&self) -> u64 {
+        self.warnings
+    }
+
+    /// Checks a single message against the stream configuration, updating the
+    /// configuration from configuration messages, and returning a [`Warning`]
+    /// if the message is inconsistent with the configuration.
+    pub fn check(&mut self, message: &MessageView<'_>) -> Option<Warning> {
+        let warning = match message {
+            MessageView::Stream(StreamView::StreamConfigurationNotification(notification)) => {
+                self.jitter_reduction = notification.jitter_reduction().ok();
+                None
+            }
+            MessageView::Utility(UtilityView::DeltaClockstampTicksPerQuarterNote(message)) => {
+                let received = message.ticks_per_quarter_note().ok().map(u16::from)?;
+
+                self.ticks_per_quarter_note = Some(received);
+
+                if received == 0 {
+                    Some(Warning::ZeroTicksPerQuarterNote)
+                } else {
+                    self.expected
+                        .filter(|&expected| expected != received)
+                        .map(|expected| Warning::TicksPerQuarterNote { expected, received })
+                }
+            }
+            MessageView::Utility(UtilityView::DeltaClockstamp(_)) => self
+                .ticks_per_quarter_note
+                .is_none()
+                .then_some(Warning::MissingTicksPerQuarterNote),
+            MessageView::Utility(UtilityView::JrClock(_) | UtilityView::JrTimestamp(_)) => self
+                .jitter_reduction
+                .filter(|jitter_reduction| !jitter_reduction.transmit)
+                .map(|_| Warning::UnexpectedJitterReduction),
+            _ => None,
+        };
+
+        if warning.is_some() {
+            self.warnings += 1;
+        }
+
+        warning
+    }
+
+    /// Checks each message in the buffer of words against the stream
+    /// configuration, calling the given function with the offset (in words)
+    /// of each inconsistent message and the [`Warning`] for that message.
+    /// Words which do not form a valid message (including a trailing
+    /// incomplete message) are skipped.
+    pub fn scan<F>(&mut self, words: &[u32], mut on_warning: F)
+    where
+        F: FnMut(usize, Warning),
+    {
+        let mut offset = 0;
+
+        while let Some(&word) = words.get(offset) {
+            let len = packet::word_count(word);
+
+            if offset + len > words.len() {
+                break;
+            }
+
+            if let Ok(message) = MessageView::try_from(&words[offset..offset + len]) {
+                if let Some(warning) = self.check(&message) {
+                    on_warning(offset, warning);
+                }
+            }
+
+            offset += len;
+        }
+    }
+
+    /// Forgets the received configuration, and resets the warning count to
+    /// zero (keeping any expected number of ticks per quarter note).
+    pub fn reset(&mut self) {
+        *self = Self {
+            expected: self.expected,
+            ..Self::new()
+        };
+    }
+}
@@ -0,0 +1,230 @@
+// =============================================================================
+// Debugger
+// =============================================================================
+
+//! Conditional breakpoints over streams of messages.
+//!
+//! The [`debugger`](crate::debugger) module provides a [`Debugger`], which
+//! holds a set of breakpoints -- predicates over [`MessageView`]s, such as
+//! "a Note On on channel 3 with a velocity above half" -- and reports a
+//! [`Hit`] for each message matching an enabled breakpoint as buffers of UMP
+//! words are scanned, so that tools can implement conditional capture, or
+//! pause playback and step through a stream.
+
+use alloc::{
+    boxed::Box,
+    vec::Vec,
+};
+#[cfg(feature = "fmt")]
+use core::fmt;
+
+use crate::{
+    message::MessageView,
+    packet,
+};
+
+// -----------------------------------------------------------------------------
+
+// Breakpoint ID
+
+/// The identifier of a breakpoint, returned when the breakpoint is added to a
+/// [`Debugger`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BreakpointId(usize);
+
+// Hit
+
+/// A match of a breakpoint, giving the breakpoint and the offset (in words) of
+/// the matching message within the scanned buffer.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Hit {
+    /// The breakpoint which matched.
+    pub breakpoint: BreakpointId,
+    /// The offset (in words) of the matching message.
+    pub offset: usize,
+}
+
+// -----------------------------------------------------------------------------
+
+// Debugger
+
+/// A set of conditional breakpoints over messages.
+///
+/// Each breakpoint is a predicate over a [`MessageView`], and may be enabled
+/// or disabled, and counts the messages it has matched. Where a message
+/// matches several breakpoints, a hit is reported for each, in the order the
+/// breakpoints were added. Words which do not form a valid message (including
+/// a trailing incomplete message) are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "voice")]
+/// # {
+/// # use midi_2_protocol::debugger::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// let mut debugger = Debugger::new();
+///
+/// // note on, on channel 3, with velocity above half
+/// let loud = debugger.add(|message| match message {
+///     MessageView::Voice(VoiceView::NoteOn(note_on)) => {
+///         note_on.channel().map_or(false, |channel| channel == Channel::C3)
+///             && note_on.velocity().map_or(false, |velocity| u16::from(velocity) > 0x8000)
+///     }
+///     _ => false,
+/// });
+///
+/// let words = [
+///     0x40923c00, 0x40000000, // quiet note on, channel 3
+///     0x10f80000,             // timing clock
+///     0x40923e00, 0xffff0000, // loud note on, channel 3
+/// ];
+///
+/// let mut hits = Vec::new();
+///
+/// debugger.scan(&words, |hit| hits.push(hit));
+///
+/// assert_eq!(hits, [Hit { breakpoint: loud, offset: 3 }]);
+/// assert_eq!(debugger.hit_count(loud), Some(1));
+/// # }
+/// ```
+#[derive(Default)]
+pub struct Debugger<'a> {
+    breakpoints: Vec<Breakpoint<'a>>,
+    next: usize,
+}
+
+impl<'a> Debugger<'a> {
+    /// Creates a new [`Debugger`], with no breakpoints.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an enabled breakpoint with the given predicate, returning the ID
+    /// of the new breakpoint.
+    pub fn add<F>(&mut self, predicate: F) -> BreakpointId
+    where
+        F: Fn(&MessageView<'_>) -> bool + 'a,
+    {
+        let id = BreakpointId(self.next);
+
+        self.next += 1;
+        self.breakpoints.push(Breakpoint {
+            id,
+            predicate: Box::new(predicate),
+            enabled: true,
+            hits: 0,
+        });
+
+        id
+    }
+
+    /// Removes the breakpoint with the given ID, returning `false` if there is
+    /// no such breakpoint.
+    pub fn remove(&mut self, id: BreakpointId) -> bool {
+        let len = self.breakpoints.len();
+
+        self.breakpoints.retain(|breakpoint| breakpoint.id != id);
+        self.breakpoints.len() != len
+    }
+
+    /// Enables or disables the breakpoint with the given ID (disabled
+    /// breakpoints are not checked, and do not count hits).
+    pub fn set_enabled(&mut self, id: BreakpointId, enabled: bool) {
+        if let Some(breakpoint) = self.get_mut(id) {
+            breakpoint.enabled = enabled;
+        }
+    }
+
+    /// Returns the number of messages matched by the breakpoint with the given
+    /// ID, or `None` if there is no such breakpoint.
+    #[must_use]
+    pub fn hit_count(&self, id: BreakpointId) -> Option<u64> {
+        self.breakpoints
+            .iter()
+            .find(|breakpoint| breakpoint.id == id)
+            .map(|breakpoint| breakpoint.hits)
+    }
+
+    /// Checks a single message against each enabled breakpoint, calling the
+    /// given function with the ID of each breakpoint matched.
+    pub fn check<F>(&mut self, message: &MessageView<'_>, mut on_hit: F)
+    where
+        F: FnMut(BreakpointId),
+    {
+        for breakpoint in &mut self.breakpoints {
+            if breakpoint.enabled && (breakpoint.predicate)(message) {
+                breakpoint.hits += 1;
+                on_hit(breakpoint.id);
+            }
+        }
+    }
+
+    /// Checks each message in the buffer of words against each enabled
+    /// breakpoint, calling the given function with a [`Hit`] for each match.
+    pub fn scan<F>(&mut self, words: &[u32], mut on_hit: F)
+    where
+        F: FnMut(Hit),
+    {
+        let mut offset = 0;
+
+        while let Some(&word) = words.get(offset) {
+            let len = packet::word_count(word);
+
+            if offset + len > words.len() {
+                break;
+            }
+
+            if let Ok(message) = MessageView::try_from(&words[offset..offset + len]) {
+                self.check(&message, |breakpoint| on_hit(Hit { breakpoint, offset }));
+            }
+
+            offset += len;
+        }
+    }
+
+    /// Resets the hit counts of all breakpoints to zero.
+    pub fn reset(&mut self) {
+        for breakpoint in &mut self.breakpoints {
+            breakpoint.hits = 0;
+        }
+    }
+
+    fn get_mut(&mut self, id: BreakpointId) -> Option<&mut Breakpoint<'a>> {
+        self.breakpoints
+            .iter_mut()
+            .find(|breakpoint| breakpoint.id == id)
+    }
+}
+
+#[cfg(feature = "fmt")]
+impl fmt::Debug for Debugger<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Debugger")
+            .field("breakpoints", &self.breakpoints)
+            .finish_non_exhaustive()
+    }
+}
+
+// Breakpoint
+
+struct Breakpoint<'a> {
+    id: BreakpointId,
+    predicate: Box<dyn Fn(&MessageView<'_>) -> bool + 'a>,
+    enabled: bool,
+    hits: u64,
+}
+
+#[cfg(feature = "fmt")]
+impl fmt::Debug for Breakpoint<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Breakpoint")
+            .field("id", &self.id)
+            .field("enabled", &self.enabled)
+            .field("hits", &self.hits)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,197 @@
+// =============================================================================
+// Drift
+// =============================================================================
+
+//! Clock drift estimation between JR (Jitter Reduction) time and host time.
+//!
+//! JR Clock and JR Timestamp values **([M2-104-UM 7.2])** are 16-bit counts of
+//! ticks at a nominal rate of 31250 Hz, which wrap roughly every 2.1 seconds.
+//! The clock of a remote device will never run at exactly the same rate as the
+//! local host clock, so mapping JR time to host time using the nominal rate
+//! accumulates error over long sessions.
+//!
+//! The [`Estimator`] in the [`drift`](crate::drift) module continuously fits a
+//! linear mapping from (unwrapped) remote JR time to host time, using a sliding
+//! window of recent observations, so that remote times can be converted to host
+//! times without accumulating drift.
+
+use std::collections::VecDeque;
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+/// The nominal duration of a JR tick, in nanoseconds (1 / 31250 Hz).
+pub const NOMINAL_TICK_NANOS: f64 = 32_000.0;
+
+const DEFAULT_WINDOW: usize = 64;
+
+// -----------------------------------------------------------------------------
+
+// Estimator
+
+/// Estimates the mapping from remote JR time to host time.
+///
+/// Observations pair a remote JR time (e.g. from a received JR Clock message)
+/// with the host time (in nanoseconds, from any monotonic clock) at which it
+/// was received. Observations must be supplied in order, and frequently enough
+/// that the JR time does not wrap more than once between them (JR Clock
+/// messages are normally sent at least every 250 ms).
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::drift::*;
+/// #
+/// let mut estimator = Estimator::default();
+///
+/// // a remote clock running 100 ppm fast, observed every 10000 ticks...
+/// for i in 0..10u64 {
+///     let ticks = (i * 10_000 % 65_536) as u16;
+///     let host = 1_000_000 + (i * 10_000 * 32_000) * 1_000_000 / 1_000_100;
+///
+///     estimator.observe(ticks, host);
+/// }
+///
+/// assert!((estimator.drift_ppm().unwrap() - 100.0).abs() < 1.0);
+/// ```
+#[derive(Debug)]
+pub struct Estimator {
+    window: usize,
+    observations: VecDeque<(i64, u64)>,
+    last: Option<(u16, i64)>,
+}
+
+impl Estimator {
+    /// Creates a new [`Estimator`] fitting the given number of most recent
+    /// observations (at least two observations are always retained).
+    #[must_use]
+    pub fn new(window: usize) -> Self {
+        Self {
+            window: window.max(2),
+            observations: VecDeque::new(),
+            last: None,
+        }
+    }
+
+    /// Records an observation of a remote JR time, received at the given host
+    /// time (in nanoseconds).
+    pub fn observe(&mut self, ticks: u16, host: u64) {
+        let unwrapped = self.unwrap(ticks);
+
+        if self.observations.len() == self.window {
+            self.observations.pop_front();
+        }
+
+        self.observations.push_back((unwrapped, host));
+        self.last = Some((ticks, unwrapped));
+    }
+
+    /// Converts a remote JR time (close to the most recently observed time) to
+    /// an estimated host time (in nanoseconds), or `None` if there are no
+    /// observations yet.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn to_host(&self, ticks: u16) -> Option<u64> {
+        let (last_ticks, last_unwrapped) = self.last?;
+        let unwrapped = last_unwrapped + i64::from(ticks.wrapping_sub(last_ticks) as i16);
+        let (slope, x, y) = self.fit()?;
+
+        Some(offset(y, slope * to_f64(unwrapped - x)))
+    }
+
+    /// Returns the estimated drift of the remote clock relative to the host
+    /// clock in parts per million (positive when the remote clock runs fast),
+    /// or `None` if there are fewer than two observations.
+    #[must_use]
+    pub fn drift_ppm(&self) -> Option<f64> {
+        if self.observations.len() < 2 {
+            return None;
+        }
+
+        let (slope, _, _) = self.fit()?;
+
+        Some((NOMINAL_TICK_NANOS / slope - 1.0) * 1_000_000.0)
+    }
+
+    /// Discards all observations (e.g. on reconnection to a remote device).
+    pub fn reset(&mut self) {
+        self.observations.clear();
+        self.last = None;
+    }
+
+    // Fits a line through the observations, returning the slope (nanoseconds
+    // per tick) and an anchor point (the most recent observation, with the
+    // fitted offset applied).
+
+    #[allow(clippy::cast_precision_loss)]
+    fn fit(&self) -> Option<(f64, i64, u64)> {
+        let &(x0, y0) = self.observations.back()?;
+        let n = self.observations.len() as f64;
+
+        let points = self
+            .observations
+            .iter()
+            .map(|&(x, y)| (to_f64(x - x0), difference(y, y0)));
+
+        let (sx, sy) = points
+            .clone()
+            .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+        let (mx, my) = (sx / n, sy / n);
+        let (sxy, sxx) = points.fold((0.0, 0.0), |(sxy, sxx), (x, y)| {
+            ((x - mx).mul_add(y - my, sxy), (x - mx).mul_add(x - mx, sxx))
+        });
+
+        let slope = if sxx > 0.0 {
+            sxy / sxx
+        } else {
+            NOMINAL_TICK_NANOS
+        };
+
+        Some((slope, x0, offset(y0, slope.mul_add(-mx, my))))
+    }
+
+    fn unwrap(&self, ticks: u16) -> i64 {
+        match self.last {
+            Some((last_ticks, last_unwrapped)) => {
+                last_unwrapped + i64::from(ticks.wrapping_sub(last_ticks))
+            }
+            None => i64::from(ticks),
+        }
+    }
+}
+
+impl Default for Estimator {
+    fn default() -> Self {
+        Self::new(DEFAULT_WINDOW)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+#[allow(clippy::cast_precision_loss)]
+fn difference(a: u64, b: u64) -> f64 {
+    if a >= b {
+        (a - b) as f64
+    } else {
+        -((b - a) as f64)
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn offset(host: u64, offset: f64) -> u64 {
+    if offset < 0.0 {
+        host.saturating_sub((-offset).round() as u64)
+    } else {
+        host.saturating_add(offset.round() as u64)
+    }
+}
+
+// Not a const fn, as casting integers to floats in a const fn requires a later
+// Rust version than the minimum supported version.
+#[allow(clippy::cast_precision_loss, clippy::missing_const_for_fn)]
+fn to_f64(ticks: i64) -> f64 {
+    ticks as f64
+}
@@ -0,0 +1,248 @@
+// =============================================================================
+// Drum Map
+// =============================================================================
+
+//! Remapping of note numbers.
+//!
+//! The [`drum_map`](crate::drum_map) module provides a [`DrumMap`], a table of
+//! note number substitutions (optionally restricted to a group and channel)
+//! which is applied in place to the per-note Channel Voice messages in a buffer
+//! of UMP words -- for example, to translate the General MIDI drum map to the
+//! note layout of a particular drum machine.
+//!
+//! With the `serde` feature enabled, drum maps can be serialized, so that maps
+//! for different devices can be saved and loaded.
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{
+    de::{
+        self,
+        Unexpected,
+    },
+    Deserialize,
+    Deserializer,
+    Serialize,
+};
+
+use crate::{
+    message::{
+        voice::{
+            self,
+            Note,
+        },
+        Channel,
+        Group,
+    },
+    packet,
+};
+
+// -----------------------------------------------------------------------------
+
+// Drum Map
+
+/// A table of note number substitutions.
+///
+/// Each entry maps a note number to another, either for all groups and
+/// channels, or only for a given group and/or channel. Where several entries
+/// match a message, the entry added first is used.
+///
+/// The map applies to all MIDI 2.0 Channel Voice messages which address a note
+/// (Note On, Note Off, Poly Pressure, Registered and Assignable Per-Note
+/// Controllers, Per-Note Pitch Bend and Per-Note Management).
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::drum_map::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// // map the GM closed hi-hat (42) to 44, and the snare (38) on channel 10 to 40
+/// let map = DrumMap::default()
+///     .set_note(None, None, Note::new(42), Note::new(44))
+///     .set_note(None, Some(Channel::C10), Note::new(38), Note::new(40));
+///
+/// let mut buffer = [
+///     0x40992a00, 0xffff0000, // note on 42, channel 10
+///     0x40902600, 0xffff0000, // note on 38, channel 1
+///     0x40892600, 0x00000000, // note off 38, channel 10
+///     0x10f80000,             // timing clock
+/// ];
+///
+/// map.apply(&mut buffer);
+///
+/// assert_eq!(
+///     buffer,
+///     [0x40992c00, 0xffff0000, 0x40902600, 0xffff0000, 0x40892800, 0, 0x10f80000]
+/// );
+/// ```
+///
+/// With the `serde` feature enabled, the entries of a deserialized map are
+/// checked, so that a map with an out of range group, channel or note number
+/// is rejected:
+///
+/// ```rust
+/// # #[cfg(feature = "serde")]
+/// # {
+/// # use std::collections::BTreeMap;
+/// # use serde::de::{value::Error, Deserialize, IntoDeserializer};
+/// # use midi_2_protocol::drum_map::*;
+/// #
+/// let map = |to: u8| {
+///     let entry = BTreeMap::from([("from", 38u8), ("to", to)]);
+///
+///     IntoDeserializer::<Error>::into_deserializer(BTreeMap::from([("entries", vec![entry])]))
+/// };
+///
+/// assert!(DrumMap::deserialize(map(40)).is_ok());
+/// assert!(DrumMap::deserialize(map(200)).is_err());
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize), serde(default))]
+pub struct DrumMap {
+    entries: Vec<Entry>,
+}
+
+impl DrumMap {
+    /// Sets the note number substituted for the given note, on the given group
+    /// and channel (or on all groups or channels where `None`), replacing any
+    /// existing entry for the same note, group and channel.
+    #[must_use]
+    pub fn set_note(
+        mut self,
+        group: Option<Group>,
+        channel: Option<Channel>,
+        from: Note,
+        to: Note,
+    ) -> Self {
+        let entry = Entry {
+            group: group.map(u8::from),
+            channel: channel.map(u8::from),
+            from: u8::from(from),
+            to: u8::from(to),
+        };
+
+        match self.entries.iter_mut().find(|e| e.is_same(entry)) {
+            Some(existing) => existing.to = entry.to,
+            None => self.entries.push(entry),
+        }
+
+        self
+    }
+
+    /// Returns the note number substituted for the given note on the given
+    /// group and channel, if any.
+    #[must_use]
+    pub fn note(&self, group: Group, channel: Channel, note: Note) -> Option<Note> {
+        self.lookup(u8::from(group), u8::from(channel), u8::from(note))
+            .map(Note::new)
+    }
+
+    /// Applies the map in place to each message in the buffer. A trailing
+    /// incomplete message in the buffer is left unchanged.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn apply(&self, buffer: &mut [u32]) {
+        let mut position = 0;
+
+        while let Some(&word) = buffer.get(position) {
+            let len = packet::word_count(word);
+
+            if position + len > buffer.len() {
+                break;
+            }
+
+            if voice::is_per_note(word) {
+                let group = (word >> 24 & 0xf) as u8;
+                let channel = (word >> 16 & 0xf) as u8;
+                let note = (word >> 8 & 0x7f) as u8;
+
+                if let Some(to) = self.lookup(group, channel, note) {
+                    buffer[position] = word & !0x0000_ff00 | u32::from(to) << 8;
+                }
+            }
+
+            position += len;
+        }
+    }
+
+    fn lookup(&self, group: u8, channel: u8, note: u8) -> Option<u8> {
+        self.entries
+            .iter()
+            .find(|entry| entry.matches(group, channel, note))
+            .map(|entry| entry.to)
+    }
+}
+
+// Entry
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+struct Entry {
+    group: Option<u8>,
+    channel: Option<u8>,
+    from: u8,
+    to: u8,
+}
+
+impl Entry {
+    fn is_same(self, other: Self) -> bool {
+        self.group == other.group && self.channel == other.channel && self.from == other.from
+    }
+
+    fn matches(self, group: u8, channel: u8, note: u8) -> bool {
+        self.from == note
+            && self.group.map_or(true, |g| g == group)
+            && self.channel.map_or(true, |c| c == channel)
+    }
+}
+
+// Entries are deserialized through the raw representation, checking the
+// ranges of the group, channel and note numbers, which would otherwise panic
+// when looked up, or corrupt messages when applied.
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Entry {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Raw {
+            group: Option<u8>,
+            channel: Option<u8>,
+            from: u8,
+            to: u8,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+
+        check(raw.group, 0xf, "a group number from 0 to 15")?;
+        check(raw.channel, 0xf, "a channel number from 0 to 15")?;
+        check(Some(raw.from), 0x7f, "a note number from 0 to 127")?;
+        check(Some(raw.to), 0x7f, "a note number from 0 to 127")?;
+
+        Ok(Self {
+            group: raw.group,
+            channel: raw.channel,
+            from: raw.from,
+            to: raw.to,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+fn check<E>(value: Option<u8>, max: u8, expected: &'static str) -> Result<(), E>
+where
+    E: de::Error,
+{
+    match value {
+        Some(value) if value > max => Err(E::invalid_value(
+            Unexpected::Unsigned(u64::from(value)),
+            &expected,
+        )),
+        _ => Ok(()),
+    }
+}
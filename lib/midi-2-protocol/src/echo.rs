@@ -0,0 +1,133 @@
+// =============================================================================
+// Echo
+// =============================================================================
+
+//! Suppression of locally echoed messages.
+//!
+//! The [`echo`](crate::echo) module provides an [`EchoSuppressor`], which
+//! records packets as they are sent, and identifies received packets which
+//! match a recently sent packet, so that they can be dropped when a device
+//! loops back everything it receives (which would otherwise cause feedback
+//! loops).
+//!
+//! Times are supplied by the caller as host times in nanoseconds (from any
+//! monotonic clock), so the suppressor itself performs no I/O or timekeeping.
+
+use alloc::vec::Vec;
+
+use crate::packet::{
+    self,
+    Packet,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const MAX_SENT: usize = 256;
+
+// -----------------------------------------------------------------------------
+
+// Echo Suppressor
+
+/// Suppresses received packets which echo recently sent packets.
+///
+/// A received packet is an echo if it matches a packet sent within the
+/// configured window, ignoring the Group field (as devices may loop messages
+/// back on a different group). Each sent packet suppresses at most one echo.
+/// Only the most recent 256 sent packets are retained.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::echo::*;
+/// # use midi_2_protocol::packet::*;
+/// #
+/// let mut suppressor = EchoSuppressor::new(5_000_000);
+///
+/// suppressor.sent(Packet::from([0x40903c00, 0xffff0000]), 0);
+///
+/// // the echo (here on another group) is suppressed once...
+/// assert!(suppressor.suppress(&Packet::from([0x42903c00, 0xffff0000]), 1_000_000));
+/// assert!(!suppressor.suppress(&Packet::from([0x40903c00, 0xffff0000]), 1_500_000));
+///
+/// // ...and packets are no longer matched once the window has passed
+/// suppressor.sent(Packet::from([0x40903c00, 0xffff0000]), 2_000_000);
+///
+/// assert!(!suppressor.suppress(&Packet::from([0x40903c00, 0xffff0000]), 8_000_000));
+/// ```
+#[derive(Debug)]
+pub struct EchoSuppressor {
+    window: u64,
+    sent: Vec<(u64, Packet)>,
+}
+
+impl EchoSuppressor {
+    /// Creates a new [`EchoSuppressor`], matching echoes received within the
+    /// given window (in nanoseconds) of the packet being sent.
+    #[must_use]
+    pub const fn new(window: u64) -> Self {
+        Self {
+            window,
+            sent: Vec::new(),
+        }
+    }
+
+    /// Records a packet as sent at the given host time (in nanoseconds).
+    pub fn sent(&mut self, packet: Packet, now: u64) {
+        self.expire(now);
+
+        if self.sent.len() == MAX_SENT {
+            self.sent.remove(0);
+        }
+
+        self.sent.push((now, packet));
+    }
+
+    /// Observes a packet received at the given host time (in nanoseconds),
+    /// returning `true` if it is an echo of a recently sent packet (in which
+    /// case it should be dropped).
+    pub fn suppress(&mut self, packet: &Packet, now: u64) -> bool {
+        self.expire(now);
+
+        match self.sent.iter().position(|(_, sent)| matches(sent, packet)) {
+            Some(position) => {
+                self.sent.remove(position);
+
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Discards all recorded sent packets.
+    pub fn reset(&mut self) {
+        self.sent.clear();
+    }
+
+    fn expire(&mut self, now: u64) {
+        let start = now.saturating_sub(self.window);
+
+        self.sent.retain(|(sent, _)| *sent >= start);
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+// Returns whether two packets are equal, ignoring the Group field of grouped
+// messages.
+
+fn matches(a: &[u32], b: &[u32]) -> bool {
+    let mask = match a.first() {
+        Some(&word) if packet::is_grouped(word) => !packet::GROUP_MASK,
+        _ => u32::MAX,
+    };
+
+    a.len() == b.len()
+        && a.iter().zip(b).enumerate().all(|(i, (a, b))| match i {
+            0 => a & mask == b & mask,
+            _ => a == b,
+        })
+}
@@ -26,6 +26,24 @@ pub trait WriteToPacket {
         P: GetBitSlice;
 }
 
+// Integral
+
+// Field types which are stored as (and validated from) a single integral value,
+// allowing messages to provide setters taking raw integral values. Every type
+// also converts from itself, so that such setters accept field values too.
+
+pub trait TryFromIntegral<I> {
+    fn try_from_integral(value: I) -> Result<Self, Error>
+    where
+        Self: Sized;
+}
+
+impl<T> TryFromIntegral<T> for T {
+    fn try_from_integral(value: T) -> Result<Self, Error> {
+        Ok(value)
+    }
+}
+
 // -----------------------------------------------------------------------------
 
 // Macros
@@ -47,15 +65,19 @@ macro_rules! impl_field {
 
 // Field Struct
 
+// Not every rule of the field macros is used by every combination of message
+// family features (System messages have no unsized fields, for example).
+
+#[allow(unused_macro_rules)]
 macro_rules! impl_field_struct {
     ($($meta:meta)*, $vis:vis, $field:ident, $integral:ty, $size:literal) => {
         $(#[$meta])*
-        #[derive(Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+        #[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
         $vis struct $field(UInt<$integral, $size>);
     };
     ($($meta:meta)*, $vis:vis, $field:ident, $integral:ty) => {
         $(#[$meta])*
-        #[derive(Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
+        #[derive(Clone, Copy, Debug, Default, Eq, Ord, PartialEq, PartialOrd)]
         $vis struct $field($integral);
     };
 }
@@ -70,6 +92,7 @@ macro_rules! impl_field_constructor {
     };
 }
 
+#[allow(unused_macro_rules)]
 macro_rules! impl_field_constructor_fns {
     ($field:ident, $integral:ty, $size:literal) => {
         ::paste::paste! {
@@ -108,6 +131,7 @@ macro_rules! impl_field_trait_from {
     };
 }
 
+#[allow(unused_macro_rules)]
 macro_rules! impl_field_trait_from_fns {
     ($field:ident, $integral:ty, $size:literal) => {
         fn from(value: $field) -> Self {
@@ -133,6 +157,7 @@ macro_rules! impl_field_trait_try_from {
     };
 }
 
+#[allow(unused_macro_rules)]
 macro_rules! impl_field_trait_try_from_fns {
     ($field:ident, $integral:ty, $size:literal) => {
         fn try_from(value: $integral) -> Result<Self, Self::Error> {
@@ -152,11 +177,22 @@ macro_rules! impl_field_trait_try_from_fns {
 
 macro_rules! impl_field_trait_field_traits {
     ($field:ident, $integral:ty, $range:expr) => {
+        crate::field::impl_field_trait_try_from_integral!($field, $integral);
         crate::field::impl_field_trait_try_read_from_packet!($field, $integral, $range);
         crate::field::impl_field_trait_write_to_packet!($field, $integral, $range);
     };
 }
 
+macro_rules! impl_field_trait_try_from_integral {
+    ($field:ident, $integral:ty) => {
+        impl crate::field::TryFromIntegral<$integral> for $field {
+            fn try_from_integral(value: $integral) -> Result<Self, Error> {
+                Self::try_from(value)
+            }
+        }
+    };
+}
+
 macro_rules! impl_field_trait_try_read_from_packet {
     ($field:ident, $integral:ty, $range:expr) => {
         impl TryReadFromPacket for $field {
@@ -190,10 +226,73 @@ macro_rules! impl_field_trait_write_to_packet {
     };
 }
 
+// Bytes
+
+// Byte array field types, for fields carrying opaque data (such as System
+// Exclusive data or text) rather than a single integral value.
+
+#[cfg(any(feature = "data", feature = "flex-data", feature = "stream"))]
+macro_rules! impl_bytes {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $field:ident { $len:literal, $start:literal }
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+        $vis struct $field([u8; $len]);
+
+        impl $field {
+            #[must_use]
+            pub const fn new(value: [u8; $len]) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$field> for [u8; $len] {
+            fn from(value: $field) -> Self {
+                value.0
+            }
+        }
+
+        impl TryReadFromPacket for $field {
+            fn try_read_from_packet<P>(packet: &P) -> Result<Self, Error>
+            where
+                P: GetBitSlice + ?Sized,
+            {
+                let bit_slice = packet.get_bit_slice();
+                let mut bytes = [0u8; $len];
+
+                for (byte, bits) in bytes.iter_mut().zip(bit_slice[$start..].chunks(8)) {
+                    *byte = bits.load_be::<u8>();
+                }
+
+                Ok(Self(bytes))
+            }
+        }
+
+        impl WriteToPacket for $field {
+            fn write_to_packet<P>(self, mut packet: P) -> P
+            where
+                P: GetBitSlice,
+            {
+                let bit_slice = packet.get_bit_slice_mut();
+
+                for (byte, bits) in self.0.iter().zip(bit_slice[$start..].chunks_mut(8)) {
+                    bits.store_be::<u8>(*byte);
+                }
+
+                packet
+            }
+        }
+    };
+}
+
 // -----------------------------------------------------------------------------
 
 // Macro Exports
 
+#[cfg(any(feature = "data", feature = "flex-data", feature = "stream"))]
+pub(crate) use impl_bytes;
 pub(crate) use impl_field;
 pub(crate) use impl_field_constructor;
 pub(crate) use impl_field_constructor_fns;
@@ -203,5 +302,6 @@ pub(crate) use impl_field_trait_from;
 pub(crate) use impl_field_trait_from_fns;
 pub(crate) use impl_field_trait_try_from;
 pub(crate) use impl_field_trait_try_from_fns;
+pub(crate) use impl_field_trait_try_from_integral;
 pub(crate) use impl_field_trait_try_read_from_packet;
 pub(crate) use impl_field_trait_write_to_packet;
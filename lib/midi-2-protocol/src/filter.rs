@@ -0,0 +1,138 @@
+// =============================================================================
+// Filter
+// =============================================================================
+
+//! Fast classification of messages by their first word.
+//!
+//! The [`filter`](crate::filter) module provides a [`FastClassifier`], which
+//! accepts or rejects messages using a precomputed 256-bit set indexed by the
+//! Message Type and the opcode or status of each message, so that routers can
+//! filter messages in hot paths with a table lookup, without parsing them.
+
+#[cfg(feature = "system")]
+use crate::message::system;
+#[cfg(feature = "voice")]
+use crate::message::voice;
+use crate::message::MessageType;
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+// The shift of the 4 bits following the Group, indexed by Message Type -- the
+// low nibble of the status byte for System messages (as every System status
+// byte begins 0xf), and the high nibble of the second byte otherwise.
+const SHIFTS: [u32; 16] = [
+    20, 16, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20, 20,
+];
+
+// -----------------------------------------------------------------------------
+
+// Fast Classifier
+
+/// A set of accepted message kinds, checked by a table lookup on the first
+/// word of each message.
+///
+/// Messages are classified by an 8-bit key, combining the Message Type with
+/// the opcode of Channel Voice messages, the status of System messages, or the
+/// status of Utility and Data messages (the Group is ignored). Flex Data and
+/// Stream messages do not carry a status in these bits, and should be accepted
+/// by Message Type only.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(all(feature = "system", feature = "voice"))]
+/// # {
+/// # use midi_2_protocol::filter::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::system::Status;
+/// # use midi_2_protocol::message::voice::Opcode;
+/// #
+/// let classifier = FastClassifier::new()
+///     .accept_voice(Opcode::NoteOff)
+///     .accept_voice(Opcode::NoteOn)
+///     .accept_system(Status::TimingClock)
+///     .accept_message_type(MessageType::FlexData);
+///
+/// assert!(classifier.matches(0x40903c00)); // note on, group 1
+/// assert!(classifier.matches(0x43913c00)); // note on, group 4
+/// assert!(!classifier.matches(0x40b04a00)); // control change
+/// assert!(classifier.matches(0x10f80000)); // timing clock
+/// assert!(!classifier.matches(0x10fe0000)); // active sensing
+/// assert!(classifier.matches(0xd0100001)); // flex data
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct FastClassifier {
+    accepted: [u64; 4],
+}
+
+impl FastClassifier {
+    /// Creates a new [`FastClassifier`], accepting no messages.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { accepted: [0; 4] }
+    }
+
+    /// Creates a new [`FastClassifier`], accepting all messages.
+    #[must_use]
+    pub const fn all() -> Self {
+        Self {
+            accepted: [u64::MAX; 4],
+        }
+    }
+
+    /// Accepts messages of the given Message Type with the given opcode or
+    /// status (of which only the low 4 bits are used, so that System messages
+    /// may be given by their whole status byte).
+    #[must_use]
+    pub const fn accept(mut self, message_type: MessageType, status: u8) -> Self {
+        let key = (message_type as u8) << 4 | status & 0xf;
+
+        self.accepted[(key >> 6) as usize] |= 1 << (key & 0x3f);
+        self
+    }
+
+    /// Accepts all messages of the given Message Type.
+    #[must_use]
+    pub const fn accept_message_type(mut self, message_type: MessageType) -> Self {
+        let message_type = message_type as u8;
+
+        self.accepted[(message_type >> 2) as usize] |= 0xffff << ((message_type & 0x3) * 16);
+        self
+    }
+
+    /// Accepts MIDI 2.0 Channel Voice messages with the given opcode.
+    #[cfg(feature = "voice")]
+    #[must_use]
+    pub const fn accept_voice(self, opcode: voice::Opcode) -> Self {
+        self.accept(MessageType::Voice, opcode as u8)
+    }
+
+    /// Accepts System messages with the given status.
+    #[cfg(feature = "system")]
+    #[must_use]
+    pub const fn accept_system(self, status: system::Status) -> Self {
+        self.accept(MessageType::System, status as u8)
+    }
+
+    /// Returns the 8-bit key by which a message is classified, given the first
+    /// word of the message.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn key(word: u32) -> u8 {
+        let shift = SHIFTS[(word >> 28) as usize];
+
+        (word >> 24 & 0xf0 | word >> shift & 0xf) as u8
+    }
+
+    /// Returns whether the classifier accepts the message beginning with the
+    /// given word.
+    #[must_use]
+    pub const fn matches(&self, word: u32) -> bool {
+        let key = Self::key(word);
+
+        self.accepted[(key >> 6) as usize] >> (key & 0x3f) & 1 != 0
+    }
+}
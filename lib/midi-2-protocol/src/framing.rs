@@ -0,0 +1,133 @@
+// =============================================================================
+// Framing
+// =============================================================================
+
+//! Length-prefixed framing of UMP packets over byte streams.
+//!
+//! The [`framing`](crate::framing) module provides a simple codec for
+//! exchanging UMP packets over byte streams such as pipes, stdio, or local
+//! sockets (e.g. between an application and a sandboxed helper process, with
+//! this crate used on both sides).
+//!
+//! Each frame is a 32-bit little-endian count of words, followed by that many
+//! 32-bit little-endian UMP words. A frame always contains whole messages, and
+//! may be empty.
+
+use alloc::vec::Vec;
+use std::io::{
+    self,
+    ErrorKind,
+    Read,
+    Write,
+};
+
+use crate::packet;
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+/// The maximum number of words in a frame. Larger frames are rejected when
+/// read, to bound memory use when reading from an untrusted peer.
+pub const MAX_FRAME_WORDS: usize = 0x1_0000;
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+/// Writes the given words to the writer as a single frame.
+///
+/// # Errors
+///
+/// Returns an [`io::Error`] if the words do not consist of whole messages, if
+/// there are more than [`MAX_FRAME_WORDS`] words, or if the writer fails.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::framing::*;
+/// #
+/// let mut pipe = Vec::new();
+///
+/// write_frame(&mut pipe, &[0x10f80000, 0x40903c00, 0xffff0000])?;
+///
+/// let mut words = Vec::new();
+///
+/// assert!(read_frame(&mut &pipe[..], &mut words)?);
+/// assert_eq!(words, [0x10f80000, 0x40903c00, 0xffff0000]);
+/// #
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_frame<W>(writer: &mut W, words: &[u32]) -> io::Result<()>
+where
+    W: Write + ?Sized,
+{
+    let length = validate(words)?;
+    let mut bytes = Vec::with_capacity((words.len() + 1) * 4);
+
+    bytes.extend_from_slice(&length.to_le_bytes());
+
+    for word in words {
+        bytes.extend_from_slice(&word.to_le_bytes());
+    }
+
+    writer.write_all(&bytes)
+}
+
+/// Reads a single frame from the reader into the given buffer.
+///
+/// The contents of the buffer are replaced with the words of the frame.
+/// Returns `false` (leaving the buffer empty) if the reader is at the end of
+/// the stream before a frame begins.
+///
+/// # Errors
+///
+/// Returns an [`io::Error`] if the stream ends part way through a frame, if
+/// the frame is longer than [`MAX_FRAME_WORDS`] words or does not consist of
+/// whole messages, or if the reader fails.
+pub fn read_frame<R>(reader: &mut R, words: &mut Vec<u32>) -> io::Result<bool>
+where
+    R: Read + ?Sized,
+{
+    words.clear();
+
+    let mut length = [0; 4];
+
+    match reader.read(&mut length[..1])? {
+        0 => return Ok(false),
+        _ => reader.read_exact(&mut length[1..])?,
+    }
+
+    let length = usize::try_from(u32::from_le_bytes(length)).unwrap_or(usize::MAX);
+
+    if length > MAX_FRAME_WORDS {
+        return Err(invalid("frame exceeds maximum length"));
+    }
+
+    let mut bytes = alloc::vec![0; length * 4];
+
+    reader.read_exact(&mut bytes)?;
+    words.extend(
+        bytes
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])),
+    );
+
+    validate(words).map(|_| true)
+}
+
+fn validate(words: &[u32]) -> io::Result<u32> {
+    if words.len() > MAX_FRAME_WORDS {
+        return Err(invalid("frame exceeds maximum length"));
+    }
+
+    if !packet::split_at_message(words, words.len()).1.is_empty() {
+        return Err(invalid("frame contains an incomplete message"));
+    }
+
+    u32::try_from(words.len()).map_err(|_| invalid("frame exceeds maximum length"))
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(ErrorKind::InvalidData, message)
+}
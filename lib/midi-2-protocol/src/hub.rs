@@ -0,0 +1,496 @@
+// =============================================================================
+// Hub
+// =============================================================================
+
+//! Fan-out of packets from multiple producers to multiple consumers.
+//!
+//! The [`hub`](crate::hub) module provides a [`Hub`], which accepts packets
+//! from any number of producers (on any thread) and distributes them to any
+//! number of [`Consumer`]s, each with an independent [`Filter`] and a bounded
+//! queue with an [`Overflow`] policy, as the core of a virtual MIDI patchbay.
+//!
+//! A set of named consumer connections may be described by a [`Patchbay`],
+//! which (with the `serde` feature enabled) can be serialized, so that
+//! patchbay applications can save and restore their routing setups.
+
+use alloc::{
+    string::String,
+    vec::Vec,
+};
+use std::{
+    collections::VecDeque,
+    sync::{
+        Arc,
+        Condvar,
+        Mutex,
+        MutexGuard,
+        PoisonError,
+        Weak,
+    },
+    time::Duration,
+};
+
+#[cfg(feature = "serde")]
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    message::{
+        Channel,
+        Group,
+        MessageType,
+    },
+    packet::{
+        self,
+        Packet,
+    },
+};
+
+// -----------------------------------------------------------------------------
+
+// Filter
+
+/// A filter selecting the packets delivered to a consumer, by Message Type,
+/// Group, and Channel.
+///
+/// Groups are only considered for messages with a Group field, and Channels
+/// only for Channel Voice messages (of either protocol). The default filter
+/// accepts all packets.
+///
+/// When serialized, each criterion is given as a 16-bit mask, in which bit `n`
+/// is set if the value `n` (the 4-bit field value, e.g. `0` for Group 1) is
+/// accepted.
+///
+/// # Examples
+///
+/// ```rust
+/// # #[cfg(feature = "voice")]
+/// # {
+/// # use midi_2_protocol::hub::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::voice::*;
+/// # use midi_2_protocol::packet::*;
+/// #
+/// let filter = Filter::default()
+///     .set_message_types(&[MessageType::Voice])
+///     .set_channels(&[Channel::C1, Channel::C2]);
+///
+/// assert!(filter.matches(&Packet::from([0x40913c00, 0xffff0000])));
+/// assert!(!filter.matches(&Packet::from([0x40923c00, 0xffff0000])));
+/// assert!(!filter.matches(&Packet::from([0x10f80000])));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize), serde(default))]
+pub struct Filter {
+    message_types: u16,
+    groups: u16,
+    channels: u16,
+}
+
+impl Filter {
+    /// Sets the Message Types accepted by the filter.
+    #[must_use]
+    pub fn set_message_types(mut self, message_types: &[MessageType]) -> Self {
+        self.message_types = mask(message_types.iter().map(|m| u8::from(*m)));
+        self
+    }
+
+    /// Sets the Groups accepted by the filter.
+    #[must_use]
+    pub fn set_groups(mut self, groups: &[Group]) -> Self {
+        self.groups = mask(groups.iter().map(|g| u8::from(*g)));
+        self
+    }
+
+    /// Sets the Channels accepted by the filter.
+    #[must_use]
+    pub fn set_channels(mut self, channels: &[Channel]) -> Self {
+        self.channels = mask(channels.iter().map(|c| u8::from(*c)));
+        self
+    }
+
+    /// Returns whether the filter accepts the given packet.
+    #[must_use]
+    pub fn matches(&self, packet: &[u32]) -> bool {
+        let word = match packet.first() {
+            Some(&word) => word,
+            None => return false,
+        };
+        let nibble = |shift: u32| 1 << (word >> shift & 0xf);
+
+        self.message_types & nibble(28) != 0
+            && (!packet::is_grouped(word) || self.groups & nibble(24) != 0)
+            && (!matches!(word >> 28, 0x2 | 0x4) || self.channels & nibble(16) != 0)
+    }
+}
+
+impl Default for Filter {
+    fn default() -> Self {
+        Self {
+            message_types: u16::MAX,
+            groups: u16::MAX,
+            channels: u16::MAX,
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Overflow
+
+/// The behaviour of a consumer queue when a packet is sent while it is full.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub enum Overflow {
+    /// The oldest queued packet is discarded.
+    #[default]
+    DropOldest,
+    /// The new packet is discarded.
+    DropNewest,
+    /// The producer blocks until there is space in the queue.
+    Block,
+}
+
+// -----------------------------------------------------------------------------
+
+// Options
+
+/// The options for a consumer connected to a [`Hub`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize), serde(default))]
+pub struct Options {
+    pub filter: Filter,
+    /// The maximum number of packets held in the queue (at least one).
+    pub capacity: usize,
+    pub overflow: Overflow,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            filter: Filter::default(),
+            capacity: 256,
+            overflow: Overflow::default(),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Patchbay
+
+/// A named consumer connection, as part of a [`Patchbay`].
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Connection {
+    pub name: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub options: Options,
+}
+
+/// A routing setup, given as a list of named consumer connections.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::hub::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::packet::*;
+/// #
+/// let patchbay = Patchbay {
+///     connections: vec![
+///         Connection {
+///             name: String::from("synth"),
+///             options: Options {
+///                 filter: Filter::default().set_groups(&[Group::G1]),
+///                 ..Options::default()
+///             },
+///         },
+///         Connection {
+///             name: String::from("monitor"),
+///             options: Options::default(),
+///         },
+///     ],
+/// };
+///
+/// let hub = Hub::new();
+/// let consumers = patchbay.connect(&hub);
+///
+/// hub.send(Packet::from([0x11f80000]));
+///
+/// assert_eq!(consumers[0].0, "synth");
+/// assert_eq!(consumers[0].1.try_recv(), None);
+/// assert!(consumers[1].1.try_recv().is_some());
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Patchbay {
+    pub connections: Vec<Connection>,
+}
+
+impl Patchbay {
+    /// Connects a consumer to the hub for each connection, returning the names
+    /// and consumers in order.
+    #[must_use]
+    pub fn connect(&self, hub: &Hub) -> Vec<(String, Consumer)> {
+        self.connections
+            .iter()
+            .map(|connection| (connection.name.clone(), hub.connect(connection.options)))
+            .collect()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Hub
+
+/// Distributes packets from multiple producers to multiple consumers.
+///
+/// A [`Hub`] is a cheap handle, which may be cloned and passed to each
+/// producer. Consumers are connected with [`Hub::connect`], and are
+/// disconnected when dropped.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::hub::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::packet::*;
+/// #
+/// let hub = Hub::new();
+///
+/// let all = hub.connect(Options::default());
+/// let g2 = hub.connect(Options {
+///     filter: Filter::default().set_groups(&[Group::G2]),
+///     ..Options::default()
+/// });
+///
+/// let producer = hub.clone();
+///
+/// std::thread::spawn(move || {
+///     producer.send(Packet::from([0x10f80000]));
+///     producer.send(Packet::from([0x11f80000]));
+/// })
+/// .join()
+/// .unwrap();
+///
+/// assert_eq!(all.try_recv().as_deref(), Some(&[0x10f80000][..]));
+/// assert_eq!(all.try_recv().as_deref(), Some(&[0x11f80000][..]));
+/// assert_eq!(g2.try_recv().as_deref(), Some(&[0x11f80000][..]));
+/// assert_eq!(g2.try_recv(), None);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Hub {
+    queues: Arc<Mutex<Vec<Weak<Queue>>>>,
+}
+
+impl Hub {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Connects a new [`Consumer`] with the given options.
+    #[must_use]
+    pub fn connect(&self, options: Options) -> Consumer {
+        let queue = Arc::new(Queue {
+            options: Options {
+                capacity: options.capacity.max(1),
+                ..options
+            },
+            state: Mutex::new(State::default()),
+            readable: Condvar::new(),
+            writable: Condvar::new(),
+        });
+
+        lock(&self.queues).push(Arc::downgrade(&queue));
+
+        Consumer { queue }
+    }
+
+    /// Sends a packet to each connected consumer whose filter accepts it.
+    ///
+    /// This function blocks if the queue of a consumer with the
+    /// [`Overflow::Block`] policy is full, until the consumer receives a
+    /// packet (or is dropped).
+    pub fn send(&self, packet: Packet) {
+        let queues: Vec<_> = {
+            let mut queues = lock(&self.queues);
+
+            queues.retain(|queue| queue.strong_count() > 0);
+            queues.iter().filter_map(Weak::upgrade).collect()
+        };
+
+        for queue in queues {
+            if queue.options.filter.matches(&packet) {
+                queue.push(packet);
+            }
+        }
+    }
+
+    /// Returns the number of connected consumers.
+    #[must_use]
+    pub fn consumers(&self) -> usize {
+        lock(&self.queues)
+            .iter()
+            .filter(|queue| queue.strong_count() > 0)
+            .count()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Consumer
+
+/// A consumer of packets from a [`Hub`], with a bounded queue.
+#[derive(Debug)]
+pub struct Consumer {
+    queue: Arc<Queue>,
+}
+
+impl Consumer {
+    /// Receives the next queued packet, or `None` if the queue is empty.
+    #[must_use]
+    pub fn try_recv(&self) -> Option<Packet> {
+        self.queue.pop(&mut lock(&self.queue.state))
+    }
+
+    /// Receives the next packet, blocking until one is available.
+    #[must_use]
+    pub fn recv(&self) -> Packet {
+        let mut state = lock(&self.queue.state);
+
+        loop {
+            if let Some(packet) = self.queue.pop(&mut state) {
+                return packet;
+            }
+
+            state = self
+                .queue
+                .readable
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+    }
+
+    /// Receives the next packet, blocking until one is available or the
+    /// timeout elapses (returning `None`).
+    #[must_use]
+    pub fn recv_timeout(&self, timeout: Duration) -> Option<Packet> {
+        let (mut state, _) = self
+            .queue
+            .readable
+            .wait_timeout_while(lock(&self.queue.state), timeout, |state| {
+                state.packets.is_empty()
+            })
+            .unwrap_or_else(PoisonError::into_inner);
+
+        self.queue.pop(&mut state)
+    }
+
+    /// Returns the number of packets discarded by this consumer's queue (under
+    /// the [`Overflow::DropOldest`] or [`Overflow::DropNewest`] policies).
+    #[must_use]
+    pub fn dropped(&self) -> u64 {
+        lock(&self.queue.state).dropped
+    }
+
+    #[must_use]
+    pub fn options(&self) -> Options {
+        self.queue.options
+    }
+}
+
+impl Drop for Consumer {
+    fn drop(&mut self) {
+        // Producers blocked on this queue are released, and will find that the
+        // consumer has gone.
+
+        lock(&self.queue.state).closed = true;
+        self.queue.writable.notify_all();
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Queue
+
+#[derive(Debug)]
+struct Queue {
+    options: Options,
+    state: Mutex<State>,
+    readable: Condvar,
+    writable: Condvar,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    packets: VecDeque<Packet>,
+    dropped: u64,
+    closed: bool,
+}
+
+impl Queue {
+    fn push(&self, packet: Packet) {
+        let mut state = lock(&self.state);
+
+        if state.packets.len() >= self.options.capacity {
+            match self.options.overflow {
+                Overflow::DropOldest => {
+                    state.packets.pop_front();
+                    state.dropped += 1;
+                }
+                Overflow::DropNewest => {
+                    state.dropped += 1;
+
+                    return;
+                }
+                Overflow::Block => {
+                    state = self
+                        .writable
+                        .wait_while(state, |state| {
+                            !state.closed && state.packets.len() >= self.options.capacity
+                        })
+                        .unwrap_or_else(PoisonError::into_inner);
+
+                    if state.closed {
+                        return;
+                    }
+                }
+            }
+        }
+
+        state.packets.push_back(packet);
+        drop(state);
+
+        self.readable.notify_one();
+    }
+
+    fn pop(&self, state: &mut State) -> Option<Packet> {
+        let packet = state.packets.pop_front()?;
+
+        self.writable.notify_one();
+
+        Some(packet)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn mask<I>(values: I) -> u16
+where
+    I: IntoIterator<Item = u8>,
+{
+    values
+        .into_iter()
+        .fold(0, |mask, value| mask | 1 << (value & 0xf))
+}
@@ -0,0 +1,350 @@
+// =============================================================================
+// Interop
+// =============================================================================
+
+//! Conversions to and from the [`midly`] MIDI 1.0 event types.
+//!
+//! The [`interop`](crate::interop) module (enabled with the `midly` feature)
+//! converts between [`midly::live::LiveEvent`] values and UMP packets, so that
+//! applications built around a MIDI 1.0 event model can adopt MIDI 2.0 by
+//! converting at the boundary.
+//!
+//! Channel Voice events are translated to and from MIDI 2.0 Channel Voice
+//! messages **([M2-104-UM D.3])**, with velocities and controller values
+//! scaled between 7 and 16 or 32 bits (upscaling using the min-center-max
+//! method, so that values survive a round trip unchanged). System Common and
+//! System Real Time events map directly to their UMP equivalents.
+//!
+//! Events with no equivalent in this crate (e.g. Program Change, Pitch Bend,
+//! or System Exclusive) are not converted. Controller messages are translated
+//! individually -- Bank Select and RPN/NRPN sequences are not combined.
+
+use alloc::vec::Vec;
+
+use midly::{
+    live::{
+        LiveEvent,
+        MtcQuarterFrameMessage,
+        SystemCommon,
+        SystemRealtime,
+    },
+    num::{
+        u14,
+        u4,
+        u7,
+    },
+    MidiMessage,
+};
+
+use crate::{
+    message::{
+        system::{
+            common::{
+                self,
+                Common,
+                MIDITimeCode,
+                QuarterFrame,
+                Significance,
+                Song,
+                SongPosition,
+                SongPositionPointer,
+                SongSelect,
+                TuneRequest,
+                Type,
+            },
+            real_time::{
+                ActiveSensing,
+                Continue,
+                RealTime,
+                Reset,
+                Start,
+                Stop,
+                TimingClock,
+            },
+            System,
+        },
+        voice::{
+            ControlChange,
+            Data,
+            Index,
+            Note,
+            NoteOff,
+            NoteOn,
+            Velocity,
+            Voice,
+        },
+        Channel,
+        Group,
+        Message,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+/// Attempts to convert the given [`LiveEvent`] to a UMP packet on the given
+/// group, appending the packet to the output buffer.
+///
+/// Returns `false` (leaving the buffer unchanged) if the event has no
+/// equivalent message.
+///
+/// # Errors
+///
+/// Returns an [`Error`](crate::Error) if the message cannot be written.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::interop::*;
+/// # use midi_2_protocol::message::*;
+/// # use midly::{live::LiveEvent, num::{u4, u7}, MidiMessage};
+/// #
+/// let event = LiveEvent::Midi {
+///     channel: u4::new(1),
+///     message: MidiMessage::NoteOn { key: u7::new(60), vel: u7::new(127) },
+/// };
+///
+/// let mut out = Vec::new();
+///
+/// assert!(try_write_live_event(&event, Group::G1, &mut out)?);
+/// assert_eq!(out, [0x40913c00, 0xffff0000]);
+///
+/// let message = Message::try_from(&mut out[..])?;
+///
+/// assert_eq!(try_to_live_event(&message)?, Some(event));
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+pub fn try_write_live_event(
+    event: &LiveEvent<'_>,
+    group: Group,
+    out: &mut Vec<u32>,
+) -> Result<bool, Error> {
+    match *event {
+        LiveEvent::Midi { channel, message } => try_write_midi(channel, message, group, out),
+        LiveEvent::Common(common) => try_write_common(common, group, out),
+        LiveEvent::Realtime(real_time) => try_write_real_time(real_time, group, out),
+    }
+}
+
+/// Attempts to convert the given message to a [`LiveEvent`], returning `None`
+/// if the message has no MIDI 1.0 equivalent event. The group of the message
+/// is discarded.
+///
+/// # Errors
+///
+/// Returns an [`Error`](crate::Error) if the fields of the message cannot be
+/// read.
+pub fn try_to_live_event(message: &Message<'_>) -> Result<Option<LiveEvent<'static>>, Error> {
+    match message {
+        Message::Voice(voice) => try_to_midi(voice),
+        Message::System(System::Common(common)) => try_to_common(common),
+        Message::System(System::RealTime(real_time)) => Ok(Some(to_real_time(real_time))),
+        #[cfg(any(
+            feature = "data",
+            feature = "flex-data",
+            feature = "stream",
+            feature = "utility"
+        ))]
+        _ => Ok(None),
+    }
+}
+
+// Channel Voice
+
+fn try_write_midi(
+    channel: u4,
+    message: MidiMessage,
+    group: Group,
+    out: &mut Vec<u32>,
+) -> Result<bool, Error> {
+    let channel = Channel::try_from(channel.as_int())?;
+    let mut packet = ControlChange::packet();
+
+    match message {
+        MidiMessage::NoteOff { key, vel } => {
+            let _ = NoteOff::try_init(&mut packet, note(key), velocity(vel))?
+                .set_group(group)
+                .set_channel(channel);
+        }
+        MidiMessage::NoteOn { key, vel } if vel.as_int() == 0 => {
+            let _ = NoteOff::try_init(&mut packet, note(key), Velocity::new(0x8000))?
+                .set_group(group)
+                .set_channel(channel);
+        }
+        MidiMessage::NoteOn { key, vel } => {
+            let _ = NoteOn::try_init(&mut packet, note(key), velocity(vel))?
+                .set_group(group)
+                .set_channel(channel);
+        }
+        MidiMessage::Controller { controller, value } => {
+            let _ = ControlChange::try_init(&mut packet, Index::new(controller.as_int()))?
+                .set_group(group)
+                .set_channel(channel)
+                .set_data(Data::from_midi1(value.as_int()));
+        }
+        _ => return Ok(false),
+    }
+
+    out.extend_from_slice(&packet);
+
+    Ok(true)
+}
+
+fn try_to_midi(voice: &Voice<'_>) -> Result<Option<LiveEvent<'static>>, Error> {
+    let (channel, message) = match voice {
+        Voice::NoteOff(message) => (message.channel()?, MidiMessage::NoteOff {
+            key: u7::new(u8::from(message.note()?)),
+            vel: u7::new(message.velocity()?.to_midi1()),
+        }),
+        Voice::NoteOn(message) => (message.channel()?, MidiMessage::NoteOn {
+            key: u7::new(u8::from(message.note()?)),
+            vel: u7::new(message.velocity()?.to_midi1().max(1)),
+        }),
+        Voice::ControlChange(message) => (message.channel()?, MidiMessage::Controller {
+            controller: u7::new(u8::from(message.index()?)),
+            value: u7::new(message.data()?.to_midi1()),
+        }),
+        _ => return Ok(None),
+    };
+
+    Ok(Some(LiveEvent::Midi {
+        channel: u4::new(channel.into()),
+        message,
+    }))
+}
+
+// System Common
+
+fn try_write_common(
+    common: SystemCommon<'_>,
+    group: Group,
+    out: &mut Vec<u32>,
+) -> Result<bool, Error> {
+    let mut packet = TuneRequest::packet();
+
+    match common {
+        SystemCommon::MidiTimeCodeQuarterFrame(kind, data) => {
+            let quarter_frame = QuarterFrame(common::Data::new(data.as_int()), to_type(kind));
+            let _ = MIDITimeCode::try_init(&mut packet, quarter_frame)?.set_group(group);
+        }
+        SystemCommon::SongPosition(position) => {
+            let position = SongPosition::new(position.as_int());
+            let _ = SongPositionPointer::try_init(&mut packet, position)?.set_group(group);
+        }
+        SystemCommon::SongSelect(song) => {
+            let _ = SongSelect::try_init(&mut packet, Song::new(song.as_int()))?.set_group(group);
+        }
+        SystemCommon::TuneRequest => {
+            let _ = TuneRequest::try_init(&mut packet)?.set_group(group);
+        }
+        _ => return Ok(false),
+    }
+
+    out.extend_from_slice(&packet);
+
+    Ok(true)
+}
+
+fn try_to_common(common: &Common<'_>) -> Result<Option<LiveEvent<'static>>, Error> {
+    let common = match common {
+        Common::MIDITimeCode(message) => {
+            let QuarterFrame(data, kind) = message.quarter_frame()?;
+
+            SystemCommon::MidiTimeCodeQuarterFrame(from_type(&kind), u4::new(data.into()))
+        }
+        Common::SongPositionPointer(message) => {
+            SystemCommon::SongPosition(u14::new(message.song_position()?.into()))
+        }
+        Common::SongSelect(message) => SystemCommon::SongSelect(u7::new(message.song()?.into())),
+        Common::TuneRequest(_) => SystemCommon::TuneRequest,
+    };
+
+    Ok(Some(LiveEvent::Common(common)))
+}
+
+const fn to_type(kind: MtcQuarterFrameMessage) -> Type {
+    match kind {
+        MtcQuarterFrameMessage::FramesLow => Type::Frames(Significance::Least),
+        MtcQuarterFrameMessage::FramesHigh => Type::Frames(Significance::Most),
+        MtcQuarterFrameMessage::SecondsLow => Type::Seconds(Significance::Least),
+        MtcQuarterFrameMessage::SecondsHigh => Type::Seconds(Significance::Most),
+        MtcQuarterFrameMessage::MinutesLow => Type::Minutes(Significance::Least),
+        MtcQuarterFrameMessage::MinutesHigh => Type::Minutes(Significance::Most),
+        MtcQuarterFrameMessage::HoursLow => Type::Hours(Significance::Least),
+        MtcQuarterFrameMessage::HoursHigh => Type::Hours(Significance::Most),
+    }
+}
+
+const fn from_type(kind: &Type) -> MtcQuarterFrameMessage {
+    match kind {
+        Type::Frames(Significance::Least) => MtcQuarterFrameMessage::FramesLow,
+        Type::Frames(Significance::Most) => MtcQuarterFrameMessage::FramesHigh,
+        Type::Seconds(Significance::Least) => MtcQuarterFrameMessage::SecondsLow,
+        Type::Seconds(Significance::Most) => MtcQuarterFrameMessage::SecondsHigh,
+        Type::Minutes(Significance::Least) => MtcQuarterFrameMessage::MinutesLow,
+        Type::Minutes(Significance::Most) => MtcQuarterFrameMessage::MinutesHigh,
+        Type::Hours(Significance::Least) => MtcQuarterFrameMessage::HoursLow,
+        Type::Hours(Significance::Most) => MtcQuarterFrameMessage::HoursHigh,
+    }
+}
+
+// System Real Time
+
+fn try_write_real_time(
+    real_time: SystemRealtime,
+    group: Group,
+    out: &mut Vec<u32>,
+) -> Result<bool, Error> {
+    let mut packet = TimingClock::packet();
+
+    match real_time {
+        SystemRealtime::TimingClock => {
+            let _ = TimingClock::try_init(&mut packet)?.set_group(group);
+        }
+        SystemRealtime::Start => {
+            let _ = Start::try_init(&mut packet)?.set_group(group);
+        }
+        SystemRealtime::Continue => {
+            let _ = Continue::try_init(&mut packet)?.set_group(group);
+        }
+        SystemRealtime::Stop => {
+            let _ = Stop::try_init(&mut packet)?.set_group(group);
+        }
+        SystemRealtime::ActiveSensing => {
+            let _ = ActiveSensing::try_init(&mut packet)?.set_group(group);
+        }
+        SystemRealtime::Reset => {
+            let _ = Reset::try_init(&mut packet)?.set_group(group);
+        }
+        SystemRealtime::Undefined(_) => return Ok(false),
+    }
+
+    out.extend_from_slice(&packet);
+
+    Ok(true)
+}
+
+const fn to_real_time(real_time: &RealTime<'_>) -> LiveEvent<'static> {
+    LiveEvent::Realtime(match real_time {
+        RealTime::TimingClock(_) => SystemRealtime::TimingClock,
+        RealTime::Start(_) => SystemRealtime::Start,
+        RealTime::Continue(_) => SystemRealtime::Continue,
+        RealTime::Stop(_) => SystemRealtime::Stop,
+        RealTime::ActiveSensing(_) => SystemRealtime::ActiveSensing,
+        RealTime::Reset(_) => SystemRealtime::Reset,
+    })
+}
+
+// Values
+
+fn note(key: u7) -> Note {
+    Note::new(key.as_int())
+}
+
+fn velocity(vel: u7) -> Velocity {
+    Velocity::from_midi1(vel.as_int())
+}
@@ -0,0 +1,254 @@
+// =============================================================================
+// Latency
+// =============================================================================
+
+//! Round-trip latency measurement.
+//!
+//! The [`latency`](crate::latency) module provides a [`Probe`], which emits
+//! marker messages (Assignable Controller messages carrying a sequence number
+//! as data), matches them when they are echoed back by a loopback device or
+//! connection, and computes round-trip [`Statistics`].
+//!
+//! Times are supplied by the caller as host times in nanoseconds (from any
+//! monotonic clock), so the probe itself performs no I/O or timekeeping.
+
+use alloc::vec::Vec;
+
+use crate::{
+    learn::Descriptor,
+    message::{
+        voice::{
+            AssignableController,
+            Bank,
+            Channel,
+            Controller,
+            Data,
+            Voice,
+        },
+        Group,
+        Message,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const MAX_PENDING: usize = 64;
+const MAX_SAMPLES: usize = 1024;
+
+// -----------------------------------------------------------------------------
+
+// Statistics
+
+/// Round-trip time statistics, in nanoseconds.
+///
+/// Jitter is given as the mean absolute deviation of the round-trip times from
+/// the median.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Statistics {
+    pub count: usize,
+    pub min: u64,
+    pub median: u64,
+    pub max: u64,
+    pub jitter: u64,
+}
+
+// -----------------------------------------------------------------------------
+
+// Probe
+
+/// Emits round-trip markers and measures the time taken for them to return.
+///
+/// Markers which are not echoed back are eventually discarded (only the most
+/// recent 64 unanswered markers are retained). Statistics are computed over a
+/// window of the most recent round-trip times (1024 by default), so that a
+/// long-running probe uses bounded memory.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::latency::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// let mut probe = Probe::new(Group::G1, Channel::C16, Bank::new(127), Controller::new(127));
+///
+/// for (sent, received) in [(0, 2_000_000), (10_000_000, 13_000_000)] {
+///     let mut packet = probe.try_mark(sent)?;
+///
+///     // ...the packet is sent, and echoed back by a loopback connection...
+///
+///     assert!(probe.try_observe(&Message::try_from(&mut packet[..])?, received)?);
+/// }
+///
+/// let statistics = probe.statistics().unwrap();
+///
+/// assert_eq!(statistics.min, 2_000_000);
+/// assert_eq!(statistics.max, 3_000_000);
+///
+/// // only the most recent round-trip time is kept with a window of one
+/// let mut probe = probe.with_window(1);
+///
+/// for (sent, received) in [(20_000_000, 24_000_000), (30_000_000, 35_000_000)] {
+///     let mut packet = probe.try_mark(sent)?;
+///
+///     probe.try_observe(&Message::try_from(&mut packet[..])?, received)?;
+/// }
+///
+/// assert_eq!(probe.statistics().unwrap().count, 1);
+/// assert_eq!(probe.statistics().unwrap().min, 5_000_000);
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Probe {
+    group: Group,
+    channel: Channel,
+    bank: Bank,
+    controller: Controller,
+    sequence: u32,
+    pending: Vec<(u32, u64)>,
+    samples: Vec<u64>,
+    window: usize,
+    next: usize,
+}
+
+impl Probe {
+    /// Creates a new [`Probe`], which will use Assignable Controller messages
+    /// with the given addressing as markers. The controller should be one which
+    /// is not otherwise in use on the connection being measured.
+    #[must_use]
+    pub const fn new(group: Group, channel: Channel, bank: Bank, controller: Controller) -> Self {
+        Self {
+            group,
+            channel,
+            bank,
+            controller,
+            sequence: 0,
+            pending: Vec::new(),
+            samples: Vec::new(),
+            window: MAX_SAMPLES,
+            next: 0,
+        }
+    }
+
+    /// Sets the number of most recent round-trip times over which statistics
+    /// are computed (at least one round-trip time is always retained),
+    /// discarding any recorded round-trip times.
+    #[must_use]
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self.samples.clear();
+        self.next = 0;
+        self
+    }
+
+    /// Returns a new marker packet, recording it as sent at the given host time
+    /// (in nanoseconds).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the marker message cannot be
+    /// initialized.
+    pub fn try_mark(&mut self, now: u64) -> Result<[u32; 2], Error> {
+        let mut packet = AssignableController::packet();
+
+        let _ = AssignableController::try_init(&mut packet, self.bank, self.controller)?
+            .set_group(self.group)
+            .set_channel(self.channel)
+            .set_data(Data::new(self.sequence));
+
+        if self.pending.len() == MAX_PENDING {
+            self.pending.remove(0);
+        }
+
+        self.pending.push((self.sequence, now));
+        self.sequence = self.sequence.wrapping_add(1);
+
+        Ok(packet)
+    }
+
+    /// Observes a received message at the given host time (in nanoseconds),
+    /// returning `true` if it was an echoed marker (in which case a round-trip
+    /// time is recorded).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the fields of the message cannot
+    /// be read.
+    pub fn try_observe(&mut self, message: &Message<'_>, now: u64) -> Result<bool, Error> {
+        let descriptor =
+            Descriptor::AssignableController(self.group, self.channel, self.bank, self.controller);
+
+        if !descriptor.try_matches(message)? {
+            return Ok(false);
+        }
+
+        let sequence = match message {
+            Message::Voice(Voice::AssignableController(marker)) => u32::from(marker.data()?),
+            Message::Voice(_) => return Ok(false),
+            #[cfg(any(
+                feature = "data",
+                feature = "flex-data",
+                feature = "stream",
+                feature = "system",
+                feature = "utility"
+            ))]
+            _ => return Ok(false),
+        };
+
+        match self.pending.iter().position(|(s, _)| *s == sequence) {
+            Some(position) => {
+                let (_, sent) = self.pending.remove(position);
+
+                self.record(now.saturating_sub(sent));
+
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Returns statistics for the round-trip times recorded so far, or `None`
+    /// if no markers have yet returned.
+    #[must_use]
+    pub fn statistics(&self) -> Option<Statistics> {
+        let mut samples = self.samples.clone();
+
+        samples.sort_unstable();
+
+        let count = samples.len();
+        let median = *samples.get(count / 2)?;
+        let deviation: u64 = samples.iter().map(|sample| sample.abs_diff(median)).sum();
+
+        Some(Statistics {
+            count,
+            min: samples[0],
+            median,
+            max: samples[count - 1],
+            jitter: deviation / count as u64,
+        })
+    }
+
+    /// Discards all pending markers and recorded round-trip times.
+    pub fn reset(&mut self) {
+        self.pending.clear();
+        self.samples.clear();
+        self.next = 0;
+    }
+
+    // Records a round-trip time, replacing the oldest recorded time once the
+    // window is full (the order of the samples is not significant).
+
+    fn record(&mut self, sample: u64) {
+        if self.samples.len() < self.window {
+            self.samples.push(sample);
+        } else {
+            self.samples[self.next] = sample;
+            self.next = (self.next + 1) % self.window;
+        }
+    }
+}
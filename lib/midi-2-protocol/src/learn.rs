@@ -0,0 +1,268 @@
+// =============================================================================
+// Learn
+// =============================================================================
+
+//! "MIDI learn" classification of incoming controls.
+//!
+//! The [`learn`](crate::learn) module identifies the controlling entity behind
+//! a stream of incoming messages (a controller index, a per-note controller,
+//! pitch bend, or note velocity) as a stable [`Descriptor`], which can then be
+//! stored and used to match later messages from the same control.
+//!
+//! A [`Learner`] observes messages while the user moves a control, and reports
+//! a [`Descriptor`] once the same control has been seen often enough within a
+//! short window of recent messages to rule out incidental traffic (e.g. a stray
+//! note while a fader is moved).
+
+use alloc::vec::Vec;
+
+use crate::{
+    message::{
+        voice::{
+            Bank,
+            Channel,
+            Controller,
+            Index,
+            PerNoteController,
+            Voice,
+        },
+        Group,
+        Message,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const WINDOW: usize = 16;
+
+// -----------------------------------------------------------------------------
+
+// Descriptor
+
+/// A stable description of a single control, independent of its value.
+///
+/// Per-note descriptors deliberately ignore the note, so that (for example) a
+/// per-note controller learned from one key will match the same controller on
+/// every key.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Descriptor {
+    ControlChange(Group, Channel, Index),
+    RegisteredController(Group, Channel, Bank, Controller),
+    AssignableController(Group, Channel, Bank, Controller),
+    RegisteredPerNoteController(Group, Channel, PerNoteController),
+    AssignablePerNoteController(Group, Channel, PerNoteController),
+    PerNotePitchBend(Group, Channel),
+    Velocity(Group, Channel),
+}
+
+impl Descriptor {
+    /// Returns the [`Descriptor`] of the control which produced the message,
+    /// or `None` if the message does not come from a learnable control.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the fields of the message cannot
+    /// be read.
+    pub fn try_from_message(message: &Message<'_>) -> Result<Option<Self>, Error> {
+        #[allow(clippy::infallible_destructuring_match)]
+        let voice = match message {
+            Message::Voice(voice) => voice,
+            #[cfg(any(
+                feature = "data",
+                feature = "flex-data",
+                feature = "stream",
+                feature = "system",
+                feature = "utility"
+            ))]
+            _ => return Ok(None),
+        };
+
+        let descriptor = match voice {
+            Voice::ControlChange(m) => Self::ControlChange(m.group()?, m.channel()?, m.index()?),
+            Voice::RegisteredController(m) => {
+                Self::RegisteredController(m.group()?, m.channel()?, m.bank()?, m.controller()?)
+            }
+            Voice::AssignableController(m) => {
+                Self::AssignableController(m.group()?, m.channel()?, m.bank()?, m.controller()?)
+            }
+            Voice::RegisteredPerNoteController(m) => Self::RegisteredPerNoteController(
+                m.group()?,
+                m.channel()?,
+                m.per_note_controller()?,
+            ),
+            Voice::AssignablePerNoteController(m) => Self::AssignablePerNoteController(
+                m.group()?,
+                m.channel()?,
+                m.per_note_controller()?,
+            ),
+            Voice::PerNotePitchBend(m) => Self::PerNotePitchBend(m.group()?, m.channel()?),
+            Voice::NoteOn(m) => Self::Velocity(m.group()?, m.channel()?),
+            _ => return Ok(None),
+        };
+
+        Ok(Some(descriptor))
+    }
+
+    /// Returns `true` if the message was produced by the control this
+    /// [`Descriptor`] describes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the fields of the message cannot
+    /// be read.
+    pub fn try_matches(&self, message: &Message<'_>) -> Result<bool, Error> {
+        Ok(Self::try_from_message(message)?.as_ref() == Some(self))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Learner
+
+/// Observes incoming messages and identifies the control being moved.
+///
+/// A control is identified once it has produced a threshold number of the
+/// most recent learnable messages (those with a [`Descriptor`]), within a
+/// window of 16 messages by default. Older messages expire from the window, so
+/// that occasional stray messages from another control do not add up over time
+/// and are never learned in place of the control being moved.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::learn::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// let mut learner = Learner::new(2);
+///
+/// let mut packet = ControlChange::packet();
+/// let _ = ControlChange::try_init(&mut packet, Index::new(74))?.set_channel(Channel::C3);
+///
+/// // the first message is not yet enough to identify the control...
+/// assert_eq!(learner.try_observe(&Message::try_from(&mut packet[..])?)?, None);
+///
+/// // ...but the second is
+/// assert_eq!(
+///     learner.try_observe(&Message::try_from(&mut packet[..])?)?,
+///     Some(Descriptor::ControlChange(Group::G1, Channel::C3, Index::new(74)))
+/// );
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+///
+/// Messages from a noisy control expire before they reach the threshold:
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::learn::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// let mut learner = Learner::new(3).with_window(4);
+///
+/// let mut observe = |index: u8| {
+///     let mut packet = ControlChange::packet();
+///     let _ = ControlChange::try_init(&mut packet, Index::new(index))?;
+///
+///     learner.try_observe(&Message::try_from(&mut packet[..])?)
+/// };
+///
+/// // a stray controller (1), seen once in every window of four messages...
+/// for index in [1, 2, 3, 4, 1, 5, 6, 7, 1] {
+///     assert_eq!(observe(index)?, None);
+/// }
+///
+/// // ...is never learned, while the controller being moved (74) is
+/// assert_eq!(observe(74)?, None);
+/// assert_eq!(observe(74)?, None);
+/// assert_eq!(
+///     observe(74)?,
+///     Some(Descriptor::ControlChange(Group::G1, Channel::C1, Index::new(74)))
+/// );
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Learner {
+    threshold: usize,
+    observations: Vec<Descriptor>,
+    window: usize,
+    next: usize,
+}
+
+impl Learner {
+    /// Creates a new [`Learner`], which will identify a control once it has
+    /// produced `threshold` messages (a threshold of zero is treated as one).
+    #[must_use]
+    pub const fn new(threshold: usize) -> Self {
+        Self {
+            threshold,
+            observations: Vec::new(),
+            window: WINDOW,
+            next: 0,
+        }
+    }
+
+    /// Sets the number of most recent learnable messages within which a
+    /// control must produce the threshold number of messages (a window of
+    /// zero is treated as one), discarding any observations.
+    #[must_use]
+    pub fn with_window(mut self, window: usize) -> Self {
+        self.window = window.max(1);
+        self.reset();
+        self
+    }
+
+    /// Observes a message, returning the [`Descriptor`] of the learned control
+    /// once one has been identified.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the fields of the message cannot
+    /// be read.
+    pub fn try_observe(&mut self, message: &Message<'_>) -> Result<Option<Descriptor>, Error> {
+        let descriptor = match Descriptor::try_from_message(message)? {
+            Some(descriptor) => descriptor,
+            None => return Ok(None),
+        };
+
+        self.record(descriptor);
+
+        let count = self
+            .observations
+            .iter()
+            .filter(|observed| **observed == descriptor)
+            .count();
+
+        Ok((count >= self.threshold).then_some(descriptor))
+    }
+
+    /// Discards all observations, ready to learn a new control.
+    pub fn reset(&mut self) {
+        self.observations.clear();
+        self.next = 0;
+    }
+
+    // Records an observation, replacing the oldest observation once the window
+    // is full (the order of the observations is not significant).
+
+    fn record(&mut self, descriptor: Descriptor) {
+        if self.observations.len() < self.window {
+            self.observations.push(descriptor);
+        } else {
+            self.observations[self.next] = descriptor;
+            self.next = (self.next + 1) % self.window;
+        }
+    }
+}
+
+impl Default for Learner {
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
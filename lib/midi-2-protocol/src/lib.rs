@@ -1,9 +1,77 @@
+#![no_std]
+
+// The message types are usable with core alone -- modules which allocate
+// require the `alloc` feature, and those which use platform facilities (time,
+// I/O, threads and floating point functions) require the `std` feature. The
+// `Debug` and `Display` implementations of messages (and anything else which
+// exists only to format values) require the `fmt` feature, so that firmware
+// builds can drop the formatting code. The transport modules (the framing of
+// packets over byte streams, and the hub) require the `transports` feature.
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 mod field;
-mod packet;
 
+#[cfg(all(feature = "alloc", feature = "flex-data", feature = "voice"))]
+pub mod analysis;
+pub mod annotation;
+#[cfg(feature = "alloc")]
+pub mod archive;
+#[cfg(all(feature = "alloc", feature = "voice"))]
+pub mod catalog;
+#[cfg(feature = "std")]
+pub mod clock;
+#[cfg(all(feature = "stream", feature = "utility"))]
+pub mod clockstamp;
+#[cfg(feature = "alloc")]
+pub mod debugger;
+#[cfg(feature = "std")]
+pub mod drift;
+#[cfg(all(feature = "alloc", feature = "voice"))]
+pub mod drum_map;
+#[cfg(feature = "alloc")]
+pub mod echo;
+pub mod filter;
+#[cfg(feature = "transports")]
+pub mod framing;
+#[cfg(feature = "transports")]
+pub mod hub;
+#[cfg(feature = "midly")]
+pub mod interop;
+#[cfg(all(feature = "alloc", feature = "voice"))]
+pub mod latency;
+#[cfg(all(feature = "alloc", feature = "voice"))]
+pub mod learn;
+#[cfg(all(feature = "std", feature = "voice"))]
+pub mod mapping;
 pub mod message;
+#[cfg(all(feature = "alloc", feature = "flex-data", feature = "voice"))]
+pub mod metronome;
+#[cfg(all(feature = "alloc", feature = "voice"))]
+pub mod mono;
+#[cfg(all(feature = "flex-data", feature = "voice"))]
+pub mod notation;
+pub mod packet;
+#[cfg(all(feature = "alloc", feature = "voice"))]
+pub mod panic;
+pub mod prelude;
+#[cfg(all(feature = "flex-data", feature = "voice"))]
+pub mod quantize;
+pub mod scale;
+#[cfg(feature = "alloc")]
+pub mod sysex;
+#[cfg(feature = "std")]
+pub mod timestamp;
+#[cfg(feature = "alloc")]
+pub mod transform;
+#[cfg(feature = "alloc")]
+pub mod translate;
 
-use thiserror::Error;
+#[cfg(feature = "fmt")]
+use core::fmt;
 
 // =============================================================================
 // MIDI 2 Protocol
@@ -11,14 +79,16 @@ use thiserror::Error;
 
 // Errors
 
-#[derive(Debug, Error)]
+#[derive(Clone, Copy, Debug)]
 pub enum Error {
-    #[error("Conversion: Attempted to convert from {0}, not a valid variant.")]
     Conversion(u8),
-    #[error("Overflow: Attempted to store value {0} in a {1} bit type.")]
+    Limit { limit: usize, needed: usize },
     Overflow(u64, u8),
-    #[error("Size: Expected a packet of {0} bits, but found {1} bits.")]
+    Reserved(&'static str, u8, u8),
+    Sequence(&'static str),
     Size(u8, u8),
+    Truncated { needed: usize, available: usize },
+    Unsupported(&'static str),
 }
 
 impl Error {
@@ -26,11 +96,76 @@ impl Error {
         Self::Conversion(value)
     }
 
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn limit(limit: usize, needed: usize) -> Self {
+        Self::Limit { limit, needed }
+    }
+
     pub(crate) fn overflow(value: impl Into<u64>, size: u8) -> Self {
         Self::Overflow(value.into(), size)
     }
 
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn reserved(message: &'static str, first: u8, last: u8) -> Self {
+        Self::Reserved(message, first, last)
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn sequence(message: &'static str) -> Self {
+        Self::Sequence(message)
+    }
+
     pub(crate) const fn size(expected: u8, actual: u8) -> Self {
         Self::Size(expected, actual)
     }
+
+    pub(crate) const fn truncated(needed: usize, available: usize) -> Self {
+        Self::Truncated { needed, available }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) const fn unsupported(message: &'static str) -> Self {
+        Self::Unsupported(message)
+    }
 }
+
+#[cfg(feature = "fmt")]
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Conversion(value) => write!(
+                f,
+                "Conversion: Attempted to convert from {value}, not a valid variant."
+            ),
+            Self::Limit { limit, needed } => write!(
+                f,
+                "Limit: Required {needed} bytes, exceeding the limit of {limit} bytes."
+            ),
+            Self::Overflow(value, size) => write!(
+                f,
+                "Overflow: Attempted to store value {value} in a {size} bit type."
+            ),
+            Self::Reserved(message, first, last) => write!(
+                f,
+                "Reserved: Found non-zero reserved bits {first}..={last} in a {message} message."
+            ),
+            Self::Sequence(message) => {
+                write!(f, "Sequence: Found a {message} message out of sequence.")
+            }
+            Self::Size(expected, actual) => write!(
+                f,
+                "Size: Expected a packet of {expected} bits, but found {actual} bits."
+            ),
+            Self::Truncated { needed, available } => write!(
+                f,
+                "Truncated: Expected a packet of {needed} words, but found {available} words."
+            ),
+            Self::Unsupported(message) => {
+                write!(f, "Unsupported: Found a {message} message, which is not supported.")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
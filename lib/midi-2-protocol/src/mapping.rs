@@ -0,0 +1,607 @@
+// =============================================================================
+// Mapping
+// =============================================================================
+
+//! Mapping of incoming controls to application parameters.
+//!
+//! The [`mapping`](crate::mapping) module resolves incoming messages against a
+//! set of [`Binding`]s (typically created from [`learn`](crate::learn)
+//! descriptors), producing normalized parameter [`Change`]s for the host
+//! application.
+//!
+//! All parameter values are normalized to the range `0.0..=1.0`. Each binding
+//! may restrict the control to a sub-range of the parameter, shape the control
+//! response with a [`Curve`], and choose how the control takes over from the
+//! current parameter value with a [`Takeover`] mode.
+//!
+//! A [`Calibration`] (measured from a recorded sweep of a physical control)
+//! may be used as a curve, to compensate for a non-linear hardware response.
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "serde")]
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::{
+    learn::Descriptor,
+    message::{
+        voice::Voice,
+        Message,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const CALIBRATION_SEGMENTS: u8 = 16;
+
+// -----------------------------------------------------------------------------
+
+// Values
+
+// Curve
+
+/// The response curve applied to a normalized control value.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Curve {
+    /// The control value is used as-is.
+    #[default]
+    Linear,
+    /// The control value is raised to the given power (values above 1.0 give
+    /// finer control at the low end of the range, values below 1.0 give finer
+    /// control at the high end).
+    Power(f64),
+    /// The control value is reversed (1.0 - value).
+    Inverted,
+    /// The control value is corrected using a measured [`Calibration`].
+    Calibrated(Calibration),
+}
+
+impl Curve {
+    fn apply(self, value: f64) -> f64 {
+        match self {
+            Self::Linear => value,
+            Self::Power(exponent) => value.powf(exponent),
+            Self::Inverted => 1.0 - value,
+            Self::Calibrated(calibration) => calibration.apply(value),
+        }
+    }
+
+    fn invert(self, value: f64) -> f64 {
+        match self {
+            Self::Linear => value,
+            Self::Power(exponent) => value.powf(exponent.recip()),
+            Self::Inverted => 1.0 - value,
+            Self::Calibrated(calibration) => calibration.invert(value),
+        }
+    }
+}
+
+// Takeover
+
+/// How a control takes over from the current value of a parameter.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Takeover {
+    /// The parameter jumps to the control value immediately.
+    #[default]
+    Jump,
+    /// Control values are ignored until the control reaches (or crosses) the
+    /// current parameter value, avoiding sudden jumps (see [`Pickup`]).
+    Pickup,
+}
+
+// -----------------------------------------------------------------------------
+
+// Calibration
+
+/// A correction curve for a physical control with a non-linear response,
+/// measured from a recorded sweep of the control.
+///
+/// A calibration maps the normalized values reported by the control to the
+/// (normalized) physical positions at which they were reported, using a
+/// piecewise-linear table of 16 segments, so that the corrected value is
+/// linear in the travel of the control. The range of reported values is also
+/// stretched to the full range, compensating for controls which do not reach
+/// the ends of their range.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::mapping::*;
+/// #
+/// // a fader swept at constant speed, with a response which rises quickly
+/// // and stops short of the maximum value
+/// let sweep: Vec<(u64, f64)> = (0..=100u32)
+///     .map(|i| (u64::from(i) * 10, 0.9 * (f64::from(i) / 100.0).sqrt()))
+///     .collect();
+///
+/// let calibration = Calibration::from_sweep(&sweep).unwrap();
+///
+/// assert!((calibration.apply(0.45) - 0.25).abs() < 0.01);
+/// assert!((calibration.apply(0.9) - 1.0).abs() < 0.01);
+/// assert!((calibration.invert(0.25) - 0.45).abs() < 0.01);
+///
+/// // the calibration is used as the curve of a binding
+/// let curve = Curve::Calibrated(calibration);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct Calibration {
+    /// The lowest value reported by the control.
+    pub min: f64,
+    /// The highest value reported by the control.
+    pub max: f64,
+    /// The corrected values at evenly spaced points between the lowest and
+    /// highest reported values (non-decreasing, from 0.0 to 1.0).
+    pub points: [f64; CALIBRATION_SEGMENTS as usize + 1],
+}
+
+impl Calibration {
+    /// Measures a calibration from a recorded sweep of the control over its
+    /// full travel (in either direction) at a constant speed, given as the
+    /// times (in any unit) and normalized values of the messages received, in
+    /// time order. Returns `None` if the sweep does not cover a range of both
+    /// time and value.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_sweep(samples: &[(u64, f64)]) -> Option<Self> {
+        let &(start, first) = samples.first()?;
+        let &(end, last) = samples.last()?;
+
+        if end <= start {
+            return None;
+        }
+
+        // Samples are oriented so that values rise with position, and values
+        // which fall back (e.g. due to noise) are held at their previous peak.
+
+        let duration = (end - start) as f64;
+        let mut sweep: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|&(time, value)| (time.saturating_sub(start) as f64 / duration, value))
+            .collect();
+
+        if last < first {
+            sweep.reverse();
+
+            for (position, _) in &mut sweep {
+                *position = 1.0 - *position;
+            }
+        }
+
+        let mut peak = f64::MIN;
+
+        for (_, value) in &mut sweep {
+            peak = peak.max(*value);
+            *value = peak;
+        }
+
+        let min = sweep[0].1;
+        let max = peak;
+
+        if max <= min {
+            return None;
+        }
+
+        let mut points = [0.0; CALIBRATION_SEGMENTS as usize + 1];
+
+        points[usize::from(CALIBRATION_SEGMENTS)] = 1.0;
+
+        for i in 1..CALIBRATION_SEGMENTS {
+            let value = (max - min).mul_add(f64::from(i) / f64::from(CALIBRATION_SEGMENTS), min);
+            let k = sweep.iter().position(|(_, v)| *v >= value)?;
+            let (p0, v0) = sweep[k - 1];
+            let (p1, v1) = sweep[k];
+
+            points[usize::from(i)] = ((value - v0) / (v1 - v0)).mul_add(p1 - p0, p0);
+        }
+
+        Some(Self { min, max, points })
+    }
+
+    /// Returns the corrected value for a normalized value reported by the
+    /// control.
+    #[must_use]
+    pub fn apply(&self, value: f64) -> f64 {
+        let x = ((value - self.min) / (self.max - self.min)).clamp(0.0, 1.0)
+            * f64::from(CALIBRATION_SEGMENTS);
+        let i = segment(|i| f64::from(i) <= x);
+        let (y0, y1) = (self.points[usize::from(i)], self.points[usize::from(i) + 1]);
+
+        (x - f64::from(i)).mul_add(y1 - y0, y0)
+    }
+
+    /// Returns the normalized value which the control reports for the given
+    /// corrected value (the inverse of [`Calibration::apply`]).
+    #[must_use]
+    pub fn invert(&self, value: f64) -> f64 {
+        let value = value.clamp(0.0, 1.0);
+        let i = segment(|i| self.points[usize::from(i)] <= value);
+        let (y0, y1) = (self.points[usize::from(i)], self.points[usize::from(i) + 1]);
+        let x = if y1 > y0 {
+            f64::from(i) + (value - y0) / (y1 - y0)
+        } else {
+            f64::from(i)
+        };
+
+        (x / f64::from(CALIBRATION_SEGMENTS)).mul_add(self.max - self.min, self.min)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Pickup
+
+/// Pickup (soft-takeover) reconciliation between a control and a target value.
+///
+/// A [`Pickup`] ignores control values until the control reaches the target
+/// value, or crosses it between two successive values, at which point it is
+/// engaged and all further values are accepted (until a new target is set).
+///
+/// Values are compared in control space (normalized `0.0..=1.0`), where a
+/// control of limited resolution can only produce a fixed set of steps -- a
+/// 7-bit controller upscaled to a 32-bit value can only reach one of 128
+/// values, and so will rarely hit an arbitrary target exactly. The pickup is
+/// therefore also engaged when a value lands within one step of the target,
+/// based on the resolution of the control.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::mapping::*;
+/// #
+/// let mut pickup = Pickup::new(7);
+///
+/// pickup.set_target(0.5);
+///
+/// // values below the target are ignored...
+/// assert!(!pickup.update(0.2));
+/// assert!(!pickup.update(0.4));
+///
+/// // ...until the control crosses the target
+/// assert!(pickup.update(0.6));
+/// assert!(pickup.update(0.1));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Pickup {
+    tolerance: f64,
+    target: Option<f64>,
+    previous: Option<f64>,
+    engaged: bool,
+}
+
+impl Pickup {
+    /// Creates a new [`Pickup`] for a control with the given resolution (in
+    /// bits, e.g. 7 for a MIDI 1.0 controller, 32 for a MIDI 2.0 controller).
+    /// With no target set, the pickup is engaged.
+    #[must_use]
+    pub fn new(resolution: u8) -> Self {
+        let steps = 2f64.powi(i32::from(resolution.clamp(1, 32))) - 1.0;
+
+        Self {
+            tolerance: steps.recip(),
+            target: None,
+            previous: None,
+            engaged: true,
+        }
+    }
+
+    /// Sets a new target value, disengaging the pickup until the control
+    /// reaches it.
+    pub fn set_target(&mut self, target: f64) {
+        self.target = Some(target);
+        self.previous = None;
+        self.engaged = false;
+    }
+
+    /// Updates the pickup with a new control value, returning `true` if the
+    /// pickup is engaged (and so the value should be applied).
+    pub fn update(&mut self, value: f64) -> bool {
+        if let (false, Some(target)) = (self.engaged, self.target) {
+            let reached = (value - target).abs() <= self.tolerance;
+            let crossed = self
+                .previous
+                .map_or(false, |previous| (previous < target) != (value < target));
+
+            self.engaged = reached || crossed;
+            self.previous = Some(value);
+        }
+
+        self.engaged
+    }
+
+    #[must_use]
+    pub const fn is_engaged(&self) -> bool {
+        self.engaged
+    }
+}
+
+impl Default for Pickup {
+    fn default() -> Self {
+        Self::new(32)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Binding
+
+/// A binding of a control (identified by a [`Descriptor`]) to a parameter.
+///
+/// Bindings are created with default scaling (the full parameter range, a
+/// linear curve, jump takeover, and a 32-bit control resolution), which may be
+/// changed using builder-style `set_*` functions.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Binding<P> {
+    pub descriptor: Descriptor,
+    pub parameter: P,
+    pub range: (f64, f64),
+    pub curve: Curve,
+    pub takeover: Takeover,
+    pub resolution: u8,
+}
+
+impl<P> Binding<P> {
+    /// Creates a new [`Binding`] of the control to the parameter, with default
+    /// scaling.
+    #[must_use]
+    pub const fn new(descriptor: Descriptor, parameter: P) -> Self {
+        Self {
+            descriptor,
+            parameter,
+            range: (0.0, 1.0),
+            curve: Curve::Linear,
+            takeover: Takeover::Jump,
+            resolution: 32,
+        }
+    }
+
+    /// Sets the (normalized) parameter range covered by the full travel of the
+    /// control. The range may be reversed (`min > max`).
+    #[must_use]
+    pub const fn set_range(mut self, min: f64, max: f64) -> Self {
+        self.range = (min, max);
+        self
+    }
+
+    #[must_use]
+    pub const fn set_curve(mut self, curve: Curve) -> Self {
+        self.curve = curve;
+        self
+    }
+
+    #[must_use]
+    pub const fn set_takeover(mut self, takeover: Takeover) -> Self {
+        self.takeover = takeover;
+        self
+    }
+
+    /// Sets the effective resolution of the control in bits (e.g. 7 for a
+    /// control on a MIDI 1.0 device, where values have been upscaled), used
+    /// to reconcile [`Takeover::Pickup`] values.
+    #[must_use]
+    pub const fn set_resolution(mut self, resolution: u8) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    fn scale(&self, value: f64) -> f64 {
+        let (min, max) = self.range;
+
+        self.curve
+            .apply(value.clamp(0.0, 1.0))
+            .mul_add(max - min, min)
+            .clamp(min.min(max), min.max(max))
+    }
+
+    fn unscale(&self, value: f64) -> f64 {
+        let (min, max) = self.range;
+        let value = if (max - min).abs() > f64::EPSILON {
+            (value - min) / (max - min)
+        } else {
+            0.0
+        };
+
+        self.curve.invert(value.clamp(0.0, 1.0))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Change
+
+/// A normalized parameter value change resulting from an incoming message.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Change<P> {
+    pub parameter: P,
+    pub value: f64,
+}
+
+// -----------------------------------------------------------------------------
+
+// Engine
+
+/// Resolves incoming messages to parameter changes using registered bindings.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::learn::*;
+/// # use midi_2_protocol::mapping::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// let mut engine = Engine::new();
+/// let descriptor = Descriptor::ControlChange(Group::G1, Channel::C1, Index::new(7));
+///
+/// engine.bind(Binding::new(descriptor, "volume").set_range(0.0, 0.5));
+///
+/// let mut packet = ControlChange::packet();
+/// let _ = ControlChange::try_init(&mut packet, Index::new(7))?.set_data(Data::new(u32::MAX));
+///
+/// let changes = engine.try_resolve(&Message::try_from(&mut packet[..])?)?;
+///
+/// assert_eq!(changes, [Change { parameter: "volume", value: 0.5 }]);
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug)]
+pub struct Engine<P> {
+    bindings: Vec<(Binding<P>, State)>,
+}
+
+#[derive(Debug)]
+struct State {
+    pickup: Pickup,
+}
+
+impl<P> Engine<P>
+where
+    P: Copy + PartialEq,
+{
+    /// Creates a new [`Engine`] with no bindings.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            bindings: Vec::new(),
+        }
+    }
+
+    /// Registers a binding. A control may be bound to several parameters, and
+    /// a parameter may be bound to several controls.
+    pub fn bind(&mut self, binding: Binding<P>) {
+        let pickup = Pickup::new(binding.resolution);
+
+        self.bindings.push((binding, State { pickup }));
+    }
+
+    /// Removes all bindings to the given parameter.
+    pub fn unbind(&mut self, parameter: P) {
+        self.bindings
+            .retain(|(binding, _)| binding.parameter != parameter);
+    }
+
+    /// Informs the engine of the current value of a parameter, when changed by
+    /// the host (e.g. by loading a preset, or by editing in the UI). Controls
+    /// bound to the parameter using [`Takeover::Pickup`] will need to pick up
+    /// the new value before having any further effect.
+    pub fn set_value(&mut self, parameter: P, value: f64) {
+        for (binding, state) in &mut self.bindings {
+            if binding.parameter == parameter {
+                state.pickup.set_target(binding.unscale(value));
+            }
+        }
+    }
+
+    /// Resolves an incoming message against the registered bindings, returning
+    /// the resulting parameter changes (if any).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the fields of the message cannot
+    /// be read.
+    pub fn try_resolve(&mut self, message: &Message<'_>) -> Result<Vec<Change<P>>, Error> {
+        let mut changes = Vec::new();
+
+        let descriptor = match Descriptor::try_from_message(message)? {
+            Some(descriptor) => descriptor,
+            None => return Ok(changes),
+        };
+
+        let control = match try_read_value(message)? {
+            Some(control) => control,
+            None => return Ok(changes),
+        };
+
+        for (binding, state) in &mut self.bindings {
+            if binding.descriptor != descriptor {
+                continue;
+            }
+
+            if binding.takeover == Takeover::Pickup && !state.pickup.update(control) {
+                continue;
+            }
+
+            changes.push(Change {
+                parameter: binding.parameter,
+                value: binding.scale(control),
+            });
+        }
+
+        // Other controls bound to a changed parameter must now pick up the new
+        // value in turn.
+
+        for change in &changes {
+            for (binding, state) in &mut self.bindings {
+                if binding.parameter == change.parameter && binding.descriptor != descriptor {
+                    state.pickup.set_target(binding.unscale(change.value));
+                }
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+impl<P> Default for Engine<P>
+where
+    P: Copy + PartialEq,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Values
+
+fn try_read_value(message: &Message<'_>) -> Result<Option<f64>, Error> {
+    #[allow(clippy::infallible_destructuring_match)]
+    let voice = match message {
+        Message::Voice(voice) => voice,
+        #[cfg(any(
+            feature = "data",
+            feature = "flex-data",
+            feature = "stream",
+            feature = "system",
+            feature = "utility"
+        ))]
+        _ => return Ok(None),
+    };
+
+    let value = match voice {
+        Voice::ControlChange(m) => u32::from(m.data()?),
+        Voice::RegisteredController(m) => u32::from(m.data()?),
+        Voice::AssignableController(m) => u32::from(m.data()?),
+        Voice::RegisteredPerNoteController(m) => u32::from(m.data()?),
+        Voice::AssignablePerNoteController(m) => u32::from(m.data()?),
+        Voice::PerNotePitchBend(m) => u32::from(m.data()?),
+        Voice::NoteOn(m) => {
+            return Ok(Some(
+                f64::from(u16::from(m.velocity()?)) / f64::from(u16::MAX),
+            ));
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(f64::from(value) / f64::from(u32::MAX)))
+}
+
+// Returns the last calibration segment whose start satisfies the predicate.
+
+fn segment<F>(predicate: F) -> u8
+where
+    F: Fn(u8) -> bool,
+{
+    (0..CALIBRATION_SEGMENTS)
+        .rev()
+        .find(|&i| predicate(i))
+        .unwrap_or(0)
+}
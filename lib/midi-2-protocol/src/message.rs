@@ -40,11 +40,73 @@
 //! initialize the packet to contain the supplied message data, and return a
 //! type which can further modify the packet as needed.
 //!
+//! Where only the packet is needed, each message type also implements a
+//! `builder()` function, returning a builder with a function to set each
+//! field (from a value of the field type, or from a raw integral value), which
+//! produces the initialized packet directly. Raw values are validated, and the
+//! first invalid value is returned as an error when the packet is built.
+//!
+//! ```rust
+//! # use midi_2_protocol::*;
+//! # #[cfg(feature = "voice")]
+//! # {
+//! # use midi_2_protocol::message::*;
+//! # use midi_2_protocol::message::voice::*;
+//! #
+//! let packet = NoteOn::builder()
+//!     .group(Group::G4)
+//!     .channel(Channel::C6)
+//!     .note(64)
+//!     .velocity(30000)
+//!     .build()?;
+//!
+//! assert_eq!(packet, [0x43954000, 0x75300000]);
+//!
+//! // 128 is not a valid (7-bit) note number
+//! assert!(NoteOn::builder().note(128).build().is_err());
+//! # }
+//! #
+//! # Ok::<(), Error>(())
+//! ```
+//!
 //! [1]: https://midi.org/specifications/universal-midi-packet-ump-and-midi-2-0-protocol-specification/download
 
+#[cfg(feature = "data")]
+pub mod data;
+#[cfg(feature = "flex-data")]
+pub mod flex_data;
+#[cfg(feature = "stream")]
+pub mod stream;
+#[cfg(feature = "system")]
 pub mod system;
+#[cfg(feature = "utility")]
+pub mod utility;
+#[cfg(feature = "voice")]
 pub mod voice;
 
+#[cfg(not(any(
+    feature = "data",
+    feature = "flex-data",
+    feature = "stream",
+    feature = "system",
+    feature = "utility",
+    feature = "voice"
+)))]
+compile_error!(
+    "at least one message family feature (data, flex-data, stream, system, utility, voice) must \
+     be enabled"
+);
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "fmt")]
+use core::fmt;
+use core::{
+    iter::FusedIterator,
+    mem,
+    ops::RangeInclusive,
+};
+
 use bitvec::{
     field::BitField,
     order::Msb0,
@@ -65,6 +127,7 @@ use crate::{
     message,
     packet::{
         GetBitSlice,
+        Packet,
         TryReadField,
     },
     Error,
@@ -94,6 +157,8 @@ use crate::{
 ///
 /// ```rust
 /// # use midi_2_protocol::*;
+/// # #[cfg(feature = "system")]
+/// # {
 /// # use midi_2_protocol::message::*;
 /// # use midi_2_protocol::message::system::real_time::*;
 /// #
@@ -101,11 +166,12 @@ use crate::{
 /// let mut message = TimingClock::try_init(&mut packet)?;
 ///
 /// assert_eq!(message.message_type()?, MessageType::System);
+/// # }
 /// #
 /// # Ok::<(), Error>(())
 /// ```
 #[allow(clippy::module_name_repetitions)]
-#[derive(Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
 #[num_enum(error_type(name = Error, constructor = Error::conversion))]
 #[repr(u8)]
 pub enum MessageType {
@@ -118,8 +184,36 @@ pub enum MessageType {
     Stream = 0xf,
 }
 
+impl MessageType {
+    /// Returns the number of 32-bit words in a message of this Message Type
+    /// **([M2-104-UM 2.1.4])**.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::message::*;
+    /// #
+    /// assert_eq!(MessageType::System.word_count(), 1);
+    /// assert_eq!(MessageType::Voice.word_count(), 2);
+    /// assert_eq!(MessageType::FlexData.word_count(), 4);
+    /// ```
+    #[must_use]
+    pub fn word_count(&self) -> usize {
+        WORD_COUNTS[usize::from(u8::from(*self))]
+    }
+}
+
 field::impl_field_trait_field_traits!(MessageType, u8, 0..=3);
 
+/// The number of 32-bit words in a message, indexed by the value of the 4-bit
+/// Message Type field **([M2-104-UM 2.1.4])**.
+///
+/// The table covers all Message Type values, including those reserved for
+/// future use (which have sizes defined by the specification, so that
+/// receivers can skip unknown messages), and those not yet represented by a
+/// [`MessageType`] variant.
+pub const WORD_COUNTS: [usize; 16] = [1, 1, 1, 2, 2, 4, 1, 1, 2, 2, 2, 3, 3, 4, 4, 4];
+
 // Group
 
 /// Group field type.
@@ -127,12 +221,15 @@ field::impl_field_trait_field_traits!(MessageType, u8, 0..=3);
 /// The `Group` field type accesses the 4-bit Group field present in most UMP
 /// messages (exluding Utility and Stream messages) **([M2-104-UM 2.1.2])**.
 /// Messages which contain a Group field provide `group(...)` and
-/// `set_group(...)` functions to read and write the Group value.
+/// `set_group(...)` functions to read and write the Group value, and implement
+/// the [`Grouped`] trait.
 ///
 /// # Examples
 ///
 /// ```rust
 /// # use midi_2_protocol::*;
+/// # #[cfg(feature = "system")]
+/// # {
 /// # use midi_2_protocol::message::*;
 /// # use midi_2_protocol::message::system::real_time::*;
 /// #
@@ -146,10 +243,11 @@ field::impl_field_trait_field_traits!(MessageType, u8, 0..=3);
 ///
 /// // packet is now [0x13f80000]...
 /// assert_eq!(message.group()?, Group::G4);
+/// # }
 /// #
 /// # Ok::<(), Error>(())
 /// ```
-#[derive(Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[derive(Clone, Copy, Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
 #[num_enum(error_type(name = Error, constructor = Error::conversion))]
 #[repr(u8)]
 pub enum Group {
@@ -174,6 +272,235 @@ pub enum Group {
 
 field::impl_field_trait_field_traits!(Group, u8, 4..=7);
 
+// Grouped
+
+/// A message which contains a Group field.
+///
+/// The trait is implemented for (and only for) the messages and read-only
+/// views of each message family with a Group field -- Utility and Stream
+/// messages are not sent on a Group, and neither implement the trait nor
+/// provide `group(...)` functions -- so that generic code (such as routing by
+/// Group) can require messages with a Group field.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # #[cfg(all(feature = "system", feature = "voice"))]
+/// # {
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::system::real_time::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// fn is_on_first_group<M: Grouped>(message: &M) -> Result<bool, Error> {
+///     Ok(Grouped::group(message)? == Group::G1)
+/// }
+///
+/// let mut packet = TimingClock::packet();
+///
+/// assert!(is_on_first_group(&TimingClock::try_init(&mut packet)?)?);
+///
+/// let mut packet = NoteOn::packet();
+/// let note_on = NoteOn::try_init(&mut packet, Note::new(60), Velocity::MAX)?
+///     .set_group(Group::G2);
+///
+/// assert!(!is_on_first_group(&note_on.view())?);
+/// # }
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+///
+/// Utility messages have no Group field:
+///
+/// ```rust,compile_fail
+/// # use midi_2_protocol::message::utility::*;
+/// #
+/// let mut packet = Noop::packet();
+/// let noop = Noop::try_init(&mut packet).unwrap();
+///
+/// let _ = noop.group();
+/// ```
+pub trait Grouped {
+    /// Gets the Group of the message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the Group field cannot be read.
+    fn group(&self) -> Result<Group, Error>;
+}
+
+// Channel
+
+/// Channel field type.
+///
+/// The `Channel` field type accesses the 4-bit Channel field present in
+/// Channel Voice messages, and in Flex Data messages addressed to a channel
+/// **([M2-104-UM 7.4] and [M2-104-UM 7.5])**. Messages which contain a Channel
+/// field provide `channel(...)` and `set_channel(...)` functions to read and
+/// write the Channel value.
+#[derive(Clone, Copy, Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum Channel {
+    #[default]
+    C1 = 0x0,
+    C2 = 0x1,
+    C3 = 0x2,
+    C4 = 0x3,
+    C5 = 0x4,
+    C6 = 0x5,
+    C7 = 0x6,
+    C8 = 0x7,
+    C9 = 0x8,
+    C10 = 0x9,
+    C11 = 0xa,
+    C12 = 0xb,
+    C13 = 0xc,
+    C14 = 0xd,
+    C15 = 0xe,
+    C16 = 0xf,
+}
+
+field::impl_field_trait_field_traits!(Channel, u8, 12..=15);
+
+// -----------------------------------------------------------------------------
+
+// Field Changes
+
+/// The set of fields of a message which differ from a previous encoding of the
+/// message, as returned by `changed_fields(...)` functions.
+///
+/// Fields are identified by the names of their accessor functions (e.g.
+/// `"velocity"` for the `velocity(...)` function of a `NoteOn` message). When
+/// the previous encoding is of a different size, all fields are reported as
+/// changed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FieldChangeSet {
+    fields: &'static [&'static str],
+    changed: u32,
+}
+
+impl FieldChangeSet {
+    pub(crate) fn new(fields: &'static [&'static str], changed: &[bool]) -> Self {
+        Self {
+            fields,
+            changed: changed
+                .iter()
+                .enumerate()
+                .filter(|(_, changed)| **changed)
+                .fold(0, |set, (i, _)| set | 1 << i),
+        }
+    }
+
+    /// Returns `true` if the named field has changed.
+    #[must_use]
+    pub fn contains(&self, field: &str) -> bool {
+        self.fields().any(|changed| changed == field)
+    }
+
+    /// Returns `true` if no fields have changed.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.changed == 0
+    }
+
+    /// Returns the names of the changed fields, in message order.
+    pub fn fields(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.fields
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| self.changed & 1 << i != 0)
+            .map(|(_, field)| *field)
+    }
+}
+
+// Reserved Violations
+
+/// A range of reserved bits in a message which are not zero, as returned by
+/// `reserved_violations(...)` functions.
+///
+/// Violations are displayed as a path to the offending bits within the message
+/// (e.g. `NoteOn.reserved[24..=31]`), with bits numbered from the most
+/// significant bit of the first word.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReservedViolation {
+    pub message: &'static str,
+    pub bits: RangeInclusive<usize>,
+}
+
+#[cfg(feature = "fmt")]
+impl fmt::Display for ReservedViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.reserved[{:?}]", self.message, self.bits)
+    }
+}
+
+// Re-encode Mismatches
+
+/// A difference between a message and its re-encoding, as returned by
+/// [`verify_reencode`].
+///
+/// Re-encoded bits are displayed as a path to the differing bits within the
+/// message (e.g. `3: NoteOn[40..=47]`), prefixed by the offset (in words) of
+/// the message, with bits numbered from the most significant bit of the first
+/// word of the message.
+#[cfg(feature = "alloc")]
+#[derive(Debug)]
+pub enum Mismatch {
+    /// The words at the offset could not be decoded as a message (including a
+    /// trailing incomplete message).
+    Decode { offset: usize, error: Error },
+    /// The message was decoded, but writing its fields back to the packet
+    /// changed the given range of bits.
+    Reencode {
+        offset: usize,
+        message: &'static str,
+        bits: RangeInclusive<usize>,
+    },
+}
+
+#[cfg(feature = "alloc")]
+impl Mismatch {
+    /// Returns the offset (in words) of the message.
+    #[must_use]
+    pub const fn offset(&self) -> usize {
+        match self {
+            Self::Decode { offset, .. } | Self::Reencode { offset, .. } => *offset,
+        }
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "fmt"))]
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decode { offset, error } => write!(f, "{offset}: {error}"),
+            Self::Reencode {
+                offset,
+                message,
+                bits,
+            } => write!(f, "{offset}: {message}[{bits:?}]"),
+        }
+    }
+}
+
+// Address Scope
+
+/// The scope to which a message is addressed, as returned by
+/// [`Message::address_scope`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AddressScope {
+    /// The whole endpoint (for messages with no Group field, such as Utility
+    /// and Stream messages).
+    Endpoint,
+    /// A whole group.
+    Group,
+    /// A channel within a group.
+    GroupChannel,
+    /// A note on a channel within a group.
+    GroupNote,
+}
+
 // -----------------------------------------------------------------------------
 
 // Enumeration
@@ -193,6 +520,8 @@ field::impl_field_trait_field_traits!(Group, u8, 4..=7);
 ///
 /// ```rust
 /// # use midi_2_protocol::*;
+/// # #[cfg(feature = "voice")]
+/// # {
 /// # use midi_2_protocol::message::*;
 /// # use midi_2_protocol::message::voice::*;
 /// #
@@ -224,118 +553,1260 @@ field::impl_field_trait_field_traits!(Group, u8, 4..=7);
 /// } else {
 ///     panic!("Oh No!")
 /// }
+/// # }
 /// #
 /// # Ok::<(), Error>(())
 /// ```
-#[derive(Debug)]
+///
+/// When the data contains fewer words than required by the message type (e.g.
+/// when reading from a stream), a `Truncated` error is returned, giving the
+/// number of words needed.
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::message::*;
+/// #
+/// let mut words = [0x40903c00];
+///
+/// assert!(matches!(
+///     Message::try_from(&mut words[..]),
+///     Err(Error::Truncated { needed: 2, available: 1 })
+/// ));
+/// ```
+#[cfg_attr(feature = "fmt", derive(Debug))]
 pub enum Message<'a> {
+    #[cfg(feature = "data")]
+    Data(data::Data<'a>),
+    #[cfg(feature = "flex-data")]
+    FlexData(flex_data::FlexData<'a>),
+    #[cfg(feature = "stream")]
+    Stream(stream::Stream<'a>),
+    #[cfg(feature = "system")]
     System(system::System<'a>),
+    #[cfg(feature = "utility")]
+    Utility(utility::Utility<'a>),
+    #[cfg(feature = "voice")]
     Voice(voice::Voice<'a>),
 }
 
+/// A read-only view of a message over an immutable packet.
+///
+/// Message views mirror the [`Message`] enumeration (with a `View` type for
+/// each message type, such as [`voice::NoteOnView`]), but are created from
+/// `&[u32]` rather than `&mut [u32]`, and provide only the getters of each
+/// message type, so that received packets (e.g. in a buffer shared with
+/// another thread) can be inspected in place. A view of an existing message
+/// can be obtained using the `view()` function of the message.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # #[cfg(feature = "voice")]
+/// # {
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// let packet: &[u32] = &[0x40913c00, 0xffff0000];
+///
+/// if let MessageView::Voice(VoiceView::NoteOn(note_on)) = MessageView::try_from(packet)? {
+///     assert_eq!(note_on.channel()?, Channel::C2);
+///     assert_eq!(note_on.note()?, Note::new(60));
+/// } else {
+///     panic!("Oh No!")
+/// }
+/// # }
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+///
+/// Views (and messages) are equal when their fields are equal, ignoring any
+/// reserved bits, and can be compared with packets directly:
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # #[cfg(all(feature = "fmt", feature = "system", feature = "voice"))]
+/// # {
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// let received: &[u32] = &[0x10f800ff]; // timing clock, non-zero reserved byte
+/// let expected: &[u32] = &[0x10f80000];
+///
+/// assert_eq!(MessageView::try_from(received)?, MessageView::try_from(expected)?);
+/// assert_eq!(MessageView::try_from(received)?, [0x10f80000]);
+/// assert_ne!(MessageView::try_from(received)?, [0x10fa0000]);
+///
+/// let mut packet = NoteOn::packet();
+/// let note_on = NoteOn::try_init(&mut packet, Note::new(60), Velocity::MAX)?
+///     .set_channel(Channel::C2);
+///
+/// assert_eq!(note_on, [0x40913c00, 0xffff0000]);
+/// assert_eq!(note_on.view(), [0x40913c00, 0xffff0000]);
+/// # }
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum MessageView<'a> {
+    #[cfg(feature = "data")]
+    Data(data::DataView<'a>),
+    #[cfg(feature = "flex-data")]
+    FlexData(flex_data::FlexDataView<'a>),
+    #[cfg(feature = "stream")]
+    Stream(stream::StreamView<'a>),
+    #[cfg(feature = "system")]
+    System(system::SystemView<'a>),
+    #[cfg(feature = "utility")]
+    Utility(utility::UtilityView<'a>),
+    #[cfg(feature = "voice")]
+    Voice(voice::VoiceView<'a>),
+}
+
 message::impl_enumeration_trait_try_from!(Message);
 
+impl<'a> MessageView<'a> {
+    pub(crate) fn try_new(bits: &'a BitSlice<u32, Msb0>) -> Result<Self, Error> {
+        match bits.try_read_field::<MessageType>()? {
+            #[cfg(feature = "data")]
+            MessageType::Data => Ok(Self::Data(data::DataView::try_new(bits)?)),
+            #[cfg(feature = "flex-data")]
+            MessageType::FlexData => Ok(Self::FlexData(flex_data::FlexDataView::try_new(bits)?)),
+            #[cfg(feature = "stream")]
+            MessageType::Stream => Ok(Self::Stream(stream::StreamView::try_new(bits)?)),
+            #[cfg(feature = "system")]
+            MessageType::System => Ok(Self::System(system::SystemView::try_new(bits)?)),
+            #[cfg(feature = "utility")]
+            MessageType::Utility => Ok(Self::Utility(utility::UtilityView::try_new(bits)?)),
+            #[cfg(feature = "voice")]
+            MessageType::Voice => Ok(Self::Voice(voice::VoiceView::try_new(bits)?)),
+            message_type => Err(Error::conversion(message_type.into())),
+        }
+    }
+
+    /// Returns the number of 32-bit words in the message.
+    #[must_use]
+    pub fn words(&self) -> usize {
+        match self {
+            #[cfg(feature = "data")]
+            Self::Data(message) => message.words(),
+            #[cfg(feature = "flex-data")]
+            Self::FlexData(message) => message.words(),
+            #[cfg(feature = "stream")]
+            Self::Stream(message) => message.words(),
+            #[cfg(feature = "system")]
+            Self::System(message) => message.words(),
+            #[cfg(feature = "utility")]
+            Self::Utility(message) => message.words(),
+            #[cfg(feature = "voice")]
+            Self::Voice(message) => message.words(),
+        }
+    }
+
+    /// Copies the words of the message to the start of the given buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the buffer is shorter than the
+    /// message.
+    pub fn copy_into(&self, packet: &mut [u32]) -> Result<(), Error> {
+        match self {
+            #[cfg(feature = "data")]
+            Self::Data(message) => message.copy_into(packet),
+            #[cfg(feature = "flex-data")]
+            Self::FlexData(message) => message.copy_into(packet),
+            #[cfg(feature = "stream")]
+            Self::Stream(message) => message.copy_into(packet),
+            #[cfg(feature = "system")]
+            Self::System(message) => message.copy_into(packet),
+            #[cfg(feature = "utility")]
+            Self::Utility(message) => message.copy_into(packet),
+            #[cfg(feature = "voice")]
+            Self::Voice(message) => message.copy_into(packet),
+        }
+    }
+
+    /// Returns a copy of the message as an owned [`Packet`].
+    #[must_use]
+    pub fn clone_packet(&self) -> Packet {
+        match self {
+            #[cfg(feature = "data")]
+            Self::Data(message) => message.clone_packet(),
+            #[cfg(feature = "flex-data")]
+            Self::FlexData(message) => message.clone_packet(),
+            #[cfg(feature = "stream")]
+            Self::Stream(message) => message.clone_packet(),
+            #[cfg(feature = "system")]
+            Self::System(message) => message.clone_packet(),
+            #[cfg(feature = "utility")]
+            Self::Utility(message) => message.clone_packet(),
+            #[cfg(feature = "voice")]
+            Self::Voice(message) => message.clone_packet(),
+        }
+    }
+}
+
+impl PartialEq for MessageView<'_> {
+    #[allow(unreachable_patterns)]
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            #[cfg(feature = "data")]
+            (Self::Data(message), Self::Data(other)) => message == other,
+            #[cfg(feature = "flex-data")]
+            (Self::FlexData(message), Self::FlexData(other)) => message == other,
+            #[cfg(feature = "stream")]
+            (Self::Stream(message), Self::Stream(other)) => message == other,
+            #[cfg(feature = "system")]
+            (Self::System(message), Self::System(other)) => message == other,
+            #[cfg(feature = "utility")]
+            (Self::Utility(message), Self::Utility(other)) => message == other,
+            #[cfg(feature = "voice")]
+            (Self::Voice(message), Self::Voice(other)) => message == other,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for MessageView<'_> {}
+
+impl<const N: usize> PartialEq<[u32; N]> for MessageView<'_> {
+    fn eq(&self, other: &[u32; N]) -> bool {
+        MessageView::try_from(&other[..]).map_or(false, |other| *self == other)
+    }
+}
+
 impl<'a> Message<'a> {
     pub(crate) fn try_new(bits: &'a mut BitSlice<u32, Msb0>) -> Result<Self, Error> {
         match bits.try_read_field::<MessageType>()? {
+            #[cfg(feature = "data")]
+            MessageType::Data => Ok(Self::Data(data::Data::try_new(bits)?)),
+            #[cfg(feature = "flex-data")]
+            MessageType::FlexData => Ok(Self::FlexData(flex_data::FlexData::try_new(bits)?)),
+            #[cfg(feature = "stream")]
+            MessageType::Stream => Ok(Self::Stream(stream::Stream::try_new(bits)?)),
+            #[cfg(feature = "system")]
             MessageType::System => Ok(Self::System(system::System::try_new(bits)?)),
+            #[cfg(feature = "utility")]
+            MessageType::Utility => Ok(Self::Utility(utility::Utility::try_new(bits)?)),
+            #[cfg(feature = "voice")]
             MessageType::Voice => Ok(Self::Voice(voice::Voice::try_new(bits)?)),
-            _ => unreachable!(),
+            message_type => Err(Error::conversion(message_type.into())),
         }
     }
-}
 
-// -----------------------------------------------------------------------------
+    /// Returns the number of 32-bit words in the message (for example, to
+    /// advance past the message in a buffer).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # #[cfg(all(feature = "system", feature = "voice"))]
+    /// # {
+    /// # use midi_2_protocol::message::*;
+    /// #
+    /// let mut buffer = [0x40903c00, 0xffff0000, 0x10f80000];
+    /// let mut offset = 0;
+    ///
+    /// offset += Message::try_from(&mut buffer[offset..offset + 2])?.words();
+    /// offset += Message::try_from(&mut buffer[offset..offset + 1])?.words();
+    ///
+    /// assert_eq!(offset, 3);
+    /// # }
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[must_use]
+    pub fn words(&self) -> usize {
+        match self {
+            #[cfg(feature = "data")]
+            Self::Data(message) => message.words(),
+            #[cfg(feature = "flex-data")]
+            Self::FlexData(message) => message.words(),
+            #[cfg(feature = "stream")]
+            Self::Stream(message) => message.words(),
+            #[cfg(feature = "system")]
+            Self::System(message) => message.words(),
+            #[cfg(feature = "utility")]
+            Self::Utility(message) => message.words(),
+            #[cfg(feature = "voice")]
+            Self::Voice(message) => message.words(),
+        }
+    }
 
-// Macros
+    /// Copies the words of the message to the start of the given buffer (for
+    /// example, to duplicate a message from a receive buffer into a transmit
+    /// buffer).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the buffer is shorter than the
+    /// message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # #[cfg(feature = "voice")]
+    /// # {
+    /// # use midi_2_protocol::message::*;
+    /// #
+    /// let mut received = [0x40903c00, 0xffff0000, 0x10f80000];
+    /// let mut transmit = [0u32; 8];
+    ///
+    /// let message = Message::try_from(&mut received[..2])?;
+    ///
+    /// message.copy_into(&mut transmit[4..])?;
+    ///
+    /// assert_eq!(transmit[4..6], [0x40903c00, 0xffff0000]);
+    /// assert_eq!(&message.clone_packet()[..], [0x40903c00, 0xffff0000]);
+    /// assert!(message.copy_into(&mut transmit[7..]).is_err());
+    /// # }
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn copy_into(&self, packet: &mut [u32]) -> Result<(), Error> {
+        match self {
+            #[cfg(feature = "data")]
+            Self::Data(message) => message.copy_into(packet),
+            #[cfg(feature = "flex-data")]
+            Self::FlexData(message) => message.copy_into(packet),
+            #[cfg(feature = "stream")]
+            Self::Stream(message) => message.copy_into(packet),
+            #[cfg(feature = "system")]
+            Self::System(message) => message.copy_into(packet),
+            #[cfg(feature = "utility")]
+            Self::Utility(message) => message.copy_into(packet),
+            #[cfg(feature = "voice")]
+            Self::Voice(message) => message.copy_into(packet),
+        }
+    }
 
-// Enumeration
+    /// Returns a copy of the message as an owned [`Packet`].
+    #[must_use]
+    pub fn clone_packet(&self) -> Packet {
+        match self {
+            #[cfg(feature = "data")]
+            Self::Data(message) => message.clone_packet(),
+            #[cfg(feature = "flex-data")]
+            Self::FlexData(message) => message.clone_packet(),
+            #[cfg(feature = "stream")]
+            Self::Stream(message) => message.clone_packet(),
+            #[cfg(feature = "system")]
+            Self::System(message) => message.clone_packet(),
+            #[cfg(feature = "utility")]
+            Self::Utility(message) => message.clone_packet(),
+            #[cfg(feature = "voice")]
+            Self::Voice(message) => message.clone_packet(),
+        }
+    }
 
-macro_rules! impl_enumeration {
-    (
-        $(#[$meta:meta])*
-        $vis:vis $enum:ident, [
-            $($message:ident,)*
-            ]
-    ) => {
-        message::impl_enumeration_struct!($($meta)*, $vis, $enum, $($message,)*);
-        message::impl_enumeration_trait_try_from!($enum);
-    };
-}
+    /// Compares the message against a previous encoding of a message of the
+    /// same type, returning the set of fields which differ (for example, to
+    /// update only the affected parts of a UI).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # #[cfg(feature = "voice")]
+    /// # {
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::voice::*;
+    /// #
+    /// let mut previous = NoteOn::packet();
+    /// let _ = NoteOn::try_init(&mut previous, Note::new(60), Velocity::new(0x8000))?;
+    ///
+    /// let mut packet = previous;
+    /// let _ = NoteOn::try_from(&mut packet[..])?.set_velocity(Velocity::new(0xffff));
+    ///
+    /// let changes = Message::try_from(&mut packet[..])?.changed_fields(&previous);
+    ///
+    /// assert!(changes.contains("velocity"));
+    /// assert_eq!(changes.fields().collect::<Vec<_>>(), ["velocity"]);
+    /// # }
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[must_use]
+    pub fn changed_fields(&self, previous: &[u32]) -> FieldChangeSet {
+        match self {
+            #[cfg(feature = "data")]
+            Self::Data(message) => message.changed_fields(previous),
+            #[cfg(feature = "flex-data")]
+            Self::FlexData(message) => message.changed_fields(previous),
+            #[cfg(feature = "stream")]
+            Self::Stream(message) => message.changed_fields(previous),
+            #[cfg(feature = "system")]
+            Self::System(message) => message.changed_fields(previous),
+            #[cfg(feature = "utility")]
+            Self::Utility(message) => message.changed_fields(previous),
+            #[cfg(feature = "voice")]
+            Self::Voice(message) => message.changed_fields(previous),
+        }
+    }
 
-macro_rules! impl_enumeration_struct {
-    ($($meta:meta)*, $vis:vis, $enum:ident, $($message:ident,)*) => {
-        $(#[$meta])*
-        #[derive(Debug)]
-        $vis enum $enum<'a> {
-            $($message($message<'a>)),*
+    /// Returns the ranges of reserved bits in the message which are not zero
+    /// (bits not covered by any field of the message, or which are not used
+    /// given the values of other fields).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the fields of the message cannot
+    /// be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # #[cfg(all(feature = "fmt", feature = "system"))]
+    /// # {
+    /// # use midi_2_protocol::message::*;
+    /// #
+    /// // a Timing Clock message, with a non-zero reserved byte (0xff)
+    /// let mut packet = [0x10f800ff];
+    /// let violations = Message::try_from(&mut packet[..])?.reserved_violations()?;
+    ///
+    /// assert_eq!(violations[0].to_string(), "TimingClock.reserved[24..=31]");
+    /// # }
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn reserved_violations(&self) -> Result<Vec<ReservedViolation>, Error> {
+        match self {
+            #[cfg(feature = "data")]
+            Self::Data(message) => message.reserved_violations(),
+            #[cfg(feature = "flex-data")]
+            Self::FlexData(message) => message.reserved_violations(),
+            #[cfg(feature = "stream")]
+            Self::Stream(message) => message.reserved_violations(),
+            #[cfg(feature = "system")]
+            Self::System(message) => message.reserved_violations(),
+            #[cfg(feature = "utility")]
+            Self::Utility(message) => message.reserved_violations(),
+            #[cfg(feature = "voice")]
+            Self::Voice(message) => message.reserved_violations(),
         }
-    };
-}
+    }
 
-macro_rules! impl_enumeration_trait_try_from {
-    ($enum:ident) => {
-        impl<'a> TryFrom<&'a mut [u32]> for $enum<'a> {
-            type Error = Error;
+    #[cfg(feature = "alloc")]
+    pub(crate) fn reencode_mismatches(&self, offset: usize) -> Result<Vec<Mismatch>, Error> {
+        match self {
+            #[cfg(feature = "data")]
+            Self::Data(message) => message.reencode_mismatches(offset),
+            #[cfg(feature = "flex-data")]
+            Self::FlexData(message) => message.reencode_mismatches(offset),
+            #[cfg(feature = "stream")]
+            Self::Stream(message) => message.reencode_mismatches(offset),
+            #[cfg(feature = "system")]
+            Self::System(message) => message.reencode_mismatches(offset),
+            #[cfg(feature = "utility")]
+            Self::Utility(message) => message.reencode_mismatches(offset),
+            #[cfg(feature = "voice")]
+            Self::Voice(message) => message.reencode_mismatches(offset),
+        }
+    }
 
-            fn try_from(value: &'a mut [u32]) -> Result<Self, Self::Error> {
-                Self::try_new(value.view_bits_mut::<Msb0>())
-            }
+    /// Returns the scope to which the message is addressed, so that generic
+    /// routing and display code can handle messages without knowledge of each
+    /// message type.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the fields of the message cannot
+    /// be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # #[cfg(all(feature = "system", feature = "voice"))]
+    /// # {
+    /// # use midi_2_protocol::message::*;
+    /// #
+    /// let mut note_on = [0x40903c00, 0xffff0000];
+    /// let mut control_change = [0x40b00700, 0xffffffff];
+    /// let mut timing_clock = [0x10f80000];
+    ///
+    /// assert_eq!(
+    ///     Message::try_from(&mut note_on[..])?.address_scope()?,
+    ///     AddressScope::GroupNote
+    /// );
+    /// assert_eq!(
+    ///     Message::try_from(&mut control_change[..])?.address_scope()?,
+    ///     AddressScope::GroupChannel
+    /// );
+    /// assert_eq!(
+    ///     Message::try_from(&mut timing_clock[..])?.address_scope()?,
+    ///     AddressScope::Group
+    /// );
+    /// # }
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[allow(clippy::missing_const_for_fn)]
+    pub fn address_scope(&self) -> Result<AddressScope, Error> {
+        match self {
+            #[cfg(feature = "data")]
+            Self::Data(_) => Ok(AddressScope::Group),
+            #[cfg(feature = "flex-data")]
+            Self::FlexData(message) => match message.address()? {
+                flex_data::Address::Channel => Ok(AddressScope::GroupChannel),
+                flex_data::Address::Group => Ok(AddressScope::Group),
+            },
+            #[cfg(feature = "stream")]
+            Self::Stream(_) => Ok(AddressScope::Endpoint),
+            #[cfg(feature = "system")]
+            Self::System(_) => Ok(AddressScope::Group),
+            #[cfg(feature = "utility")]
+            Self::Utility(_) => Ok(AddressScope::Endpoint),
+            #[cfg(feature = "voice")]
+            Self::Voice(
+                voice::Voice::RegisteredPerNoteController(_)
+                | voice::Voice::AssignablePerNoteController(_)
+                | voice::Voice::PerNotePitchBend(_)
+                | voice::Voice::NoteOff(_)
+                | voice::Voice::NoteOn(_)
+                | voice::Voice::PerNoteManagement(_),
+            ) => Ok(AddressScope::GroupNote),
+            #[cfg(feature = "voice")]
+            Self::Voice(_) => Ok(AddressScope::GroupChannel),
         }
-    };
+    }
+
+    /// Attempts to parse a message in strict mode, failing if any reserved bits
+    /// of the message are not zero (see [`Message::reserved_violations`]), for
+    /// conformance testing of devices.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the message cannot be parsed, or
+    /// a `Reserved` error giving the first range of non-zero reserved bits.
+    #[cfg(feature = "alloc")]
+    pub fn try_from_strict(words: &'a mut [u32]) -> Result<Self, Error> {
+        let message = Self::try_from(words)?;
+
+        message
+            .reserved_violations()?
+            .first()
+            .map_or(Ok(message), |violation| {
+                Err(Error::reserved(
+                    violation.message,
+                    u8::try_from(*violation.bits.start()).unwrap_or(u8::MAX),
+                    u8::try_from(*violation.bits.end()).unwrap_or(u8::MAX),
+                ))
+            })
+    }
+
+    /// Attempts to parse the first message in a buffer which may contain
+    /// further messages, returning the message and the number of words it
+    /// occupies (so that a buffer can be parsed sequentially without slicing
+    /// each message in advance).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the message cannot be parsed, or
+    /// a `Truncated` error if the buffer ends before the end of the message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # #[cfg(all(feature = "system", feature = "voice"))]
+    /// # {
+    /// # use midi_2_protocol::message::*;
+    /// #
+    /// // a 64-bit voice message followed by a 32-bit system message
+    /// let mut buffer = [0x40903c00, 0xffff0000, 0x10f80000];
+    /// let mut position = 0;
+    /// let mut voice = 0;
+    ///
+    /// while position < buffer.len() {
+    ///     let (message, len) = Message::try_parse_prefix(&mut buffer[position..])?;
+    ///
+    ///     if matches!(message, Message::Voice(_)) {
+    ///         voice += 1;
+    ///     }
+    ///
+    ///     position += len;
+    /// }
+    ///
+    /// assert_eq!((voice, position), (1, 3));
+    /// # }
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn try_parse_prefix(words: &'a mut [u32]) -> Result<(Self, usize), Error> {
+        try_check_length(words)?;
+
+        let len = WORD_COUNTS[(words[0] >> 28) as usize];
+
+        Ok((Self::try_from(&mut words[..len])?, len))
+    }
 }
 
-// Message
+// -----------------------------------------------------------------------------
 
-macro_rules! impl_message {
-    (
-        $(#[$meta:meta])*
-        $vis:vis $message:ident { $size:literal, [
-            $({ $name:ident, $type:ty },)*
-        ] }
-    ) => {
-        message::impl_message_struct!($($meta)*, $vis, $message);
-        message::impl_message_constructor!($message, $size);
-        message::impl_message_fields!($message, $({ $name, $type },)*);
-        message::impl_message_packet!($message, $size);
-        message::impl_message_reset!($message);
-        message::impl_message_trait_debug!($message, $({ $name },)*);
-        message::impl_message_trait_get_bit_slice!($message);
-        message::impl_message_trait_try_from!($message);
+// Iterators
 
-    };
+// Message Iterator
+
+/// An iterator over the successive messages in a buffer of UMP words, yielding
+/// a read-only [`MessageView`] for each message.
+///
+/// The length of each message is determined by its Message Type, so messages
+/// of differing sizes may be mixed. A message which cannot be parsed yields an
+/// error, and iteration continues with the following message. A trailing
+/// incomplete message yields a `Truncated` error, ending the iteration.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # #[cfg(all(feature = "system", feature = "voice"))]
+/// # {
+/// # use midi_2_protocol::message::*;
+/// #
+/// // a 64-bit voice message, a 32-bit system message, and half of another
+/// // 64-bit voice message
+/// let buffer = [0x40903c00, 0xffff0000, 0x10f80000, 0x40803c00];
+/// let mut messages = MessageIter::new(&buffer);
+///
+/// assert!(matches!(messages.next(), Some(Ok(MessageView::Voice(_)))));
+/// assert!(matches!(messages.next(), Some(Ok(MessageView::System(_)))));
+/// assert!(matches!(
+///     messages.next(),
+///     Some(Err(Error::Truncated { needed: 2, available: 1 }))
+/// ));
+/// assert!(messages.next().is_none());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct MessageIter<'a> {
+    words: &'a [u32],
 }
 
-macro_rules! impl_message_struct {
-    ($($meta:meta)*, $vis:vis, $message:ident) => {
-        $(#[$meta])*
-        $vis struct $message<'a> {
-            bits: &'a mut BitSlice<u32, Msb0>,
-        }
-    };
+impl<'a> MessageIter<'a> {
+    /// Creates a new [`MessageIter`] over the given buffer.
+    #[must_use]
+    pub const fn new(words: &'a [u32]) -> Self {
+        Self { words }
+    }
 }
 
-macro_rules! impl_message_constructor {
-    ($message:ident, $size:literal) => {
-        impl<'a> $message<'a> {
-            pub(crate) fn try_new(bits: &'a mut BitSlice<u32, Msb0>) -> Result<Self, Error> {
-                match bits.len() {
-                    len if len == $size * 32 => Ok(Self { bits }),
-                    len => Err(Error::size($size * 32, len.try_into().unwrap())),
-                }
-            }
+impl<'a> Iterator for MessageIter<'a> {
+    type Item = Result<MessageView<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = WORD_COUNTS[(self.words.first()? >> 28) as usize];
+
+        if len > self.words.len() {
+            let available = mem::take(&mut self.words).len();
+
+            return Some(Err(Error::truncated(len, available)));
         }
-    };
+
+        let (message, rest) = self.words.split_at(len);
+
+        self.words = rest;
+
+        Some(MessageView::try_from(message))
+    }
 }
 
-macro_rules! impl_message_packet {
-    ($message:ident, $size:literal) => {
-        ::paste::paste! {
-            impl<'a> $message<'a> {
+impl FusedIterator for MessageIter<'_> {}
+
+// Message Iterator (Mutable)
+
+/// An iterator over the successive messages in a mutable buffer of UMP words,
+/// yielding a [`Message`] for each message (which may be modified in place).
+///
+/// Messages are delimited, and errors reported, as for [`MessageIter`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # #[cfg(all(feature = "system", feature = "voice"))]
+/// # {
+/// # use midi_2_protocol::message::*;
+/// #
+/// let mut buffer = [0x40903c00, 0xffff0000, 0x10f80000];
+///
+/// for message in MessageIterMut::new(&mut buffer) {
+///     if let Message::Voice(voice::Voice::NoteOn(note_on)) = message? {
+///         let _ = note_on.set_group(Group::G2);
+///     }
+/// }
+///
+/// assert_eq!(buffer, [0x41903c00, 0xffff0000, 0x10f80000]);
+/// # }
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Debug)]
+pub struct MessageIterMut<'a> {
+    words: &'a mut [u32],
+}
+
+impl<'a> MessageIterMut<'a> {
+    /// Creates a new [`MessageIterMut`] over the given buffer.
+    #[must_use]
+    pub fn new(words: &'a mut [u32]) -> Self {
+        Self { words }
+    }
+}
+
+impl<'a> Iterator for MessageIterMut<'a> {
+    type Item = Result<Message<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = WORD_COUNTS[(self.words.first()? >> 28) as usize];
+        let words = mem::take(&mut self.words);
+
+        if len > words.len() {
+            return Some(Err(Error::truncated(len, words.len())));
+        }
+
+        let (message, rest) = words.split_at_mut(len);
+
+        self.words = rest;
+
+        Some(Message::try_from(message))
+    }
+}
+
+impl FusedIterator for MessageIterMut<'_> {}
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+// Checks that a buffer contains at least as many words as required by the
+// Message Type of the first word (so that header fields may be read safely),
+// reporting the number of words needed if not.
+
+pub(crate) fn try_check_length(words: &[u32]) -> Result<(), Error> {
+    let needed = words
+        .first()
+        .map_or(1, |word| WORD_COUNTS[(word >> 28) as usize]);
+
+    match words.len() {
+        available if available < needed => Err(Error::truncated(needed, available)),
+        _ => Ok(()),
+    }
+}
+
+// Returns a field of a message for Debug output -- the value of the field, or
+// the error encountered reading the field, so that formatting never panics.
+
+#[cfg(feature = "fmt")]
+pub(crate) fn debug_field<T>(field: &Result<T, Error>) -> &dyn fmt::Debug
+where
+    T: fmt::Debug,
+{
+    match field {
+        Ok(value) => value,
+        Err(error) => error,
+    }
+}
+
+// Returns the ranges of bits which differ between a message and its canonical
+// encoding (the encoding produced by writing each of its fields to an empty
+// packet), which are therefore non-zero reserved bits.
+
+#[cfg(feature = "alloc")]
+pub(crate) fn reserved_violations(
+    message: &'static str,
+    actual: &BitSlice<u32, Msb0>,
+    canonical: &BitSlice<u32, Msb0>,
+) -> Vec<ReservedViolation> {
+    differences(actual, canonical)
+        .map(|bits| ReservedViolation { message, bits })
+        .collect()
+}
+
+// Returns the ranges of bits which differ between a message and its
+// re-encoding (the encoding produced by writing each of its fields to a copy of
+// the message).
+
+#[cfg(feature = "alloc")]
+pub(crate) fn reencode_mismatches(
+    message: &'static str,
+    offset: usize,
+    actual: &BitSlice<u32, Msb0>,
+    reencoded: &BitSlice<u32, Msb0>,
+) -> Vec<Mismatch> {
+    differences(actual, reencoded)
+        .map(|bits| Mismatch::Reencode {
+            offset,
+            message,
+            bits,
+        })
+        .collect()
+}
+
+#[cfg(feature = "alloc")]
+fn differences(
+    actual: &BitSlice<u32, Msb0>,
+    other: &BitSlice<u32, Msb0>,
+) -> impl Iterator<Item = RangeInclusive<usize>> {
+    let mut ranges: Vec<RangeInclusive<usize>> = Vec::new();
+
+    for bit in (0..actual.len()).filter(|bit| actual[*bit] != other[*bit]) {
+        match ranges.last_mut() {
+            Some(range) if *range.end() + 1 == bit => *range = *range.start()..=bit,
+            _ => ranges.push(bit..=bit),
+        }
+    }
+
+    ranges.into_iter()
+}
+
+/// Decodes each message in the buffer of words, and re-encodes it by writing
+/// each of its fields back to a copy of the message, confirming that the
+/// re-encoded message is bit-exact with the original.
+///
+/// This serves as a self-check of the field types of the crate (every field
+/// value which can be read should be written back unchanged), and as a
+/// conformance check for captures from other implementations (every message
+/// should decode, with all field values in range).
+///
+/// Reserved bits are not normalized -- as the fields are written to a copy of
+/// the original message, reserved bits are carried through unchanged, and so
+/// are never reported as mismatches (use
+/// [`Message::reserved_violations`] to check that they are zero). No other
+/// normalizations are applied.
+///
+/// # Errors
+///
+/// Returns every [`Mismatch`] found, in buffer order -- a `Decode` mismatch
+/// for each message which cannot be decoded (including a trailing incomplete
+/// message), and a `Reencode` mismatch for each range of bits changed by
+/// re-encoding.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # #[cfg(all(feature = "system", feature = "voice"))]
+/// # {
+/// # use midi_2_protocol::message::*;
+/// #
+/// // a note on (with a non-zero reserved byte), and a timing clock
+/// assert!(verify_reencode(&[0x40903cff, 0xffff0000, 0x10f80000]).is_ok());
+///
+/// // a note on, a message with an invalid opcode, and half of a note off
+/// let mismatches = verify_reencode(&[0x40903c00, 0xffff0000, 0x40703c00, 0, 0x40803c00])
+///     .unwrap_err();
+///
+/// assert_eq!(mismatches.len(), 2);
+/// assert!(matches!(
+///     mismatches[0],
+///     Mismatch::Decode { offset: 2, error: Error::Conversion(7) }
+/// ));
+/// assert!(matches!(
+///     mismatches[1],
+///     Mismatch::Decode { offset: 4, error: Error::Truncated { needed: 2, available: 1 } }
+/// ));
+/// # }
+/// ```
+#[cfg(feature = "alloc")]
+pub fn verify_reencode(words: &[u32]) -> Result<(), Vec<Mismatch>> {
+    let mut words = words.to_vec();
+    let mut mismatches = Vec::new();
+    let mut offset = 0;
+
+    while let Some(word) = words.get(offset) {
+        let len = WORD_COUNTS[(word >> 28) as usize].min(words.len() - offset);
+
+        match Message::try_from(&mut words[offset..offset + len])
+            .and_then(|message| message.reencode_mismatches(offset))
+        {
+            Ok(found) => mismatches.extend(found),
+            Err(error) => mismatches.push(Mismatch::Decode { offset, error }),
+        }
+
+        offset += len;
+    }
+
+    if mismatches.is_empty() {
+        Ok(())
+    } else {
+        Err(mismatches)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Macros
+
+// Enumeration
+
+macro_rules! impl_enumeration {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $enum:ident, [
+            $($message:ident,)*
+            ]
+    ) => {
+        message::impl_enumeration_struct!($($meta)*, $vis, $enum, $($message,)*);
+        message::impl_enumeration_changed_fields!($enum, $($message,)*);
+        message::impl_enumeration_reserved_violations!($enum, $($message,)*);
+        message::impl_enumeration_reencode_mismatches!($enum, $($message,)*);
+        message::impl_enumeration_trait_partial_eq!($enum, $($message,)*);
+        message::impl_enumeration_trait_try_from!($enum);
+        message::impl_enumeration_words!($enum, $($message,)*);
+        message::impl_enumeration_copy!($enum, $($message,)*);
+    };
+}
+
+macro_rules! impl_enumeration_changed_fields {
+    ($enum:ident, $($message:ident,)*) => {
+        impl<'a> $enum<'a> {
+            #[doc = "Compares the message against a previous encoding of a message of the same"]
+            #[doc = "type, returning the set of fields which differ."]
+            #[must_use]
+            pub fn changed_fields(&self, previous: &[u32]) -> message::FieldChangeSet {
+                match self {
+                    $(Self::$message(message) => message.changed_fields(previous),)*
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_enumeration_reserved_violations {
+    ($enum:ident, $($message:ident,)*) => {
+        impl<'a> $enum<'a> {
+            #[doc = "Returns the ranges of reserved bits in the message which are not zero."]
+            #[doc = "# Errors"]
+            #[doc = "Returns an [`Error`](crate::Error) if the fields of the message cannot be read."]
+            #[cfg(feature = "alloc")]
+            pub fn reserved_violations(
+                &self,
+            ) -> Result<alloc::vec::Vec<message::ReservedViolation>, Error> {
+                match self {
+                    $(Self::$message(message) => message.reserved_violations(),)*
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_enumeration_reencode_mismatches {
+    ($enum:ident, $($message:ident,)*) => {
+        impl<'a> $enum<'a> {
+            #[cfg(feature = "alloc")]
+            pub(crate) fn reencode_mismatches(
+                &self,
+                offset: usize,
+            ) -> Result<alloc::vec::Vec<message::Mismatch>, Error> {
+                match self {
+                    $(Self::$message(message) => message.reencode_mismatches(offset),)*
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_enumeration_struct {
+    ($($meta:meta)*, $vis:vis, $enum:ident, $($message:ident,)*) => {
+        $(#[$meta])*
+        #[cfg_attr(feature = "fmt", derive(Debug))]
+        $vis enum $enum<'a> {
+            $($message($message<'a>)),*
+        }
+
+        ::paste::paste! {
+            #[doc = "Read-only views of [`" $enum "`] messages."]
+            #[cfg_attr(feature = "fmt", derive(Debug))]
+            $vis enum [<$enum View>]<'a> {
+                $($message([<$message View>]<'a>)),*
+            }
+        }
+    };
+}
+
+macro_rules! impl_enumeration_words {
+    ($enum:ident, $($message:ident,)*) => {
+        impl<'a> $enum<'a> {
+            #[doc = "Returns the number of 32-bit words in the message."]
+            #[must_use]
+            pub fn words(&self) -> usize {
+                match self {
+                    $(Self::$message(message) => message.words(),)*
+                }
+            }
+        }
+
+        ::paste::paste! {
+            impl<'a> [<$enum View>]<'a> {
+                #[doc = "Returns the number of 32-bit words in the message."]
+                #[must_use]
+                pub fn words(&self) -> usize {
+                    match self {
+                        $(Self::$message(message) => message.words(),)*
+                    }
+                }
+            }
+        }
+    };
+}
+
+// Enumerations are copied by copying the message of the enumeration.
+
+macro_rules! impl_enumeration_copy {
+    ($enum:ident, $($message:ident,)*) => {
+        impl<'a> $enum<'a> {
+            #[doc = "Copies the words of the message to the start of the given buffer."]
+            #[doc = "# Errors"]
+            #[doc = "Returns an [`Error`](crate::Error) if the buffer is shorter than the message."]
+            pub fn copy_into(&self, packet: &mut [u32]) -> Result<(), Error> {
+                match self {
+                    $(Self::$message(message) => message.copy_into(packet),)*
+                }
+            }
+
+            #[doc = "Returns a copy of the message as an owned [`Packet`](crate::packet::Packet)."]
+            #[must_use]
+            pub fn clone_packet(&self) -> crate::packet::Packet {
+                match self {
+                    $(Self::$message(message) => message.clone_packet().into(),)*
+                }
+            }
+        }
+
+        ::paste::paste! {
+            impl<'a> [<$enum View>]<'a> {
+                #[doc = "Copies the words of the message to the start of the given buffer."]
+                #[doc = "# Errors"]
+                #[doc = "Returns an [`Error`](crate::Error) if the buffer is shorter than the message."]
+                pub fn copy_into(&self, packet: &mut [u32]) -> Result<(), Error> {
+                    match self {
+                        $(Self::$message(message) => message.copy_into(packet),)*
+                    }
+                }
+
+                #[doc = "Returns a copy of the message as an owned [`Packet`](crate::packet::Packet)."]
+                #[must_use]
+                pub fn clone_packet(&self) -> crate::packet::Packet {
+                    match self {
+                        $(Self::$message(message) => message.clone_packet().into(),)*
+                    }
+                }
+            }
+        }
+    };
+}
+
+// Enumeration views are equal when they are views of the same message type,
+// and the views are equal.
+
+macro_rules! impl_enumeration_trait_partial_eq {
+    ($enum:ident, $($message:ident,)*) => {
+        ::paste::paste! {
+            impl<'a> PartialEq for [<$enum View>]<'a> {
+                #[allow(unreachable_patterns)]
+                fn eq(&self, other: &Self) -> bool {
+                    match (self, other) {
+                        $((Self::$message(message), Self::$message(other)) => message == other,)*
+                        _ => false,
+                    }
+                }
+            }
+
+            impl<'a> Eq for [<$enum View>]<'a> {}
+
+            impl<'a, const N: usize> PartialEq<[u32; N]> for [<$enum View>]<'a> {
+                fn eq(&self, other: &[u32; N]) -> bool {
+                    [<$enum View>]::try_from(&other[..]).map_or(false, |other| *self == other)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_enumeration_trait_try_from {
+    ($enum:ident) => {
+        impl<'a> TryFrom<&'a mut [u32]> for $enum<'a> {
+            type Error = Error;
+
+            fn try_from(value: &'a mut [u32]) -> Result<Self, Self::Error> {
+                message::try_check_length(value)?;
+
+                Self::try_new(value.view_bits_mut::<Msb0>())
+            }
+        }
+
+        ::paste::paste! {
+            impl<'a> TryFrom<&'a [u32]> for [<$enum View>]<'a> {
+                type Error = Error;
+
+                fn try_from(value: &'a [u32]) -> Result<Self, Self::Error> {
+                    message::try_check_length(value)?;
+
+                    Self::try_new(value.view_bits::<Msb0>())
+                }
+            }
+        }
+    };
+}
+
+// Message
+
+// The header fields of a message are given by the rule used: messages of the
+// families with a Group field are "grouped", and have the Message Type and
+// Group fields (and implement Grouped), while Utility and Stream messages are
+// "ungrouped", and have only the Message Type field -- so that group accessors
+// cannot be generated for messages without a Group field. Not every rule is
+// used by every combination of message family features.
+
+#[allow(unused_macro_rules)]
+macro_rules! impl_message {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $message:ident { $size:literal, grouped, [
+            $({ $name:ident, $type:ty },)*
+        ] }
+    ) => {
+        message::impl_message!(
+            @fields
+            $(#[$meta])*
+            $vis $message { $size, [
+                { message_type, MessageType },
+                { group, Group },
+              $({ $name, $type },)*
+            ] }
+        );
+        message::impl_message_trait_grouped!($message);
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis $message:ident { $size:literal, ungrouped, [
+            $({ $name:ident, $type:ty },)*
+        ] }
+    ) => {
+        message::impl_message!(
+            @fields
+            $(#[$meta])*
+            $vis $message { $size, [
+                { message_type, MessageType },
+              $({ $name, $type },)*
+            ] }
+        );
+    };
+    (
+        @fields
+        $(#[$meta:meta])*
+        $vis:vis $message:ident { $size:literal, [
+            $({ $name:ident, $type:ty },)*
+        ] }
+    ) => {
+        message::impl_message_struct!($($meta)*, $vis, $message);
+        message::impl_message_constructor!($message, $size);
+        message::impl_message_copy!($message, $size);
+        message::impl_message_fields!($message, $({ $name, $type },)*);
+        message::impl_message_changed_fields!($message, $({ $name, $type },)*);
+        message::impl_message_reserved_violations!($message, $size, $({ $name },)*);
+        message::impl_message_reencode_mismatches!($message, $size, $({ $name },)*);
+        message::impl_message_packet!($message, $size);
+        message::impl_message_builder!($vis, $message, $size, $({ $name, $type },)*);
+        message::impl_message_reset!($message);
+        message::impl_message_trait_debug!($message, $({ $name },)*);
+        message::impl_message_trait_get_bit_slice!($message);
+        message::impl_message_trait_partial_eq!($message, $size, $({ $type },)*);
+        message::impl_message_trait_try_from!($message);
+        message::impl_message_view!($vis, $message, $size, $({ $name, $type },)*);
+    };
+}
+
+macro_rules! impl_message_struct {
+    ($($meta:meta)*, $vis:vis, $message:ident) => {
+        $(#[$meta])*
+        $vis struct $message<'a> {
+            bits: &'a mut BitSlice<u32, Msb0>,
+        }
+    };
+}
+
+macro_rules! impl_message_constructor {
+    ($message:ident, $size:literal) => {
+        impl<'a> $message<'a> {
+            pub(crate) fn try_new(bits: &'a mut BitSlice<u32, Msb0>) -> Result<Self, Error> {
+                match bits.len() {
+                    len if len == $size * 32 => Ok(Self { bits }),
+                    len if len < $size * 32 => Err(Error::truncated($size, len / 32)),
+                    len => Err(Error::size($size * 32, len.try_into().unwrap_or(u8::MAX))),
+                }
+            }
+
+            #[doc = "Returns the number of 32-bit words in the message."]
+            #[must_use]
+            pub fn words(&self) -> usize {
+                self.bits.len() / 32
+            }
+        }
+    };
+}
+
+// Messages are copied bit for bit (including any reserved bits), so that a copy
+// of a message is identical to the original.
+
+macro_rules! impl_message_copy {
+    ($message:ident, $size:literal) => {
+        ::paste::paste! {
+            impl<'a> $message<'a> {
+                #[doc = "Copies the words of the message to the start of the given buffer."]
+                #[doc = "# Errors"]
+                #[doc = "Returns an [`Error`](crate::Error) if the buffer is shorter than the message."]
+                pub fn copy_into(&self, packet: &mut [u32]) -> Result<(), Error> {
+                    self.view().copy_into(packet)
+                }
+
+                #[doc = "Returns a copy of the words of the message."]
+                #[must_use]
+                pub fn clone_packet(&self) -> [u32; $size] {
+                    self.view().clone_packet()
+                }
+            }
+
+            impl<'a> [<$message View>]<'a> {
+                #[doc = "Copies the words of the message to the start of the given buffer."]
+                #[doc = "# Errors"]
+                #[doc = "Returns an [`Error`](crate::Error) if the buffer is shorter than the message."]
+                pub fn copy_into(&self, packet: &mut [u32]) -> Result<(), Error> {
+                    match packet.get_mut(..$size) {
+                        Some(packet) => {
+                            packet.view_bits_mut::<Msb0>().copy_from_bitslice(self.bits);
+                            Ok(())
+                        }
+                        None => Err(Error::truncated($size, packet.len())),
+                    }
+                }
+
+                #[doc = "Returns a copy of the words of the message."]
+                #[must_use]
+                pub fn clone_packet(&self) -> [u32; $size] {
+                    let mut packet = [0u32; $size];
+
+                    packet.view_bits_mut::<Msb0>().copy_from_bitslice(self.bits);
+                    packet
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_message_packet {
+    ($message:ident, $size:literal) => {
+        ::paste::paste! {
+            impl<'a> $message<'a> {
                 #[doc = "Returns an appropriately sized `u32` array for a `" $message "` message."]
                 #[doc = "# Examples"]
                 #[doc = "```rust"]
-                #[doc = concat!("# use ", std::module_path!(), "::")]
+                #[doc = concat!("# use ", core::module_path!(), "::")]
                 #[doc = "# " $message ";"]
                 #[doc = "let mut packet = " $message "::packet(); // Returns a [u32; " $size "]"]
                 #[doc = ""]
@@ -353,6 +1824,83 @@ macro_rules! impl_message_packet {
     };
 }
 
+// The builder of each message is created (with the packet initialized as for
+// try_init) by the message family, using impl_message_builder_init.
+
+macro_rules! impl_message_builder {
+    ($vis:vis, $message:ident, $size:literal, $({ $name:ident, $type:ty },)*) => {
+        ::paste::paste! {
+            #[doc = "A builder of [`" $message "`] messages, producing a packet directly (without"]
+            #[doc = "the packet being managed by the caller)."]
+            #[derive(Clone, Copy)]
+            #[cfg_attr(feature = "fmt", derive(Debug))]
+            $vis struct [<$message Builder>] {
+                packet: [u32; $size],
+                error: Option<Error>,
+            }
+
+            impl [<$message Builder>] {
+                $(
+                    #[doc = "Sets the [`" $type "`](" $type ") field of the message, from a value of the"]
+                    #[doc = "field type or a raw integral value (an invalid value is returned as an"]
+                    #[doc = "error by `build`)."]
+                    #[must_use]
+                    pub fn $name<I>(mut self, $name: I) -> Self
+                    where
+                        $type: crate::field::TryFromIntegral<I>,
+                    {
+                        match <$type as crate::field::TryFromIntegral<I>>::try_from_integral($name) {
+                            Ok($name) => {
+                                let _ = $message { bits: self.packet.view_bits_mut::<Msb0>() }
+                                    .[<set_ $name>]($name);
+                            }
+                            Err(error) => {
+                                self.error.get_or_insert(error);
+                            }
+                        }
+
+                        self
+                    }
+                )*
+
+                #[doc = "Returns the packet of the built message."]
+                #[doc = "# Errors"]
+                #[doc = "Returns the [`Error`](crate::Error) for the first invalid value given to the"]
+                #[doc = "builder, if any."]
+                pub const fn build(self) -> Result<[u32; $size], Error> {
+                    match self.error {
+                        Some(error) => Err(error),
+                        None => Ok(self.packet),
+                    }
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_message_builder_init {
+    ($message:ident, $init:ident) => {
+        ::paste::paste! {
+            impl<'a> $message<'a> {
+                #[doc = "Returns a builder of [`" $message "`] messages, with the packet initialized"]
+                #[doc = "as for `try_init` (with any fields given to `try_init` set to zero)."]
+                #[must_use]
+                pub fn builder() -> [<$message Builder>] {
+                    let mut packet = Self::packet();
+
+                    // the packet is of the correct size, so cannot fail to initialize
+                    let _ = $message::$init(&mut packet);
+
+                    [<$message Builder>] {
+                        packet,
+                        error: None,
+                    }
+                }
+            }
+        }
+    };
+}
+
 macro_rules! impl_message_reset {
     ($message:ident) => {
         impl<'a> $message<'a> {
@@ -383,24 +1931,138 @@ macro_rules! impl_message_fields {
                     pub fn [<set_ $name>](self, $name: $type) -> Self {
                         self.write_field::<$type>($name)
                     }
+
+                    #[doc = "Sets the [`" $type "`](" $type ") field of the message from the given integral"]
+                    #[doc = "value, if the value is valid for the field type."]
+                    #[doc = "# Errors"]
+                    #[doc = "Returns an [`Error`](crate::Error) (leaving the packet unchanged) when the"]
+                    #[doc = "value is not valid for the field type."]
+                    pub fn [<try_set_ $name>]<I>(self, $name: I) -> Result<Self, Error>
+                    where
+                        $type: crate::field::TryFromIntegral<I>,
+                    {
+                        <$type as crate::field::TryFromIntegral<I>>::try_from_integral($name)
+                            .map(|$name| self.write_field::<$type>($name))
+                    }
                 }
             )*
         }
     };
 }
 
+macro_rules! impl_message_changed_fields {
+    ($message:ident, $({ $name:ident, $type:ty },)*) => {
+        impl<'a> $message<'a> {
+            const FIELDS: &'static [&'static str] = &[$(stringify!($name),)*];
+
+            #[doc = "Compares the message against a previous encoding of a message of the same"]
+            #[doc = "type, returning the set of fields which differ."]
+            #[must_use]
+            pub fn changed_fields(&self, previous: &[u32]) -> message::FieldChangeSet {
+                let previous = previous.view_bits::<Msb0>();
+                let comparable = previous.len() == self.bits.len();
+
+                message::FieldChangeSet::new(Self::FIELDS, &[$(
+                    !comparable
+                        || self.try_read_field::<$type>().ok()
+                            != previous.try_read_field::<$type>().ok(),
+                )*])
+            }
+        }
+    };
+}
+
+macro_rules! impl_message_reserved_violations {
+    ($message:ident, $size:literal, $({ $name:ident },)*) => {
+        ::paste::paste! {
+            impl<'a> $message<'a> {
+                #[doc = "Returns the ranges of reserved bits in the message which are not zero."]
+                #[doc = "# Errors"]
+                #[doc = "Returns an [`Error`](crate::Error) if the fields of the message cannot be read."]
+                #[cfg(feature = "alloc")]
+                pub fn reserved_violations(
+                    &self,
+                ) -> Result<alloc::vec::Vec<message::ReservedViolation>, Error> {
+                    let mut packet = [0u32; $size];
+                    let canonical = $message { bits: packet.view_bits_mut::<Msb0>() }
+                        $(.[<set_ $name>](self.$name()?))*;
+
+                    Ok(message::reserved_violations(
+                        stringify!($message),
+                        &self.bits,
+                        &canonical.bits,
+                    ))
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_message_reencode_mismatches {
+    ($message:ident, $size:literal, $({ $name:ident },)*) => {
+        ::paste::paste! {
+            impl<'a> $message<'a> {
+                #[cfg(feature = "alloc")]
+                pub(crate) fn reencode_mismatches(
+                    &self,
+                    offset: usize,
+                ) -> Result<alloc::vec::Vec<message::Mismatch>, Error> {
+                    let mut packet = [0u32; $size];
+                    let bits = packet.view_bits_mut::<Msb0>();
+
+                    bits.copy_from_bitslice(&self.bits);
+
+                    let reencoded = $message { bits } $(.[<set_ $name>](self.$name()?))*;
+
+                    Ok(message::reencode_mismatches(
+                        stringify!($message),
+                        offset,
+                        &self.bits,
+                        &reencoded.bits,
+                    ))
+                }
+            }
+        }
+    };
+}
+
 macro_rules! impl_message_trait_debug {
     ($message:ident, $({ $name:ident },)*) => {
+        #[cfg(feature = "fmt")]
         impl<'a> ::core::fmt::Debug for $message<'a> {
             fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
                 f.debug_struct(stringify!($message))
-                  $(.field(stringify!($name), &self.$name().unwrap()))*
+                  $(.field(stringify!($name), message::debug_field(&self.$name())))*
                     .finish()
             }
         }
     };
 }
 
+#[cfg(any(
+    feature = "data",
+    feature = "flex-data",
+    feature = "system",
+    feature = "voice"
+))]
+macro_rules! impl_message_trait_grouped {
+    ($message:ident) => {
+        impl<'a> message::Grouped for $message<'a> {
+            fn group(&self) -> Result<message::Group, Error> {
+                self.try_read_field::<message::Group>()
+            }
+        }
+
+        ::paste::paste! {
+            impl<'a> message::Grouped for [<$message View>]<'a> {
+                fn group(&self) -> Result<message::Group, Error> {
+                    self.bits.try_read_field::<message::Group>()
+                }
+            }
+        }
+    };
+}
+
 macro_rules! impl_message_trait_get_bit_slice {
     ($message:ident) => {
         impl<'a> GetBitSlice for $message<'a> {
@@ -415,6 +2077,49 @@ macro_rules! impl_message_trait_get_bit_slice {
     };
 }
 
+// Messages (and views) are equal when each of their fields is equal (or cannot
+// be read from either), so that reserved bits are ignored.
+
+macro_rules! impl_message_trait_partial_eq {
+    ($message:ident, $size:literal, $({ $type:ty },)*) => {
+        ::paste::paste! {
+            impl<'a> [<$message View>]<'a> {
+                fn fields_eq(bits: &BitSlice<u32, Msb0>, other: &BitSlice<u32, Msb0>) -> bool {
+                    $(bits.try_read_field::<$type>().ok() == other.try_read_field::<$type>().ok())&&*
+                }
+            }
+
+            impl<'a> PartialEq for $message<'a> {
+                fn eq(&self, other: &Self) -> bool {
+                    [<$message View>]::fields_eq(self.bits, other.bits)
+                }
+            }
+
+            impl<'a> Eq for $message<'a> {}
+
+            impl<'a> PartialEq<[u32; $size]> for $message<'a> {
+                fn eq(&self, other: &[u32; $size]) -> bool {
+                    [<$message View>]::fields_eq(self.bits, other.view_bits::<Msb0>())
+                }
+            }
+
+            impl<'a> PartialEq for [<$message View>]<'a> {
+                fn eq(&self, other: &Self) -> bool {
+                    Self::fields_eq(self.bits, other.bits)
+                }
+            }
+
+            impl<'a> Eq for [<$message View>]<'a> {}
+
+            impl<'a> PartialEq<[u32; $size]> for [<$message View>]<'a> {
+                fn eq(&self, other: &[u32; $size]) -> bool {
+                    Self::fields_eq(self.bits, other.view_bits::<Msb0>())
+                }
+            }
+        }
+    };
+}
+
 macro_rules! impl_message_trait_try_from {
     ($message:ident) => {
         impl<'a> TryFrom<&'a mut [u32]> for $message<'a> {
@@ -427,19 +2132,105 @@ macro_rules! impl_message_trait_try_from {
     };
 }
 
+macro_rules! impl_message_view {
+    ($vis:vis, $message:ident, $size:literal, $({ $name:ident, $type:ty },)*) => {
+        ::paste::paste! {
+            #[doc = "A read-only view of a [`" $message "`] message over an immutable packet,"]
+            #[doc = "providing the getters of the message type (but not the setters)."]
+            $vis struct [<$message View>]<'a> {
+                bits: &'a BitSlice<u32, Msb0>,
+            }
+
+            impl<'a> [<$message View>]<'a> {
+                pub(crate) fn try_new(bits: &'a BitSlice<u32, Msb0>) -> Result<Self, Error> {
+                    match bits.len() {
+                        len if len == $size * 32 => Ok(Self { bits }),
+                        len if len < $size * 32 => Err(Error::truncated($size, len / 32)),
+                        len => Err(Error::size($size * 32, len.try_into().unwrap_or(u8::MAX))),
+                    }
+                }
+
+                #[doc = "Returns the number of 32-bit words in the message."]
+                #[must_use]
+                pub fn words(&self) -> usize {
+                    self.bits.len() / 32
+                }
+
+                $(
+                    #[doc = "Gets the [`" $type "`](" $type ") field from the message if the available,"]
+                    #[doc = "otherwise returning an [`Error`](crate::Error)."]
+                    #[doc = "# Errors"]
+                    #[doc = "Returns an [`Error`](crate::Error) when the data present in the message cannot be"]
+                    #[doc = "converted to the field type (not all field types are total across the range of"]
+                    #[doc = "possible values)."]
+                    pub fn $name(&self) -> Result<$type, Error> {
+                        self.bits.try_read_field::<$type>()
+                    }
+                )*
+            }
+
+            impl<'a> $message<'a> {
+                #[doc = "Returns a read-only view of the message."]
+                #[must_use]
+                pub fn view(&self) -> [<$message View>]<'_> {
+                    [<$message View>] { bits: self.bits }
+                }
+            }
+
+            #[cfg(feature = "fmt")]
+            impl<'a> ::core::fmt::Debug for [<$message View>]<'a> {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.debug_struct(stringify!([<$message View>]))
+                      $(.field(stringify!($name), message::debug_field(&self.$name())))*
+                        .finish()
+                }
+            }
+
+            impl<'a> TryFrom<&'a [u32]> for [<$message View>]<'a> {
+                type Error = Error;
+
+                fn try_from(value: &'a [u32]) -> Result<Self, Self::Error> {
+                    Self::try_new(value.view_bits::<Msb0>())
+                }
+            }
+        }
+    };
+}
+
 // -----------------------------------------------------------------------------
 
 // Macro Exports
 
 pub(crate) use impl_enumeration;
+pub(crate) use impl_enumeration_changed_fields;
+pub(crate) use impl_enumeration_copy;
+pub(crate) use impl_enumeration_reencode_mismatches;
+pub(crate) use impl_enumeration_reserved_violations;
 pub(crate) use impl_enumeration_struct;
+pub(crate) use impl_enumeration_trait_partial_eq;
 pub(crate) use impl_enumeration_trait_try_from;
+pub(crate) use impl_enumeration_words;
 pub(crate) use impl_message;
+pub(crate) use impl_message_builder;
+pub(crate) use impl_message_builder_init;
+pub(crate) use impl_message_changed_fields;
 pub(crate) use impl_message_constructor;
+pub(crate) use impl_message_copy;
 pub(crate) use impl_message_fields;
 pub(crate) use impl_message_packet;
+pub(crate) use impl_message_reencode_mismatches;
+pub(crate) use impl_message_reserved_violations;
 pub(crate) use impl_message_reset;
 pub(crate) use impl_message_struct;
 pub(crate) use impl_message_trait_debug;
 pub(crate) use impl_message_trait_get_bit_slice;
+#[cfg(any(
+    feature = "data",
+    feature = "flex-data",
+    feature = "system",
+    feature = "voice"
+))]
+pub(crate) use impl_message_trait_grouped;
+pub(crate) use impl_message_trait_partial_eq;
 pub(crate) use impl_message_trait_try_from;
+pub(crate) use impl_message_view;
@@ -0,0 +1,453 @@
+// =============================================================================
+// Data
+// =============================================================================
+
+//! Data message and value types.
+//!
+//! The [`data`](crate::message::data) module contains the 128-bit Data
+//! messages -- System Exclusive 8 messages **([M2-104-UM 7.8])**, which carry
+//! 8-bit System Exclusive data across one or more UMPs, and Mixed Data Set
+//! messages **([M2-104-UM 7.9])**, which carry large payloads of arbitrary
+//! data as a header followed by a sequence of payload chunks.
+
+use arbitrary_int::UInt;
+use bitvec::{
+    field::BitField,
+    order::Msb0,
+    slice::BitSlice,
+    view::BitView,
+};
+use num_enum::{
+    IntoPrimitive,
+    TryFromPrimitive,
+};
+
+use crate::{
+    field::{
+        self,
+        TryReadFromPacket,
+        WriteToPacket,
+    },
+    message::{
+        self,
+        data,
+        Group,
+        MessageType,
+    },
+    packet::{
+        GetBitSlice,
+        TryReadField,
+        WriteField,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Fields
+
+// Status
+
+/// Status field type.
+///
+/// The `Status` field type accesses the 4-bit Status field of a Data message
+/// **([M2-104-UM 7.8] and [M2-104-UM 7.9])**, which gives both the kind of
+/// message, and (for System Exclusive 8 messages) whether the message is
+/// complete in a single UMP, or is part of a sequence of UMPs.
+#[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum Status {
+    Sysex8Complete = 0x0,
+    Sysex8Start = 0x1,
+    Sysex8Continue = 0x2,
+    Sysex8End = 0x3,
+    MixedDataSetHeader = 0x8,
+    MixedDataSetPayload = 0x9,
+}
+
+field::impl_field_trait_field_traits!(Status, u8, 8..=11);
+
+// System Exclusive 8
+
+field::impl_field!(
+    /// The number of valid bytes in a System Exclusive 8 message, including
+    /// the Stream ID (from 1 to 14).
+    pub NumberOfBytes { u8, 12..=15, 4 }
+);
+
+field::impl_field!(
+    /// The Stream ID of a System Exclusive 8 message, allowing several System
+    /// Exclusive messages to be interleaved on the same group.
+    pub StreamId { u8, 16..=23 }
+);
+
+field::impl_bytes!(
+    /// The data bytes of a System Exclusive 8 message (of which only the first
+    /// Number Of Bytes - 1 bytes are valid).
+    pub Sysex8Data { 13, 24 }
+);
+
+// Mixed Data Set
+
+field::impl_field!(
+    /// The ID of a Mixed Data Set, allowing up to 16 Mixed Data Sets to be
+    /// interleaved on the same group.
+    pub MdsId { u8, 12..=15, 4 }
+);
+
+field::impl_field!(
+    /// The number of valid bytes in the chunk of a Mixed Data Set (the Mixed
+    /// Data Set Header message and the following Mixed Data Set Payload
+    /// messages).
+    pub NumberOfValidBytes { u16, 16..=31 }
+);
+
+field::impl_field!(
+    /// The number of chunks in a Mixed Data Set (0 if unknown).
+    pub NumberOfChunks { u16, 32..=47 }
+);
+
+field::impl_field!(
+    /// The number of the chunk within a Mixed Data Set (from 1, or 0 if
+    /// unknown).
+    pub ChunkNumber { u16, 48..=63 }
+);
+
+field::impl_field!(
+    /// The Manufacturer ID of a Mixed Data Set.
+    pub ManufacturerId { u16, 64..=79 }
+);
+
+field::impl_field!(
+    /// The Device ID of a Mixed Data Set.
+    pub DeviceId { u16, 80..=95 }
+);
+
+field::impl_field!(
+    /// The first Sub ID of a Mixed Data Set.
+    pub SubId1 { u16, 96..=111 }
+);
+
+field::impl_field!(
+    /// The second Sub ID of a Mixed Data Set.
+    pub SubId2 { u16, 112..=127 }
+);
+
+field::impl_bytes!(
+    /// The data bytes of a Mixed Data Set Payload message.
+    pub MixedDataSetData { 14, 16 }
+);
+
+// -----------------------------------------------------------------------------
+
+// Messages
+
+// System Exclusive 8
+
+data::impl_sysex8_message!(
+    /// # System Exclusive 8 (Complete)
+    ///
+    /// The System Exclusive 8 (Complete) message **([M2-104-UM 7.8])** is a
+    /// Data message sent using a 128-bit UMP, carrying a complete System
+    /// Exclusive message of up to 13 bytes (excluding the Stream ID).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::data::*;
+    /// #
+    /// let mut data = [0u8; 13];
+    ///
+    /// data[..2].copy_from_slice(&[0x7e, 0x7f]);
+    ///
+    /// let mut packet = Sysex8Complete::packet();
+    /// let message = Sysex8Complete::try_init(
+    ///     &mut packet,
+    ///     NumberOfBytes::new(3),
+    ///     StreamId::new(1),
+    ///     Sysex8Data::new(data),
+    /// )?;
+    ///
+    /// assert_eq!(message.message_type()?, MessageType::Data);
+    /// assert_eq!(message.status()?, Status::Sysex8Complete);
+    ///
+    /// assert_eq!(packet, [0x5003017e, 0x7f000000, 0x00000000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub Sysex8Complete { Status::Sysex8Complete }
+);
+
+data::impl_sysex8_message!(
+    /// # System Exclusive 8 (Start)
+    ///
+    /// The System Exclusive 8 (Start) message **([M2-104-UM 7.8])** is a Data
+    /// message sent using a 128-bit UMP, carrying the first part of a System
+    /// Exclusive message sent across several UMPs.
+    pub Sysex8Start { Status::Sysex8Start }
+);
+
+data::impl_sysex8_message!(
+    /// # System Exclusive 8 (Continue)
+    ///
+    /// The System Exclusive 8 (Continue) message **([M2-104-UM 7.8])** is a
+    /// Data message sent using a 128-bit UMP, carrying a middle part of a
+    /// System Exclusive message sent across several UMPs.
+    pub Sysex8Continue { Status::Sysex8Continue }
+);
+
+data::impl_sysex8_message!(
+    /// # System Exclusive 8 (End)
+    ///
+    /// The System Exclusive 8 (End) message **([M2-104-UM 7.8])** is a Data
+    /// message sent using a 128-bit UMP, carrying the final part of a System
+    /// Exclusive message sent across several UMPs.
+    pub Sysex8End { Status::Sysex8End }
+);
+
+// Mixed Data Set Header
+
+data::impl_message!(
+    /// # Mixed Data Set Header
+    ///
+    /// The Mixed Data Set Header message **([M2-104-UM 7.9])** is a Data
+    /// message sent using a 128-bit UMP, beginning a chunk of a Mixed Data Set
+    /// and identifying the size and position of the chunk, and the
+    /// manufacturer and device to which the data applies.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::data::*;
+    /// #
+    /// let mut packet = MixedDataSetHeader::packet();
+    /// let message = MixedDataSetHeader::try_init(
+    ///     &mut packet,
+    ///     MdsId::new(2),
+    ///     NumberOfValidBytes::new(14),
+    /// )?
+    /// .set_number_of_chunks(NumberOfChunks::new(2))
+    /// .set_chunk_number(ChunkNumber::new(1))
+    /// .set_manufacturer_id(ManufacturerId::new(0x0041));
+    ///
+    /// assert_eq!(message.status()?, Status::MixedDataSetHeader);
+    ///
+    /// assert_eq!(packet, [0x5082000e, 0x00020001, 0x00410000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub MixedDataSetHeader { Status::MixedDataSetHeader, [
+        { mds_id, MdsId },
+        { number_of_valid_bytes, NumberOfValidBytes },
+        { number_of_chunks, NumberOfChunks },
+        { chunk_number, ChunkNumber },
+        { manufacturer_id, ManufacturerId },
+        { device_id, DeviceId },
+        { sub_id_1, SubId1 },
+        { sub_id_2, SubId2 },
+    ] }
+);
+
+impl<'a> MixedDataSetHeader<'a> {
+    /// Initializes the packet as a Mixed Data Set Header message with the given
+    /// Mixed Data Set ID and number of valid bytes in the chunk (with all other
+    /// fields zero).
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        mds_id: MdsId,
+        number_of_valid_bytes: NumberOfValidBytes,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_mds_id(mds_id)
+            .set_number_of_valid_bytes(number_of_valid_bytes))
+    }
+}
+
+// Mixed Data Set Payload
+
+data::impl_message!(
+    /// # Mixed Data Set Payload
+    ///
+    /// The Mixed Data Set Payload message **([M2-104-UM 7.9])** is a Data
+    /// message sent using a 128-bit UMP, carrying 14 bytes of the data of a
+    /// chunk of a Mixed Data Set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::data::*;
+    /// #
+    /// let mut packet = MixedDataSetPayload::packet();
+    /// let message = MixedDataSetPayload::try_init(
+    ///     &mut packet,
+    ///     MdsId::new(2),
+    ///     MixedDataSetData::new([1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]),
+    /// )?;
+    ///
+    /// assert_eq!(message.mds_id()?, MdsId::new(2));
+    ///
+    /// assert_eq!(packet, [0x50920102, 0x03040506, 0x0708090a, 0x0b0c0d0e]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub MixedDataSetPayload { Status::MixedDataSetPayload, [
+        { mds_id, MdsId },
+        { data, MixedDataSetData },
+    ] }
+);
+
+impl<'a> MixedDataSetPayload<'a> {
+    /// Initializes the packet as a Mixed Data Set Payload message with the
+    /// given Mixed Data Set ID and data.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        mds_id: MdsId,
+        data: MixedDataSetData,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_mds_id(mds_id)
+            .set_data(data))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Enumeration
+
+message::impl_enumeration!(
+    /// Data messages.
+    pub Data, [
+        Sysex8Complete,
+        Sysex8Start,
+        Sysex8Continue,
+        Sysex8End,
+        MixedDataSetHeader,
+        MixedDataSetPayload,
+    ]
+);
+
+impl<'a> Data<'a> {
+    pub(crate) fn try_new(bits: &'a mut BitSlice<u32, Msb0>) -> Result<Self, Error> {
+        match bits.try_read_field::<Status>()? {
+            Status::Sysex8Complete => Ok(Self::Sysex8Complete(Sysex8Complete::try_new(bits)?)),
+            Status::Sysex8Start => Ok(Self::Sysex8Start(Sysex8Start::try_new(bits)?)),
+            Status::Sysex8Continue => Ok(Self::Sysex8Continue(Sysex8Continue::try_new(bits)?)),
+            Status::Sysex8End => Ok(Self::Sysex8End(Sysex8End::try_new(bits)?)),
+            Status::MixedDataSetHeader => {
+                Ok(Self::MixedDataSetHeader(MixedDataSetHeader::try_new(bits)?))
+            }
+            Status::MixedDataSetPayload => Ok(Self::MixedDataSetPayload(
+                MixedDataSetPayload::try_new(bits)?,
+            )),
+        }
+    }
+}
+
+impl<'a> DataView<'a> {
+    pub(crate) fn try_new(bits: &'a BitSlice<u32, Msb0>) -> Result<Self, Error> {
+        match bits.try_read_field::<Status>()? {
+            Status::Sysex8Complete => Ok(Self::Sysex8Complete(Sysex8CompleteView::try_new(bits)?)),
+            Status::Sysex8Start => Ok(Self::Sysex8Start(Sysex8StartView::try_new(bits)?)),
+            Status::Sysex8Continue => Ok(Self::Sysex8Continue(Sysex8ContinueView::try_new(bits)?)),
+            Status::Sysex8End => Ok(Self::Sysex8End(Sysex8EndView::try_new(bits)?)),
+            Status::MixedDataSetHeader => Ok(Self::MixedDataSetHeader(
+                MixedDataSetHeaderView::try_new(bits)?,
+            )),
+            Status::MixedDataSetPayload => Ok(Self::MixedDataSetPayload(
+                MixedDataSetPayloadView::try_new(bits)?,
+            )),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Macros
+
+// Message
+
+macro_rules! impl_message {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $message:ident { $status:expr, [
+            $({ $name:ident, $type:ty },)*
+        ] }
+    ) => {
+            message::impl_message!(
+                $(#[$meta])*
+                $vis $message { 4, grouped, [
+                    { status, Status },
+                  $({ $name, $type },)*
+                ] }
+            );
+
+            impl<'a> $message<'a> {
+                pub(crate) const STATUS: Status = $status;
+
+                fn try_init_internal(packet: &'a mut [u32]) -> Result<Self, Error> {
+                    Ok(Self::try_from(packet)?
+                        .reset()
+                        .set_message_type(MessageType::Data)
+                        .set_group(Group::default())
+                        .set_status(Self::STATUS))
+                }
+            }
+
+            message::impl_message_builder_init!($message, try_init_internal);
+    };
+}
+
+// System Exclusive 8 Message
+
+macro_rules! impl_sysex8_message {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $message:ident { $status:expr }
+    ) => {
+        data::impl_message!(
+            $(#[$meta])*
+            $vis $message { $status, [
+                { number_of_bytes, NumberOfBytes },
+                { stream_id, StreamId },
+                { data, Sysex8Data },
+            ] }
+        );
+
+        impl<'a> $message<'a> {
+            #[doc = "Initializes the packet as a System Exclusive 8 message with the given number"]
+            #[doc = "of valid bytes (including the Stream ID), Stream ID, and data."]
+            #[doc = "# Errors"]
+            #[doc = "Returns an [`Error`](crate::Error) if the packet is not of the correct"]
+            #[doc = "size for the message type."]
+            pub fn try_init(
+                packet: &'a mut [u32],
+                number_of_bytes: NumberOfBytes,
+                stream_id: StreamId,
+                data: Sysex8Data,
+            ) -> Result<Self, Error> {
+                Ok(Self::try_init_internal(packet)?
+                    .set_number_of_bytes(number_of_bytes)
+                    .set_stream_id(stream_id)
+                    .set_data(data))
+            }
+        }
+    };
+}
+
+// -----------------------------------------------------------------------------
+
+// Macro Exports
+
+pub(crate) use impl_message;
+pub(crate) use impl_sysex8_message;
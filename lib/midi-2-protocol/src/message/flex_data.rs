@@ -0,0 +1,900 @@
+// =============================================================================
+// Flex Data
+// =============================================================================
+
+//! Flex Data message and value types.
+//!
+//! The [`flex_data`](crate::message::flex_data) module contains the 128-bit
+//! Flex Data messages **([M2-104-UM 7.5])**, which carry setup, performance
+//! and metadata events, addressed to either a channel or a whole group.
+//!
+//! Messages are identified by a Status Bank and a Status. Setup and
+//! Performance messages (such as Set Tempo) are distinct message types, while
+//! the Metadata Text and Performance Text banks are each represented by a
+//! single message type, carrying the kind of text as its Status (and the text
+//! itself, which may span several UMPs, as its data).
+
+use arbitrary_int::UInt;
+use bitvec::{
+    field::BitField,
+    order::Msb0,
+    slice::BitSlice,
+    view::BitView,
+};
+use num_enum::{
+    IntoPrimitive,
+    TryFromPrimitive,
+};
+
+use crate::{
+    field::{
+        self,
+        TryReadFromPacket,
+        WriteToPacket,
+    },
+    message::{
+        self,
+        flex_data,
+        Channel,
+        Group,
+        MessageType,
+    },
+    packet::{
+        GetBitSlice,
+        TryReadField,
+        WriteField,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Fields
+
+// Format
+
+/// Format field type.
+///
+/// The `Format` field type accesses the 2-bit Format field of a Flex Data
+/// message **([M2-104-UM 7.5])**, which indicates whether the message is
+/// complete in a single UMP, or is part of a sequence of UMPs.
+#[derive(Clone, Copy, Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum Format {
+    #[default]
+    Complete = 0x0,
+    Start = 0x1,
+    Continue = 0x2,
+    End = 0x3,
+}
+
+field::impl_field_trait_field_traits!(Format, u8, 8..=9);
+
+// Address
+
+/// Address field type.
+///
+/// The `Address` field type accesses the 2-bit Address field of a Flex Data
+/// message **([M2-104-UM 7.5])**, which indicates whether the message applies
+/// to the channel given in the message, or to the whole group.
+#[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum Address {
+    Channel = 0x0,
+    Group = 0x1,
+}
+
+field::impl_field_trait_field_traits!(Address, u8, 10..=11);
+
+// Status Bank
+
+/// Status Bank field type.
+///
+/// The `StatusBank` field type accesses the 8-bit Status Bank field of a Flex
+/// Data message **([M2-104-UM 7.5])**.
+#[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum StatusBank {
+    SetupAndPerformance = 0x00,
+    MetadataText = 0x01,
+    PerformanceText = 0x02,
+}
+
+field::impl_field_trait_field_traits!(StatusBank, u8, 16..=23);
+
+// Status
+
+/// Status field type.
+///
+/// The `Status` field type accesses the 8-bit Status field of a Flex Data
+/// message in the Setup and Performance status bank **([M2-104-UM 7.5])**.
+#[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum Status {
+    SetTempo = 0x00,
+    SetTimeSignature = 0x01,
+    SetMetronome = 0x02,
+    SetKeySignature = 0x05,
+    SetChordName = 0x06,
+}
+
+field::impl_field_trait_field_traits!(Status, u8, 24..=31);
+
+/// Metadata Text Status field type.
+///
+/// The `MetadataTextStatus` field type accesses the 8-bit Status field of a
+/// Flex Data message in the Metadata Text status bank **([M2-104-UM 7.5])**,
+/// giving the kind of text carried by the message.
+#[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum MetadataTextStatus {
+    Unknown = 0x00,
+    ProjectName = 0x01,
+    CompositionName = 0x02,
+    MIDIClipName = 0x03,
+    CopyrightNotice = 0x04,
+    ComposerName = 0x05,
+    LyricistName = 0x06,
+    ArrangerName = 0x07,
+    PublisherName = 0x08,
+    PrimaryPerformerName = 0x09,
+    AccompanyingPerformerName = 0x0a,
+    RecordingDate = 0x0b,
+    RecordingLocation = 0x0c,
+}
+
+field::impl_field_trait_field_traits!(MetadataTextStatus, u8, 24..=31);
+
+/// Performance Text Status field type.
+///
+/// The `PerformanceTextStatus` field type accesses the 8-bit Status field of a
+/// Flex Data message in the Performance Text status bank **([M2-104-UM
+/// 7.5])**, giving the kind of text carried by the message.
+#[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum PerformanceTextStatus {
+    Unknown = 0x00,
+    Lyrics = 0x01,
+    LyricsLanguage = 0x02,
+    Ruby = 0x03,
+    RubyLanguage = 0x04,
+}
+
+field::impl_field_trait_field_traits!(PerformanceTextStatus, u8, 24..=31);
+
+// Tempo
+
+field::impl_field!(
+    /// The tempo, in units of 10 nanoseconds per quarter note.
+    pub Tempo { u32, 32..=63 }
+);
+
+// Time Signature
+
+field::impl_field!(
+    /// The numerator of the time signature (the number of beats in a bar).
+    pub Numerator { u8, 32..=39 }
+);
+
+field::impl_field!(
+    /// The denominator of the time signature, as a negative power of two (so
+    /// that 2 represents a quarter note, and 3 an eighth note).
+    pub Denominator { u8, 40..=47 }
+);
+
+field::impl_field!(
+    /// The number of 1/32 notes in a beat (0 if unused).
+    pub NumberOf32ndNotes { u8, 48..=55 }
+);
+// Metronome
+
+field::impl_field!(
+    /// The number of MIDI clocks (24 per quarter note) per primary click (0
+    /// turns the metronome off).
+    pub ClocksPerPrimaryClick { u8, 32..=39 }
+);
+
+field::impl_field!(
+    /// The number of primary clicks in the first part of the bar (0 for no
+    /// bar accent).
+    pub BarAccent1 { u8, 40..=47 }
+);
+
+field::impl_field!(
+    /// The number of primary clicks in the second part of the bar.
+    pub BarAccent2 { u8, 48..=55 }
+);
+
+field::impl_field!(
+    /// The number of primary clicks in the third part of the bar.
+    pub BarAccent3 { u8, 56..=63 }
+);
+
+field::impl_field!(
+    /// The number of first subdivision clicks between primary clicks.
+    pub SubdivisionClicks1 { u8, 64..=71 }
+);
+
+field::impl_field!(
+    /// The number of second subdivision clicks between primary clicks.
+    pub SubdivisionClicks2 { u8, 72..=79 }
+);
+
+// Key Signature
+
+field::impl_field!(
+    /// The number of sharps (positive) or flats (negative) in the key
+    /// signature, as a 4-bit two's complement value (0x8 for a non-standard
+    /// key signature). In a Set Chord Name message, the accidental of the
+    /// chord tonic (from -2 for double flat, to 2 for double sharp).
+    pub SharpsFlats { u8, 32..=35, 4 }
+);
+
+/// Tonic field type.
+///
+/// The `Tonic` field type accesses the 4-bit Tonic Note field of a Set Key
+/// Signature or Set Chord Name message **([M2-104-UM 7.5])**, giving the
+/// letter name of the tonic (the accidental, if any, being given by the
+/// `SharpsFlats` field).
+#[derive(Clone, Copy, Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum Tonic {
+    #[default]
+    Unknown = 0x0,
+    A = 0x1,
+    B = 0x2,
+    C = 0x3,
+    D = 0x4,
+    E = 0x5,
+    F = 0x6,
+    G = 0x7,
+}
+
+field::impl_field_trait_field_traits!(Tonic, u8, 36..=39);
+
+// Chord Name
+
+field::impl_field!(
+    /// The type of the chord (e.g. 0x01 for a major chord), as listed for the
+    /// Chord Type field of the Set Chord Name message.
+    pub ChordType { u8, 40..=47 }
+);
+
+field::impl_field!(
+    /// The first alteration of the chord, as a 4-bit alteration type (1 to add,
+    /// 2 to subtract, 3 to raise, or 4 to lower a degree) followed by a 4-bit
+    /// degree (0 for no alteration).
+    pub Alteration1 { u8, 48..=55 }
+);
+
+field::impl_field!(
+    /// The second alteration of the chord (see [`Alteration1`]).
+    pub Alteration2 { u8, 56..=63 }
+);
+
+field::impl_field!(
+    /// The third alteration of the chord (see [`Alteration1`]).
+    pub Alteration3 { u8, 64..=71 }
+);
+
+field::impl_field!(
+    /// The fourth alteration of the chord (see [`Alteration1`]).
+    pub Alteration4 { u8, 72..=79 }
+);
+
+field::impl_field!(
+    /// The accidental of the bass note (from -2 for double flat, to 2 for
+    /// double sharp), as a 4-bit two's complement value.
+    pub BassSharpsFlats { u8, 96..=99, 4 }
+);
+
+/// Bass Note field type.
+///
+/// The `BassNote` field type accesses the 4-bit Bass Note field of a Set Chord
+/// Name message **([M2-104-UM 7.5])**, giving the letter name of the bass note
+/// of a slash chord (or `Unknown` if the bass note is the chord tonic).
+#[derive(Clone, Copy, Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum BassNote {
+    #[default]
+    Unknown = 0x0,
+    A = 0x1,
+    B = 0x2,
+    C = 0x3,
+    D = 0x4,
+    E = 0x5,
+    F = 0x6,
+    G = 0x7,
+}
+
+field::impl_field_trait_field_traits!(BassNote, u8, 100..=103);
+
+field::impl_field!(
+    /// The type of the chord built on the bass note (see [`ChordType`]), or 0
+    /// if the bass note is a single note.
+    pub BassChordType { u8, 104..=111 }
+);
+
+field::impl_field!(
+    /// The first alteration of the bass chord (see [`Alteration1`]).
+    pub BassAlteration1 { u8, 112..=119 }
+);
+
+field::impl_field!(
+    /// The second alteration of the bass chord (see [`Alteration1`]).
+    pub BassAlteration2 { u8, 120..=127 }
+);
+
+// Text
+
+field::impl_bytes!(
+    /// The text of a Metadata Text or Performance Text message, as UTF-8 bytes
+    /// (padded with zero bytes if the text is shorter than 12 bytes). Text
+    /// longer than 12 bytes is sent using a sequence of messages.
+    pub Text { 12, 32 }
+);
+
+// -----------------------------------------------------------------------------
+
+// Messages
+
+// Set Tempo
+
+flex_data::impl_message!(
+    /// # Set Tempo
+    ///
+    /// The Set Tempo message **([M2-104-UM 7.5])** is a Flex Data message sent
+    /// using a 128-bit UMP, giving the tempo (in units of 10 nanoseconds per
+    /// quarter note) from this point onwards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::flex_data::*;
+    /// #
+    /// // 120 beats per minute (500 ms per quarter note)
+    /// let mut packet = SetTempo::packet();
+    /// let message = SetTempo::try_init(&mut packet, Tempo::new(50_000_000))?;
+    ///
+    /// assert_eq!(message.status()?, Status::SetTempo);
+    ///
+    /// assert_eq!(packet, [0xd0100000, 0x02faf080, 0x00000000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub SetTempo { Status::SetTempo, [
+        { tempo, Tempo },
+    ] }
+);
+
+impl<'a> SetTempo<'a> {
+    /// Initializes the packet as a Set Tempo message with the given tempo.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(packet: &'a mut [u32], tempo: Tempo) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?.set_tempo(tempo))
+    }
+}
+
+// Set Time Signature
+
+flex_data::impl_message!(
+    /// # Set Time Signature
+    ///
+    /// The Set Time Signature message **([M2-104-UM 7.5])** is a Flex Data
+    /// message sent using a 128-bit UMP, giving the time signature of the
+    /// music from this point onwards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::flex_data::*;
+    /// #
+    /// // 6/8 time
+    /// let mut packet = SetTimeSignature::packet();
+    /// let _ = SetTimeSignature::try_init(&mut packet, Numerator::new(6), Denominator::new(3))?;
+    ///
+    /// assert_eq!(packet, [0xd0100001, 0x06030000, 0x00000000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub SetTimeSignature { Status::SetTimeSignature, [
+        { numerator, Numerator },
+        { denominator, Denominator },
+        { number_of_32nd_notes, NumberOf32ndNotes },
+    ] }
+);
+
+impl<'a> SetTimeSignature<'a> {
+    /// Initializes the packet as a Set Time Signature message with the given
+    /// numerator and denominator.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        numerator: Numerator,
+        denominator: Denominator,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_numerator(numerator)
+            .set_denominator(denominator))
+    }
+}
+
+// Set Metronome
+
+flex_data::impl_message!(
+    /// # Set Metronome
+    ///
+    /// The Set Metronome message **([M2-104-UM 7.5])** is a Flex Data message
+    /// sent using a 128-bit UMP, configuring a metronome in terms of primary
+    /// clicks, bar accents and subdivision clicks.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::flex_data::*;
+    /// #
+    /// let mut packet = SetMetronome::packet();
+    /// let message = SetMetronome::try_init(&mut packet, ClocksPerPrimaryClick::new(24))?
+    ///     .set_bar_accent_1(BarAccent1::new(4))
+    ///     .set_subdivision_clicks_1(SubdivisionClicks1::new(1));
+    ///
+    /// assert_eq!(message.message_type()?, MessageType::FlexData);
+    /// assert_eq!(message.status()?, Status::SetMetronome);
+    ///
+    /// assert_eq!(packet, [0xd0100002, 0x18040000, 0x01000000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub SetMetronome { Status::SetMetronome, [
+        { clocks_per_primary_click, ClocksPerPrimaryClick },
+        { bar_accent_1, BarAccent1 },
+        { bar_accent_2, BarAccent2 },
+        { bar_accent_3, BarAccent3 },
+        { subdivision_clicks_1, SubdivisionClicks1 },
+        { subdivision_clicks_2, SubdivisionClicks2 },
+    ] }
+);
+
+impl<'a> SetMetronome<'a> {
+    /// Initializes the packet as a Set Metronome message with the given number
+    /// of clocks per primary click (with no bar accents or subdivisions).
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        clocks_per_primary_click: ClocksPerPrimaryClick,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?.set_clocks_per_primary_click(clocks_per_primary_click))
+    }
+}
+
+// Set Key Signature
+
+flex_data::impl_message!(
+    /// # Set Key Signature
+    ///
+    /// The Set Key Signature message **([M2-104-UM 7.5])** is a Flex Data
+    /// message sent using a 128-bit UMP, giving the key signature (as a number
+    /// of sharps or flats) and tonic note of the music from this point onwards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::flex_data::*;
+    /// #
+    /// let mut packet = SetKeySignature::packet();
+    /// let message = SetKeySignature::try_init(&mut packet, SharpsFlats::new(0xe), Tonic::D)?;
+    ///
+    /// assert_eq!(message.status()?, Status::SetKeySignature);
+    /// assert_eq!(message.tonic()?, Tonic::D);
+    ///
+    /// assert_eq!(packet, [0xd0100005, 0xe4000000, 0x00000000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub SetKeySignature { Status::SetKeySignature, [
+        { sharps_flats, SharpsFlats },
+        { tonic, Tonic },
+    ] }
+);
+
+impl<'a> SetKeySignature<'a> {
+    /// Initializes the packet as a Set Key Signature message with the given
+    /// number of sharps or flats and tonic note.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        sharps_flats: SharpsFlats,
+        tonic: Tonic,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_sharps_flats(sharps_flats)
+            .set_tonic(tonic))
+    }
+}
+
+// Set Chord Name
+
+flex_data::impl_message!(
+    /// # Set Chord Name
+    ///
+    /// The Set Chord Name message **([M2-104-UM 7.5])** is a Flex Data message
+    /// sent using a 128-bit UMP, giving the name of the chord from this point
+    /// onwards, as a tonic, chord type and alterations, with an optional bass
+    /// note (or chord) for slash chords.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::flex_data::*;
+    /// #
+    /// // Bbm7/F
+    /// let mut packet = SetChordName::packet();
+    /// let _ = SetChordName::try_init(&mut packet, SharpsFlats::new(0xf), Tonic::B, ChordType::new(0x09))?
+    ///     .set_bass_note(BassNote::F);
+    ///
+    /// assert_eq!(packet, [0xd0100006, 0xf2090000, 0x00000000, 0x06000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub SetChordName { Status::SetChordName, [
+        { sharps_flats, SharpsFlats },
+        { tonic, Tonic },
+        { chord_type, ChordType },
+        { alteration_1, Alteration1 },
+        { alteration_2, Alteration2 },
+        { alteration_3, Alteration3 },
+        { alteration_4, Alteration4 },
+        { bass_sharps_flats, BassSharpsFlats },
+        { bass_note, BassNote },
+        { bass_chord_type, BassChordType },
+        { bass_alteration_1, BassAlteration1 },
+        { bass_alteration_2, BassAlteration2 },
+    ] }
+);
+
+impl<'a> SetChordName<'a> {
+    /// Initializes the packet as a Set Chord Name message with the given chord
+    /// tonic (as an accidental and letter name) and chord type (with no
+    /// alterations or bass note).
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        sharps_flats: SharpsFlats,
+        tonic: Tonic,
+        chord_type: ChordType,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_sharps_flats(sharps_flats)
+            .set_tonic(tonic)
+            .set_chord_type(chord_type))
+    }
+}
+
+// Metadata Text
+
+flex_data::impl_message!(
+    /// # Metadata Text
+    ///
+    /// The Metadata Text messages **([M2-104-UM 7.5])** are Flex Data messages
+    /// sent using 128-bit UMPs, carrying text (such as the name of the project
+    /// or composer) describing the music, as given by the Status of the
+    /// message. Text longer than 12 bytes is sent using a sequence of messages,
+    /// as given by the Format of each message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::flex_data::*;
+    /// #
+    /// let mut text = [0u8; 12];
+    ///
+    /// text[..5].copy_from_slice(b"Intro");
+    ///
+    /// let mut packet = MetadataText::packet();
+    /// let _ = MetadataText::try_init(&mut packet, MetadataTextStatus::MIDIClipName, Text::new(text))?;
+    ///
+    /// if let Message::FlexData(FlexData::MetadataText(message)) = Message::try_from(&mut packet[..])? {
+    ///     assert_eq!(message.status()?, MetadataTextStatus::MIDIClipName);
+    ///     assert_eq!(&<[u8; 12]>::from(message.text()?)[..5], b"Intro");
+    /// } else {
+    ///     panic!("Oh No!")
+    /// }
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub MetadataText { StatusBank::MetadataText, MetadataTextStatus, [
+        { text, Text },
+    ] }
+);
+
+impl<'a> MetadataText<'a> {
+    /// Initializes the packet as a complete (single UMP) Metadata Text message
+    /// with the given status and text.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        status: MetadataTextStatus,
+        text: Text,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_header(packet)?
+            .set_status(status)
+            .set_text(text))
+    }
+}
+
+// Performance Text
+
+flex_data::impl_message!(
+    /// # Performance Text
+    ///
+    /// The Performance Text messages **([M2-104-UM 7.5])** are Flex Data
+    /// messages sent using 128-bit UMPs, carrying text (such as lyrics) to be
+    /// displayed as the music is performed, as given by the Status of the
+    /// message. Text longer than 12 bytes is sent using a sequence of messages,
+    /// as given by the Format of each message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::flex_data::*;
+    /// #
+    /// let mut text = [0u8; 12];
+    ///
+    /// text[..4].copy_from_slice(b"la, ");
+    ///
+    /// let mut packet = PerformanceText::packet();
+    /// let _ = PerformanceText::try_init(&mut packet, PerformanceTextStatus::Lyrics, Text::new(text))?
+    ///     .set_format(Format::Start);
+    ///
+    /// assert_eq!(packet, [0xd0500201, 0x6c612c20, 0x00000000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub PerformanceText { StatusBank::PerformanceText, PerformanceTextStatus, [
+        { text, Text },
+    ] }
+);
+
+impl<'a> PerformanceText<'a> {
+    /// Initializes the packet as a complete (single UMP) Performance Text
+    /// message with the given status and text.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        status: PerformanceTextStatus,
+        text: Text,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_header(packet)?
+            .set_status(status)
+            .set_text(text))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Enumeration
+
+message::impl_enumeration!(
+    /// Flex Data messages.
+    pub FlexData, [
+        SetTempo,
+        SetTimeSignature,
+        SetMetronome,
+        SetKeySignature,
+        SetChordName,
+        MetadataText,
+        PerformanceText,
+    ]
+);
+
+impl<'a> FlexData<'a> {
+    /// Returns the Address of the message (whether it is addressed to a
+    /// channel, or to the whole group).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the Address field holds a
+    /// reserved value (messages with a reserved Address are rejected when
+    /// parsed as a [`FlexData`] or [`Message`](crate::message::Message)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::flex_data::*;
+    /// #
+    /// let mut packet = [0xd0100000, 0x02faf080, 0x00000000, 0x00000000];
+    ///
+    /// if let Message::FlexData(message) = Message::try_from(&mut packet[..])? {
+    ///     assert_eq!(message.address()?, Address::Group);
+    /// }
+    ///
+    /// // a set tempo message with a reserved Address (2)
+    /// let mut packet = [0xd0200000, 0x02faf080, 0x00000000, 0x00000000];
+    ///
+    /// assert!(matches!(Message::try_from(&mut packet[..]), Err(Error::Conversion(2))));
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn address(&self) -> Result<Address, Error> {
+        match self {
+            Self::SetTempo(message) => message.address(),
+            Self::SetTimeSignature(message) => message.address(),
+            Self::SetMetronome(message) => message.address(),
+            Self::SetKeySignature(message) => message.address(),
+            Self::SetChordName(message) => message.address(),
+            Self::MetadataText(message) => message.address(),
+            Self::PerformanceText(message) => message.address(),
+        }
+    }
+
+    pub(crate) fn try_new(bits: &'a mut BitSlice<u32, Msb0>) -> Result<Self, Error> {
+        // reject reserved Address values, as every Flex Data message has one
+        bits.try_read_field::<Address>()?;
+
+        match bits.try_read_field::<StatusBank>()? {
+            StatusBank::SetupAndPerformance => match bits.try_read_field::<Status>()? {
+                Status::SetTempo => Ok(Self::SetTempo(SetTempo::try_new(bits)?)),
+                Status::SetTimeSignature => {
+                    Ok(Self::SetTimeSignature(SetTimeSignature::try_new(bits)?))
+                }
+                Status::SetMetronome => Ok(Self::SetMetronome(SetMetronome::try_new(bits)?)),
+                Status::SetKeySignature => {
+                    Ok(Self::SetKeySignature(SetKeySignature::try_new(bits)?))
+                }
+                Status::SetChordName => Ok(Self::SetChordName(SetChordName::try_new(bits)?)),
+            },
+            StatusBank::MetadataText => Ok(Self::MetadataText(MetadataText::try_new(bits)?)),
+            StatusBank::PerformanceText => {
+                Ok(Self::PerformanceText(PerformanceText::try_new(bits)?))
+            }
+        }
+    }
+}
+
+impl<'a> FlexDataView<'a> {
+    pub(crate) fn try_new(bits: &'a BitSlice<u32, Msb0>) -> Result<Self, Error> {
+        // reject reserved Address values, as every Flex Data message has one
+        bits.try_read_field::<Address>()?;
+
+        match bits.try_read_field::<StatusBank>()? {
+            StatusBank::SetupAndPerformance => match bits.try_read_field::<Status>()? {
+                Status::SetTempo => Ok(Self::SetTempo(SetTempoView::try_new(bits)?)),
+                Status::SetTimeSignature => {
+                    Ok(Self::SetTimeSignature(SetTimeSignatureView::try_new(bits)?))
+                }
+                Status::SetMetronome => Ok(Self::SetMetronome(SetMetronomeView::try_new(bits)?)),
+                Status::SetKeySignature => {
+                    Ok(Self::SetKeySignature(SetKeySignatureView::try_new(bits)?))
+                }
+                Status::SetChordName => Ok(Self::SetChordName(SetChordNameView::try_new(bits)?)),
+            },
+            StatusBank::MetadataText => Ok(Self::MetadataText(MetadataTextView::try_new(bits)?)),
+            StatusBank::PerformanceText => {
+                Ok(Self::PerformanceText(PerformanceTextView::try_new(bits)?))
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Macros
+
+// Message
+
+macro_rules! impl_message {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $message:ident { $status:expr, [
+            $({ $name:ident, $type:ty },)*
+        ] }
+    ) => {
+            flex_data::impl_message!(
+                @header
+                $(#[$meta])*
+                $vis $message { StatusBank::SetupAndPerformance, Status, [
+                  $({ $name, $type },)*
+                ] }
+            );
+
+            impl<'a> $message<'a> {
+                pub(crate) const STATUS: Status = $status;
+
+                fn try_init_internal(packet: &'a mut [u32]) -> Result<Self, Error> {
+                    Ok(Self::try_init_header(packet)?.set_status(Self::STATUS))
+                }
+            }
+
+            message::impl_message_builder_init!($message, try_init_internal);
+    };
+    (
+        $(#[$meta:meta])*
+        $vis:vis $message:ident { $status_bank:expr, $status:ty, [
+            $({ $name:ident, $type:ty },)*
+        ] }
+    ) => {
+            flex_data::impl_message!(
+                @header
+                $(#[$meta])*
+                $vis $message { $status_bank, $status, [
+                  $({ $name, $type },)*
+                ] }
+            );
+
+            message::impl_message_builder_init!($message, try_init_header);
+    };
+    (
+        @header
+        $(#[$meta:meta])*
+        $vis:vis $message:ident { $status_bank:expr, $status:ty, [
+            $({ $name:ident, $type:ty },)*
+        ] }
+    ) => {
+            message::impl_message!(
+                $(#[$meta])*
+                $vis $message { 4, grouped, [
+                    { format, Format },
+                    { address, Address },
+                    { channel, Channel },
+                    { status_bank, StatusBank },
+                    { status, $status },
+                  $({ $name, $type },)*
+                ] }
+            );
+
+            impl<'a> $message<'a> {
+                pub(crate) const STATUS_BANK: StatusBank = $status_bank;
+
+                fn try_init_header(packet: &'a mut [u32]) -> Result<Self, Error> {
+                    Ok(Self::try_from(packet)?
+                        .reset()
+                        .set_message_type(MessageType::FlexData)
+                        .set_group(Group::default())
+                        .set_format(Format::Complete)
+                        .set_address(Address::Group)
+                        .set_channel(Channel::default())
+                        .set_status_bank(Self::STATUS_BANK))
+                }
+            }
+    };
+}
+
+// -----------------------------------------------------------------------------
+
+// Macro Exports
+
+pub(crate) use impl_message;
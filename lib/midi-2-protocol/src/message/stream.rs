@@ -0,0 +1,1069 @@
+// =============================================================================
+// Stream
+// =============================================================================
+
+//! UMP Stream message and value types.
+//!
+//! The [`stream`](crate::message::stream) module contains the 128-bit UMP
+//! Stream messages **([M2-104-UM 7.1])**, which are used to discover and
+//! configure an endpoint -- its version, protocol, identity and names, and the
+//! function blocks into which its groups are organized.
+//!
+//! Stream messages have no Group field, and apply to the whole endpoint. Names
+//! longer than a single message are sent using a sequence of messages, as given
+//! by the Format of each message.
+
+use arbitrary_int::UInt;
+use bitvec::{
+    field::BitField,
+    order::Msb0,
+    slice::BitSlice,
+    view::BitView,
+};
+use num_enum::{
+    IntoPrimitive,
+    TryFromPrimitive,
+};
+
+use crate::{
+    field::{
+        self,
+        TryReadFromPacket,
+        WriteToPacket,
+    },
+    message::{
+        self,
+        stream,
+        MessageType,
+    },
+    packet::{
+        GetBitSlice,
+        TryReadField,
+        WriteField,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Fields
+
+// Format
+
+/// Format field type.
+///
+/// The `Format` field type accesses the 2-bit Format field of a UMP Stream
+/// message **([M2-104-UM 7.1])**, which indicates whether the message is
+/// complete in a single UMP, or is part of a sequence of UMPs.
+#[derive(Clone, Copy, Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum Format {
+    #[default]
+    Complete = 0x0,
+    Start = 0x1,
+    Continue = 0x2,
+    End = 0x3,
+}
+
+field::impl_field_trait_field_traits!(Format, u8, 4..=5);
+
+// Status
+
+/// Status field type.
+///
+/// The `Status` field type accesses the 10-bit Status field of a UMP Stream
+/// message **([M2-104-UM 7.1])**.
+#[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = status_conversion))]
+#[repr(u16)]
+pub enum Status {
+    EndpointDiscovery = 0x000,
+    EndpointInfoNotification = 0x001,
+    DeviceIdentityNotification = 0x002,
+    EndpointNameNotification = 0x003,
+    ProductInstanceIdNotification = 0x004,
+    StreamConfigurationRequest = 0x005,
+    StreamConfigurationNotification = 0x006,
+    FunctionBlockDiscovery = 0x010,
+    FunctionBlockInfoNotification = 0x011,
+    FunctionBlockNameNotification = 0x012,
+}
+
+field::impl_field_trait_field_traits!(Status, u16, 6..=15);
+
+// Endpoint
+
+field::impl_field!(
+    /// The major version of UMP supported by the endpoint.
+    pub UmpVersionMajor { u8, 16..=23 }
+);
+
+field::impl_field!(
+    /// The minor version of UMP supported by the endpoint.
+    pub UmpVersionMinor { u8, 24..=31 }
+);
+
+/// Endpoint Discovery filter flags.
+///
+/// The `EndpointFilter` field type accesses the filter bitmap of an
+/// [`EndpointDiscovery`] message **([M2-104-UM 7.1])**, giving the
+/// notifications requested from the endpoint.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct EndpointFilter {
+    pub endpoint_info: bool,
+    pub device_identity: bool,
+    pub endpoint_name: bool,
+    pub product_instance_id: bool,
+    pub stream_configuration: bool,
+}
+
+impl TryReadFromPacket for EndpointFilter {
+    fn try_read_from_packet<P>(packet: &P) -> Result<Self, Error>
+    where
+        Self: Sized,
+        P: GetBitSlice + ?Sized,
+    {
+        let bit_slice = packet.get_bit_slice();
+
+        Ok(Self {
+            endpoint_info: bit_slice[63],
+            device_identity: bit_slice[62],
+            endpoint_name: bit_slice[61],
+            product_instance_id: bit_slice[60],
+            stream_configuration: bit_slice[59],
+        })
+    }
+}
+
+impl WriteToPacket for EndpointFilter {
+    fn write_to_packet<P>(self, mut packet: P) -> P
+    where
+        P: GetBitSlice,
+    {
+        let bit_slice = packet.get_bit_slice_mut();
+
+        bit_slice.set(63, self.endpoint_info);
+        bit_slice.set(62, self.device_identity);
+        bit_slice.set(61, self.endpoint_name);
+        bit_slice.set(60, self.product_instance_id);
+        bit_slice.set(59, self.stream_configuration);
+        packet
+    }
+}
+
+field::impl_field!(
+    /// The number of function blocks of the endpoint.
+    pub NumberOfFunctionBlocks { u8, 33..=39, 7 }
+);
+
+/// Endpoint capability flags.
+///
+/// The `EndpointCapabilities` field type accesses the capability flags of an
+/// [`EndpointInfoNotification`] message **([M2-104-UM 7.1])** -- whether the
+/// function blocks of the endpoint are static, the protocols supported, and
+/// whether JR Timestamps can be received and transmitted.
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct EndpointCapabilities {
+    pub static_function_blocks: bool,
+    pub midi_2_protocol: bool,
+    pub midi_1_protocol: bool,
+    pub receive_jr_timestamps: bool,
+    pub transmit_jr_timestamps: bool,
+}
+
+impl TryReadFromPacket for EndpointCapabilities {
+    fn try_read_from_packet<P>(packet: &P) -> Result<Self, Error>
+    where
+        Self: Sized,
+        P: GetBitSlice + ?Sized,
+    {
+        let bit_slice = packet.get_bit_slice();
+
+        Ok(Self {
+            static_function_blocks: bit_slice[32],
+            midi_2_protocol: bit_slice[54],
+            midi_1_protocol: bit_slice[55],
+            receive_jr_timestamps: bit_slice[62],
+            transmit_jr_timestamps: bit_slice[63],
+        })
+    }
+}
+
+impl WriteToPacket for EndpointCapabilities {
+    fn write_to_packet<P>(self, mut packet: P) -> P
+    where
+        P: GetBitSlice,
+    {
+        let bit_slice = packet.get_bit_slice_mut();
+
+        bit_slice.set(32, self.static_function_blocks);
+        bit_slice.set(54, self.midi_2_protocol);
+        bit_slice.set(55, self.midi_1_protocol);
+        bit_slice.set(62, self.receive_jr_timestamps);
+        bit_slice.set(63, self.transmit_jr_timestamps);
+        packet
+    }
+}
+
+// Device Identity
+
+field::impl_field!(
+    /// The System Exclusive ID of the manufacturer of the device, as three
+    /// 7-bit bytes (the first being 0 for a single byte ID).
+    pub DeviceManufacturer { u32, 40..=63, 24 }
+);
+
+field::impl_field!(
+    /// The device family, as two 7-bit bytes (LSB first).
+    pub DeviceFamily { u16, 64..=79 }
+);
+
+field::impl_field!(
+    /// The model number of the device within the device family, as two 7-bit
+    /// bytes (LSB first).
+    pub DeviceFamilyModelNumber { u16, 80..=95 }
+);
+
+field::impl_field!(
+    /// The software revision level of the device, as four 7-bit bytes.
+    pub SoftwareRevisionLevel { u32, 96..=127 }
+);
+
+// Names
+
+field::impl_bytes!(
+    /// A part of the name of an endpoint, as UTF-8 bytes (padded with zero
+    /// bytes if the name is shorter than 14 bytes).
+    pub EndpointName { 14, 16 }
+);
+
+field::impl_bytes!(
+    /// A part of the product instance ID of an endpoint (e.g. a serial
+    /// number), as ASCII bytes (padded with zero bytes if the ID is shorter
+    /// than 14 bytes).
+    pub ProductInstanceId { 14, 16 }
+);
+
+field::impl_bytes!(
+    /// A part of the name of a function block, as UTF-8 bytes (padded with zero
+    /// bytes if the name is shorter than 13 bytes).
+    pub FunctionBlockName { 13, 24 }
+);
+
+// Stream Configuration
+
+/// Protocol field type.
+///
+/// The `Protocol` field type accesses the 8-bit Protocol field of a Stream
+/// Configuration Request or Notification message **([M2-104-UM 7.1])**.
+#[derive(Clone, Copy, Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum Protocol {
+    MIDI1 = 0x01,
+    #[default]
+    MIDI2 = 0x02,
+}
+
+field::impl_field_trait_field_traits!(Protocol, u8, 16..=23);
+
+/// Jitter Reduction flags.
+///
+/// The `JitterReduction` field type accesses the JR Timestamp flags of a
+/// Stream Configuration Request or Notification message **([M2-104-UM
+/// 7.1])**, giving whether JR Timestamps are to be received and transmitted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct JitterReduction {
+    pub receive: bool,
+    pub transmit: bool,
+}
+
+impl TryReadFromPacket for JitterReduction {
+    fn try_read_from_packet<P>(packet: &P) -> Result<Self, Error>
+    where
+        Self: Sized,
+        P: GetBitSlice + ?Sized,
+    {
+        let bit_slice = packet.get_bit_slice();
+
+        Ok(Self {
+            receive: bit_slice[30],
+            transmit: bit_slice[31],
+        })
+    }
+}
+
+impl WriteToPacket for JitterReduction {
+    fn write_to_packet<P>(self, mut packet: P) -> P
+    where
+        P: GetBitSlice,
+    {
+        let bit_slice = packet.get_bit_slice_mut();
+
+        bit_slice.set(30, self.receive);
+        bit_slice.set(31, self.transmit);
+        packet
+    }
+}
+
+// Function Block
+
+field::impl_field!(
+    /// The number of a function block (in a Function Block Discovery message,
+    /// 0xff requests all function blocks).
+    pub FunctionBlockNumber { u8, 16..=23 }
+);
+
+/// Function Block Discovery filter flags.
+///
+/// The `FunctionBlockFilter` field type accesses the filter bitmap of a
+/// [`FunctionBlockDiscovery`] message **([M2-104-UM 7.1])**, giving the
+/// notifications requested for each function block.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct FunctionBlockFilter {
+    pub info: bool,
+    pub name: bool,
+}
+
+impl TryReadFromPacket for FunctionBlockFilter {
+    fn try_read_from_packet<P>(packet: &P) -> Result<Self, Error>
+    where
+        Self: Sized,
+        P: GetBitSlice + ?Sized,
+    {
+        let bit_slice = packet.get_bit_slice();
+
+        Ok(Self {
+            info: bit_slice[31],
+            name: bit_slice[30],
+        })
+    }
+}
+
+impl WriteToPacket for FunctionBlockFilter {
+    fn write_to_packet<P>(self, mut packet: P) -> P
+    where
+        P: GetBitSlice,
+    {
+        let bit_slice = packet.get_bit_slice_mut();
+
+        bit_slice.set(31, self.info);
+        bit_slice.set(30, self.name);
+        packet
+    }
+}
+
+/// Active field type.
+///
+/// The `Active` field type accesses the 1-bit Active field of a Function Block
+/// Info Notification message **([M2-104-UM 7.1])**.
+#[derive(Clone, Copy, Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum Active {
+    #[default]
+    Inactive = 0x0,
+    Active = 0x1,
+}
+
+field::impl_field_trait_field_traits!(Active, u8, 16..=16);
+
+field::impl_field!(
+    /// The number of the function block described by a Function Block Info
+    /// Notification message (as a 7-bit value, following the Active field).
+    pub BlockNumber { u8, 17..=23, 7 }
+);
+
+/// UI Hint field type.
+///
+/// The `UIHint` field type accesses the 2-bit UI Hint field of a Function
+/// Block Info Notification message **([M2-104-UM 7.1])**, suggesting how the
+/// function block should be presented to a user.
+#[derive(Clone, Copy, Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum UIHint {
+    #[default]
+    Unknown = 0x0,
+    Receiver = 0x1,
+    Sender = 0x2,
+    SenderReceiver = 0x3,
+}
+
+field::impl_field_trait_field_traits!(UIHint, u8, 26..=27);
+
+/// MIDI 1.0 field type.
+///
+/// The `MIDI1` field type accesses the 2-bit MIDI 1.0 field of a Function
+/// Block Info Notification message **([M2-104-UM 7.1])**, giving whether the
+/// function block represents a MIDI 1.0 port (and if so, whether its bandwidth
+/// is restricted to that of a MIDI 1.0 DIN connection).
+#[derive(Clone, Copy, Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum MIDI1 {
+    #[default]
+    NotMIDI1 = 0x0,
+    UnrestrictedBandwidth = 0x1,
+    RestrictedBandwidth = 0x2,
+}
+
+field::impl_field_trait_field_traits!(MIDI1, u8, 28..=29);
+
+/// Direction field type.
+///
+/// The `Direction` field type accesses the 2-bit Direction field of a Function
+/// Block Info Notification message **([M2-104-UM 7.1])**.
+#[derive(Clone, Copy, Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum Direction {
+    Input = 0x1,
+    Output = 0x2,
+    #[default]
+    Bidirectional = 0x3,
+}
+
+field::impl_field_trait_field_traits!(Direction, u8, 30..=31);
+
+field::impl_field!(
+    /// The first group spanned by the function block (from 0x0 for group 1).
+    pub FirstGroup { u8, 32..=39 }
+);
+
+field::impl_field!(
+    /// The number of groups spanned by the function block.
+    pub NumberOfGroups { u8, 40..=47 }
+);
+
+field::impl_field!(
+    /// The version of MIDI-CI supported by the function block (0 if MIDI-CI
+    /// is not supported).
+    pub MIDICIVersion { u8, 48..=55 }
+);
+
+field::impl_field!(
+    /// The maximum number of simultaneous System Exclusive 8 streams supported
+    /// by the function block.
+    pub MaxSysex8Streams { u8, 56..=63 }
+);
+
+// -----------------------------------------------------------------------------
+
+// Messages
+
+// Endpoint Discovery
+
+stream::impl_message!(
+    /// # Endpoint Discovery
+    ///
+    /// The Endpoint Discovery message **([M2-104-UM 7.1])** is a UMP Stream
+    /// message sent using a 128-bit UMP, requesting information about an
+    /// endpoint (as given by the filter).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::stream::*;
+    /// #
+    /// let filter = EndpointFilter {
+    ///     endpoint_info: true,
+    ///     device_identity: true,
+    ///     endpoint_name: true,
+    ///     product_instance_id: true,
+    ///     stream_configuration: true,
+    /// };
+    ///
+    /// let mut packet = EndpointDiscovery::packet();
+    /// let message = EndpointDiscovery::try_init(
+    ///     &mut packet,
+    ///     UmpVersionMajor::new(1),
+    ///     UmpVersionMinor::new(1),
+    ///     filter,
+    /// )?;
+    ///
+    /// assert_eq!(message.message_type()?, MessageType::Stream);
+    /// assert_eq!(message.status()?, Status::EndpointDiscovery);
+    ///
+    /// assert_eq!(packet, [0xf0000101, 0x0000001f, 0x00000000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub EndpointDiscovery { Status::EndpointDiscovery, [
+        { ump_version_major, UmpVersionMajor },
+        { ump_version_minor, UmpVersionMinor },
+        { filter, EndpointFilter },
+    ] }
+);
+
+impl<'a> EndpointDiscovery<'a> {
+    /// Initializes the packet as an Endpoint Discovery message with the given
+    /// UMP version and filter.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        ump_version_major: UmpVersionMajor,
+        ump_version_minor: UmpVersionMinor,
+        filter: EndpointFilter,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_ump_version_major(ump_version_major)
+            .set_ump_version_minor(ump_version_minor)
+            .set_filter(filter))
+    }
+}
+
+// Endpoint Info Notification
+
+stream::impl_message!(
+    /// # Endpoint Info Notification
+    ///
+    /// The Endpoint Info Notification message **([M2-104-UM 7.1])** is a UMP
+    /// Stream message sent using a 128-bit UMP, giving the UMP version,
+    /// number of function blocks and capabilities of an endpoint.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::stream::*;
+    /// #
+    /// let capabilities = EndpointCapabilities {
+    ///     static_function_blocks: true,
+    ///     midi_2_protocol: true,
+    ///     midi_1_protocol: true,
+    ///     ..EndpointCapabilities::default()
+    /// };
+    ///
+    /// let mut packet = EndpointInfoNotification::packet();
+    /// let _ = EndpointInfoNotification::try_init(
+    ///     &mut packet,
+    ///     UmpVersionMajor::new(1),
+    ///     UmpVersionMinor::new(1),
+    ///     NumberOfFunctionBlocks::new(2),
+    ///     capabilities,
+    /// )?;
+    ///
+    /// assert_eq!(packet, [0xf0010101, 0x82000300, 0x00000000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub EndpointInfoNotification { Status::EndpointInfoNotification, [
+        { ump_version_major, UmpVersionMajor },
+        { ump_version_minor, UmpVersionMinor },
+        { number_of_function_blocks, NumberOfFunctionBlocks },
+        { capabilities, EndpointCapabilities },
+    ] }
+);
+
+impl<'a> EndpointInfoNotification<'a> {
+    /// Initializes the packet as an Endpoint Info Notification message with
+    /// the given UMP version, number of function blocks and capabilities.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        ump_version_major: UmpVersionMajor,
+        ump_version_minor: UmpVersionMinor,
+        number_of_function_blocks: NumberOfFunctionBlocks,
+        capabilities: EndpointCapabilities,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_ump_version_major(ump_version_major)
+            .set_ump_version_minor(ump_version_minor)
+            .set_number_of_function_blocks(number_of_function_blocks)
+            .set_capabilities(capabilities))
+    }
+}
+
+// Device Identity Notification
+
+stream::impl_message!(
+    /// # Device Identity Notification
+    ///
+    /// The Device Identity Notification message **([M2-104-UM 7.1])** is a UMP
+    /// Stream message sent using a 128-bit UMP, giving the manufacturer,
+    /// family, model and software revision of the device.
+    pub DeviceIdentityNotification { Status::DeviceIdentityNotification, [
+        { device_manufacturer, DeviceManufacturer },
+        { device_family, DeviceFamily },
+        { device_family_model_number, DeviceFamilyModelNumber },
+        { software_revision_level, SoftwareRevisionLevel },
+    ] }
+);
+
+impl<'a> DeviceIdentityNotification<'a> {
+    /// Initializes the packet as a Device Identity Notification message with
+    /// the given device identity.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        device_manufacturer: DeviceManufacturer,
+        device_family: DeviceFamily,
+        device_family_model_number: DeviceFamilyModelNumber,
+        software_revision_level: SoftwareRevisionLevel,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_device_manufacturer(device_manufacturer)
+            .set_device_family(device_family)
+            .set_device_family_model_number(device_family_model_number)
+            .set_software_revision_level(software_revision_level))
+    }
+}
+
+// Endpoint Name Notification
+
+stream::impl_message!(
+    /// # Endpoint Name Notification
+    ///
+    /// The Endpoint Name Notification message **([M2-104-UM 7.1])** is a UMP
+    /// Stream message sent using a 128-bit UMP, carrying the name of the
+    /// endpoint (or a part of it, for names longer than 14 bytes).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::stream::*;
+    /// #
+    /// let mut name = [0u8; 14];
+    ///
+    /// name[..5].copy_from_slice(b"Synth");
+    ///
+    /// let mut packet = EndpointNameNotification::packet();
+    /// let _ = EndpointNameNotification::try_init(&mut packet, EndpointName::new(name))?;
+    ///
+    /// assert_eq!(packet, [0xf0035379, 0x6e746800, 0x00000000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub EndpointNameNotification { Status::EndpointNameNotification, [
+        { name, EndpointName },
+    ] }
+);
+
+impl<'a> EndpointNameNotification<'a> {
+    /// Initializes the packet as a complete (single UMP) Endpoint Name
+    /// Notification message with the given name.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(packet: &'a mut [u32], name: EndpointName) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?.set_name(name))
+    }
+}
+
+// Product Instance ID Notification
+
+stream::impl_message!(
+    /// # Product Instance ID Notification
+    ///
+    /// The Product Instance ID Notification message **([M2-104-UM 7.1])** is a
+    /// UMP Stream message sent using a 128-bit UMP, carrying an ID unique to
+    /// this instance of the product (or a part of it, for IDs longer than 14
+    /// bytes).
+    pub ProductInstanceIdNotification { Status::ProductInstanceIdNotification, [
+        { product_instance_id, ProductInstanceId },
+    ] }
+);
+
+impl<'a> ProductInstanceIdNotification<'a> {
+    /// Initializes the packet as a complete (single UMP) Product Instance ID
+    /// Notification message with the given product instance ID.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        product_instance_id: ProductInstanceId,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?.set_product_instance_id(product_instance_id))
+    }
+}
+
+// Stream Configuration Request
+
+stream::impl_message!(
+    /// # Stream Configuration Request
+    ///
+    /// The Stream Configuration Request message **([M2-104-UM 7.1])** is a UMP
+    /// Stream message sent using a 128-bit UMP, requesting that an endpoint
+    /// use the given protocol and JR Timestamp settings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::stream::*;
+    /// #
+    /// let mut packet = StreamConfigurationRequest::packet();
+    /// let _ = StreamConfigurationRequest::try_init(
+    ///     &mut packet,
+    ///     Protocol::MIDI2,
+    ///     JitterReduction { receive: false, transmit: true },
+    /// )?;
+    ///
+    /// assert_eq!(packet, [0xf0050201, 0x00000000, 0x00000000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub StreamConfigurationRequest { Status::StreamConfigurationRequest, [
+        { protocol, Protocol },
+        { jitter_reduction, JitterReduction },
+    ] }
+);
+
+impl<'a> StreamConfigurationRequest<'a> {
+    /// Initializes the packet as a Stream Configuration Request message with
+    /// the given protocol and JR Timestamp settings.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        protocol: Protocol,
+        jitter_reduction: JitterReduction,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_protocol(protocol)
+            .set_jitter_reduction(jitter_reduction))
+    }
+}
+
+// Stream Configuration Notification
+
+stream::impl_message!(
+    /// # Stream Configuration Notification
+    ///
+    /// The Stream Configuration Notification message **([M2-104-UM 7.1])** is a
+    /// UMP Stream message sent using a 128-bit UMP, giving the protocol and JR
+    /// Timestamp settings currently in use by an endpoint.
+    pub StreamConfigurationNotification { Status::StreamConfigurationNotification, [
+        { protocol, Protocol },
+        { jitter_reduction, JitterReduction },
+    ] }
+);
+
+impl<'a> StreamConfigurationNotification<'a> {
+    /// Initializes the packet as a Stream Configuration Notification message
+    /// with the given protocol and JR Timestamp settings.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        protocol: Protocol,
+        jitter_reduction: JitterReduction,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_protocol(protocol)
+            .set_jitter_reduction(jitter_reduction))
+    }
+}
+
+// Function Block Discovery
+
+stream::impl_message!(
+    /// # Function Block Discovery
+    ///
+    /// The Function Block Discovery message **([M2-104-UM 7.1])** is a UMP
+    /// Stream message sent using a 128-bit UMP, requesting information about
+    /// one (or all) of the function blocks of an endpoint.
+    pub FunctionBlockDiscovery { Status::FunctionBlockDiscovery, [
+        { function_block_number, FunctionBlockNumber },
+        { filter, FunctionBlockFilter },
+    ] }
+);
+
+impl<'a> FunctionBlockDiscovery<'a> {
+    /// Initializes the packet as a Function Block Discovery message with the
+    /// given function block number (0xff for all function blocks) and filter.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        function_block_number: FunctionBlockNumber,
+        filter: FunctionBlockFilter,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_function_block_number(function_block_number)
+            .set_filter(filter))
+    }
+}
+
+// Function Block Info Notification
+
+stream::impl_message!(
+    /// # Function Block Info Notification
+    ///
+    /// The Function Block Info Notification message **([M2-104-UM 7.1])** is a
+    /// UMP Stream message sent using a 128-bit UMP, describing a function block
+    /// of an endpoint -- the groups it spans, its direction, and its MIDI 1.0
+    /// and MIDI-CI support.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::stream::*;
+    /// #
+    /// let mut packet = FunctionBlockInfoNotification::packet();
+    /// let _ = FunctionBlockInfoNotification::try_init(
+    ///     &mut packet,
+    ///     BlockNumber::new(0),
+    ///     FirstGroup::new(0),
+    ///     NumberOfGroups::new(1),
+    /// )?
+    /// .set_active(Active::Active)
+    /// .set_ui_hint(UIHint::SenderReceiver)
+    /// .set_midi_ci_version(MIDICIVersion::new(2));
+    ///
+    /// assert_eq!(packet, [0xf0118033, 0x00010200, 0x00000000, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub FunctionBlockInfoNotification { Status::FunctionBlockInfoNotification, [
+        { active, Active },
+        { block_number, BlockNumber },
+        { ui_hint, UIHint },
+        { midi_1, MIDI1 },
+        { direction, Direction },
+        { first_group, FirstGroup },
+        { number_of_groups, NumberOfGroups },
+        { midi_ci_version, MIDICIVersion },
+        { max_sysex8_streams, MaxSysex8Streams },
+    ] }
+);
+
+impl<'a> FunctionBlockInfoNotification<'a> {
+    /// Initializes the packet as a Function Block Info Notification message for
+    /// an inactive, bidirectional function block with the given number,
+    /// spanning the given groups.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        block_number: BlockNumber,
+        first_group: FirstGroup,
+        number_of_groups: NumberOfGroups,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_block_number(block_number)
+            .set_direction(Direction::Bidirectional)
+            .set_first_group(first_group)
+            .set_number_of_groups(number_of_groups))
+    }
+}
+
+// Function Block Name Notification
+
+stream::impl_message!(
+    /// # Function Block Name Notification
+    ///
+    /// The Function Block Name Notification message **([M2-104-UM 7.1])** is a
+    /// UMP Stream message sent using a 128-bit UMP, carrying the name of a
+    /// function block (or a part of it, for names longer than 13 bytes).
+    pub FunctionBlockNameNotification { Status::FunctionBlockNameNotification, [
+        { function_block_number, FunctionBlockNumber },
+        { name, FunctionBlockName },
+    ] }
+);
+
+impl<'a> FunctionBlockNameNotification<'a> {
+    /// Initializes the packet as a complete (single UMP) Function Block Name
+    /// Notification message with the given function block number and name.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        function_block_number: FunctionBlockNumber,
+        name: FunctionBlockName,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_function_block_number(function_block_number)
+            .set_name(name))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Enumeration
+
+message::impl_enumeration!(
+    /// UMP Stream messages.
+    pub Stream, [
+        EndpointDiscovery,
+        EndpointInfoNotification,
+        DeviceIdentityNotification,
+        EndpointNameNotification,
+        ProductInstanceIdNotification,
+        StreamConfigurationRequest,
+        StreamConfigurationNotification,
+        FunctionBlockDiscovery,
+        FunctionBlockInfoNotification,
+        FunctionBlockNameNotification,
+    ]
+);
+
+impl<'a> Stream<'a> {
+    /// Returns the Format of the message (whether it is complete, or part of a
+    /// sequence of messages carrying a name).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the Format field cannot be read.
+    pub fn format(&self) -> Result<Format, Error> {
+        match self {
+            Self::EndpointDiscovery(message) => message.format(),
+            Self::EndpointInfoNotification(message) => message.format(),
+            Self::DeviceIdentityNotification(message) => message.format(),
+            Self::EndpointNameNotification(message) => message.format(),
+            Self::ProductInstanceIdNotification(message) => message.format(),
+            Self::StreamConfigurationRequest(message) => message.format(),
+            Self::StreamConfigurationNotification(message) => message.format(),
+            Self::FunctionBlockDiscovery(message) => message.format(),
+            Self::FunctionBlockInfoNotification(message) => message.format(),
+            Self::FunctionBlockNameNotification(message) => message.format(),
+        }
+    }
+
+    pub(crate) fn try_new(bits: &'a mut BitSlice<u32, Msb0>) -> Result<Self, Error> {
+        match bits.try_read_field::<Status>()? {
+            Status::EndpointDiscovery => {
+                Ok(Self::EndpointDiscovery(EndpointDiscovery::try_new(bits)?))
+            }
+            Status::EndpointInfoNotification => Ok(Self::EndpointInfoNotification(
+                EndpointInfoNotification::try_new(bits)?,
+            )),
+            Status::DeviceIdentityNotification => Ok(Self::DeviceIdentityNotification(
+                DeviceIdentityNotification::try_new(bits)?,
+            )),
+            Status::EndpointNameNotification => Ok(Self::EndpointNameNotification(
+                EndpointNameNotification::try_new(bits)?,
+            )),
+            Status::ProductInstanceIdNotification => Ok(Self::ProductInstanceIdNotification(
+                ProductInstanceIdNotification::try_new(bits)?,
+            )),
+            Status::StreamConfigurationRequest => Ok(Self::StreamConfigurationRequest(
+                StreamConfigurationRequest::try_new(bits)?,
+            )),
+            Status::StreamConfigurationNotification => Ok(Self::StreamConfigurationNotification(
+                StreamConfigurationNotification::try_new(bits)?,
+            )),
+            Status::FunctionBlockDiscovery => Ok(Self::FunctionBlockDiscovery(
+                FunctionBlockDiscovery::try_new(bits)?,
+            )),
+            Status::FunctionBlockInfoNotification => Ok(Self::FunctionBlockInfoNotification(
+                FunctionBlockInfoNotification::try_new(bits)?,
+            )),
+            Status::FunctionBlockNameNotification => Ok(Self::FunctionBlockNameNotification(
+                FunctionBlockNameNotification::try_new(bits)?,
+            )),
+        }
+    }
+}
+
+impl<'a> StreamView<'a> {
+    pub(crate) fn try_new(bits: &'a BitSlice<u32, Msb0>) -> Result<Self, Error> {
+        match bits.try_read_field::<Status>()? {
+            Status::EndpointDiscovery => Ok(Self::EndpointDiscovery(
+                EndpointDiscoveryView::try_new(bits)?,
+            )),
+            Status::EndpointInfoNotification => Ok(Self::EndpointInfoNotification(
+                EndpointInfoNotificationView::try_new(bits)?,
+            )),
+            Status::DeviceIdentityNotification => Ok(Self::DeviceIdentityNotification(
+                DeviceIdentityNotificationView::try_new(bits)?,
+            )),
+            Status::EndpointNameNotification => Ok(Self::EndpointNameNotification(
+                EndpointNameNotificationView::try_new(bits)?,
+            )),
+            Status::ProductInstanceIdNotification => Ok(Self::ProductInstanceIdNotification(
+                ProductInstanceIdNotificationView::try_new(bits)?,
+            )),
+            Status::StreamConfigurationRequest => Ok(Self::StreamConfigurationRequest(
+                StreamConfigurationRequestView::try_new(bits)?,
+            )),
+            Status::StreamConfigurationNotification => Ok(Self::StreamConfigurationNotification(
+                StreamConfigurationNotificationView::try_new(bits)?,
+            )),
+            Status::FunctionBlockDiscovery => Ok(Self::FunctionBlockDiscovery(
+                FunctionBlockDiscoveryView::try_new(bits)?,
+            )),
+            Status::FunctionBlockInfoNotification => Ok(Self::FunctionBlockInfoNotification(
+                FunctionBlockInfoNotificationView::try_new(bits)?,
+            )),
+            Status::FunctionBlockNameNotification => Ok(Self::FunctionBlockNameNotification(
+                FunctionBlockNameNotificationView::try_new(bits)?,
+            )),
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+// Values are reported in conversion errors as 8-bit values, so invalid 10-bit
+// Status values are saturated.
+
+fn status_conversion(value: u16) -> Error {
+    Error::conversion(u8::try_from(value).unwrap_or(u8::MAX))
+}
+
+// -----------------------------------------------------------------------------
+
+// Macros
+
+// Message
+
+macro_rules! impl_message {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $message:ident { $status:expr, [
+            $({ $name:ident, $type:ty },)*
+        ] }
+    ) => {
+            message::impl_message!(
+                $(#[$meta])*
+                $vis $message { 4, ungrouped, [
+                    { format, Format },
+                    { status, Status },
+                  $({ $name, $type },)*
+                ] }
+            );
+
+            impl<'a> $message<'a> {
+                pub(crate) const STATUS: Status = $status;
+
+                fn try_init_internal(packet: &'a mut [u32]) -> Result<Self, Error> {
+                    Ok(Self::try_from(packet)?
+                        .reset()
+                        .set_message_type(MessageType::Stream)
+                        .set_format(Format::Complete)
+                        .set_status(Self::STATUS))
+                }
+            }
+
+            message::impl_message_builder_init!($message, try_init_internal);
+    };
+}
+
+// -----------------------------------------------------------------------------
+
+// Macro Exports
+
+pub(crate) use impl_message;
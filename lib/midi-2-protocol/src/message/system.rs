@@ -15,6 +15,9 @@
 pub mod common;
 pub mod real_time;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
 use bitvec::{
     field::BitField,
     prelude::Msb0,
@@ -35,6 +38,7 @@ use crate::{
     message,
     packet::{
         GetBitSlice,
+        Packet,
         TryReadField,
     },
     Error,
@@ -93,12 +97,19 @@ field::impl_field_trait_field_traits!(Status, u8, 8..=15);
 /// TODO
 /// # Examples
 /// TODO
-#[derive(Debug)]
+#[cfg_attr(feature = "fmt", derive(Debug))]
 pub enum System<'a> {
     Common(common::Common<'a>),
     RealTime(real_time::RealTime<'a>),
 }
 
+/// Read-only views of [`System`] messages.
+#[cfg_attr(feature = "fmt", derive(Debug))]
+pub enum SystemView<'a> {
+    Common(common::CommonView<'a>),
+    RealTime(real_time::RealTimeView<'a>),
+}
+
 message::impl_enumeration_trait_try_from!(System);
 
 impl<'a> System<'a> {
@@ -116,6 +127,138 @@ impl<'a> System<'a> {
             | Status::Reset => Ok(Self::RealTime(real_time::RealTime::try_new(bits)?)),
         }
     }
+
+    /// Returns the number of 32-bit words in the message.
+    #[must_use]
+    pub fn words(&self) -> usize {
+        match self {
+            Self::Common(message) => message.words(),
+            Self::RealTime(message) => message.words(),
+        }
+    }
+
+    /// Copies the words of the message to the start of the given buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the buffer is shorter than the
+    /// message.
+    pub fn copy_into(&self, packet: &mut [u32]) -> Result<(), Error> {
+        match self {
+            Self::Common(message) => message.copy_into(packet),
+            Self::RealTime(message) => message.copy_into(packet),
+        }
+    }
+
+    /// Returns a copy of the message as an owned [`Packet`].
+    #[must_use]
+    pub fn clone_packet(&self) -> Packet {
+        match self {
+            Self::Common(message) => message.clone_packet(),
+            Self::RealTime(message) => message.clone_packet(),
+        }
+    }
+
+    /// Compares the message against a previous encoding of a message of the
+    /// same type, returning the set of fields which differ.
+    #[must_use]
+    pub fn changed_fields(&self, previous: &[u32]) -> message::FieldChangeSet {
+        match self {
+            Self::Common(message) => message.changed_fields(previous),
+            Self::RealTime(message) => message.changed_fields(previous),
+        }
+    }
+
+    /// Returns the ranges of reserved bits in the message which are not zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the fields of the message cannot
+    /// be read.
+    #[cfg(feature = "alloc")]
+    pub fn reserved_violations(&self) -> Result<Vec<message::ReservedViolation>, Error> {
+        match self {
+            Self::Common(message) => message.reserved_violations(),
+            Self::RealTime(message) => message.reserved_violations(),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    pub(crate) fn reencode_mismatches(
+        &self,
+        offset: usize,
+    ) -> Result<Vec<message::Mismatch>, Error> {
+        match self {
+            Self::Common(message) => message.reencode_mismatches(offset),
+            Self::RealTime(message) => message.reencode_mismatches(offset),
+        }
+    }
+}
+
+impl<'a> SystemView<'a> {
+    pub(crate) fn try_new(bits: &'a BitSlice<u32, Msb0>) -> Result<Self, Error> {
+        match bits.try_read_field::<Status>()? {
+            Status::MIDITimeCode
+            | Status::SongPositionPointer
+            | Status::SongSelect
+            | Status::TuneRequest => Ok(Self::Common(common::CommonView::try_new(bits)?)),
+            Status::TimingClock
+            | Status::Start
+            | Status::Continue
+            | Status::Stop
+            | Status::ActiveSensing
+            | Status::Reset => Ok(Self::RealTime(real_time::RealTimeView::try_new(bits)?)),
+        }
+    }
+
+    /// Returns the number of 32-bit words in the message.
+    #[must_use]
+    pub fn words(&self) -> usize {
+        match self {
+            Self::Common(message) => message.words(),
+            Self::RealTime(message) => message.words(),
+        }
+    }
+
+    /// Copies the words of the message to the start of the given buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the buffer is shorter than the
+    /// message.
+    pub fn copy_into(&self, packet: &mut [u32]) -> Result<(), Error> {
+        match self {
+            Self::Common(message) => message.copy_into(packet),
+            Self::RealTime(message) => message.copy_into(packet),
+        }
+    }
+
+    /// Returns a copy of the message as an owned [`Packet`].
+    #[must_use]
+    pub fn clone_packet(&self) -> Packet {
+        match self {
+            Self::Common(message) => message.clone_packet(),
+            Self::RealTime(message) => message.clone_packet(),
+        }
+    }
+}
+
+impl PartialEq for SystemView<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Common(message), Self::Common(other)) => message == other,
+            (Self::RealTime(message), Self::RealTime(other)) => message == other,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for SystemView<'_> {}
+
+impl<const N: usize> PartialEq<[u32; N]> for SystemView<'_> {
+    fn eq(&self, other: &[u32; N]) -> bool {
+        SystemView::try_from(&other[..]).map_or(false, |other| *self == other)
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -142,7 +285,20 @@ macro_rules! impl_enumeration {
             pub(crate) fn try_new(bits: &'a mut BitSlice<u32, Msb0>) -> Result<Self, Error> {
                 match bits.try_read_field::<Status>()? {
                     $(Status::$message => Ok(Self::$message($message::try_new(bits)?)),)*
-                    _ => unreachable!(),
+                    status => Err(Error::conversion(status.into())),
+                }
+            }
+        }
+
+        ::paste::paste! {
+            impl<'a> [<$enum View>]<'a> {
+                pub(crate) fn try_new(bits: &'a BitSlice<u32, Msb0>) -> Result<Self, Error> {
+                    match bits.try_read_field::<Status>()? {
+                        $(Status::$message => {
+                            Ok(Self::$message([<$message View>]::try_new(bits)?))
+                        })*
+                        status => Err(Error::conversion(status.into())),
+                    }
                 }
             }
         }
@@ -160,9 +316,7 @@ macro_rules! impl_message {
     ) => {
             message::impl_message!(
                 $(#[$meta])*
-                $vis $message { 1, [
-                    { message_type, MessageType },
-                    { group, Group },
+                $vis $message { 1, grouped, [
                     { status, Status },
                   $({ $name, $type },)*
                 ] }
@@ -179,6 +333,8 @@ macro_rules! impl_message {
                         .set_status(Self::STATUS))
                 }
             }
+
+            message::impl_message_builder_init!($message, try_init_internal);
     };
 }
 
@@ -44,7 +44,7 @@ use crate::{
 /// TODO
 /// # Examples
 /// TODO
-#[derive(Debug)]
+#[derive(Debug, Eq, PartialEq)]
 pub struct QuarterFrame(pub Data, pub Type);
 
 impl TryReadFromPacket for QuarterFrame {
@@ -80,7 +80,7 @@ field::impl_field!(
 /// TODO
 /// # Examples
 /// TODO
-#[derive(Debug)]
+#[derive(Debug, Eq, PartialEq)]
 pub enum Type {
     Frames(Significance),
     Seconds(Significance),
@@ -120,7 +120,7 @@ impl TryFrom<u8> for Type {
 }
 
 /// TODO
-#[derive(Debug)]
+#[derive(Debug, Eq, PartialEq)]
 pub enum Significance {
     Least,
     Most,
@@ -135,6 +135,91 @@ impl From<Significance> for u8 {
     }
 }
 
+// Song Position
+
+/// Song Position field type.
+///
+/// The `SongPosition` field type accesses the 14-bit song position of a Song
+/// Position Pointer message **([M2-104-UM 7.6] and [MA01])**, in MIDI beats
+/// (sixteenth notes) since the start of the song, which is sent as two 7-bit
+/// bytes ([`PositionLsb`] and [`PositionMsb`]).
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::message::system::common::*;
+/// #
+/// let position = SongPosition::new(0x1234);
+///
+/// assert_eq!(position, SongPosition(PositionLsb::new(0x34), PositionMsb::new(0x24)));
+/// assert_eq!(u16::from(position), 0x1234);
+/// ```
+#[derive(Debug, Eq, PartialEq)]
+pub struct SongPosition(pub PositionLsb, pub PositionMsb);
+
+impl SongPosition {
+    /// Creates a new [`SongPosition`] from the given 14-bit value.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the value is larger than 14 bits.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn new(value: u16) -> Self {
+        Self(
+            PositionLsb::new((value & 0x7f) as u8),
+            PositionMsb::new((value >> 7) as u8),
+        )
+    }
+}
+
+impl From<SongPosition> for u16 {
+    fn from(value: SongPosition) -> Self {
+        Self::from(u8::from(value.1)) << 7 | Self::from(u8::from(value.0))
+    }
+}
+
+impl TryReadFromPacket for SongPosition {
+    fn try_read_from_packet<P>(packet: &P) -> Result<Self, Error>
+    where
+        Self: Sized,
+        P: GetBitSlice + ?Sized,
+    {
+        Ok(Self(packet.try_read_field()?, packet.try_read_field()?))
+    }
+}
+
+impl WriteToPacket for SongPosition {
+    fn write_to_packet<P>(self, packet: P) -> P
+    where
+        P: GetBitSlice,
+    {
+        packet.write_field(self.0).write_field(self.1)
+    }
+}
+
+// Position LSB
+
+field::impl_field!(
+    /// The least significant 7 bits of a [`SongPosition`].
+    pub PositionLsb { u8, 16..=23, 7 }
+);
+
+// Position MSB
+
+field::impl_field!(
+    /// The most significant 7 bits of a [`SongPosition`].
+    pub PositionMsb { u8, 24..=31, 7 }
+);
+
+// Song
+
+field::impl_field!(
+    /// The 7-bit number of the song or sequence selected by a Song Select
+    /// message **([M2-104-UM 7.6] and [MA01])**.
+    pub Song { u8, 16..=23, 7 }
+);
+
 // -----------------------------------------------------------------------------
 
 // Messages
@@ -161,6 +246,124 @@ impl<'a> MIDITimeCode<'a> {
     }
 }
 
+// Song Position Pointer
+
+system::impl_message!(
+    /// # Song Position Pointer
+    ///
+    /// The Song Position Pointer message **([M2-104-UM 7.6] and [MA01])** is a
+    /// System Common message sent using a 32-bit UMP **([M2-104-UM])**, giving
+    /// the position in the song from which playback should continue.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::system::*;
+    /// # use midi_2_protocol::message::system::common::*;
+    /// #
+    /// let mut packet = SongPositionPointer::packet();
+    /// let mut message = SongPositionPointer::try_init(&mut packet, SongPosition::new(0x1234))?;
+    ///
+    /// assert_eq!(message.message_type()?, MessageType::System);
+    /// assert_eq!(message.group()?, Group::G1);
+    /// assert_eq!(message.status()?, Status::SongPositionPointer);
+    /// assert_eq!(u16::from(message.song_position()?), 0x1234);
+    ///
+    /// assert_eq!(packet, [0x10f23424]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub SongPositionPointer { Status::SongPositionPointer, [
+        { song_position, SongPosition },
+    ]}
+);
+
+impl<'a> SongPositionPointer<'a> {
+    /// Initializes the packet as a Song Position Pointer message with the given
+    /// song position.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(packet: &'a mut [u32], song_position: SongPosition) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?.set_song_position(song_position))
+    }
+}
+
+// Song Select
+
+system::impl_message!(
+    /// # Song Select
+    ///
+    /// The Song Select message **([M2-104-UM 7.6] and [MA01])** is a System
+    /// Common message sent using a 32-bit UMP **([M2-104-UM])**, selecting the
+    /// song or sequence to be played.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::system::*;
+    /// # use midi_2_protocol::message::system::common::*;
+    /// #
+    /// let mut packet = [0x10f30500];
+    ///
+    /// if let Message::System(System::Common(Common::SongSelect(message))) = Message::try_from(&mut packet[..])? {
+    ///     assert_eq!(message.song()?, Song::new(5));
+    /// } else {
+    ///     panic!("Oh No!")
+    /// }
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub SongSelect { Status::SongSelect, [
+        { song, Song },
+    ]}
+);
+
+impl<'a> SongSelect<'a> {
+    /// Initializes the packet as a Song Select message with the given song.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(packet: &'a mut [u32], song: Song) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?.set_song(song))
+    }
+}
+
+// Tune Request
+
+system::impl_message!(
+    /// # Tune Request
+    ///
+    /// The Tune Request message **([M2-104-UM 7.6] and [MA01])** is a System
+    /// Common message sent using a 32-bit UMP **([M2-104-UM])**, requesting
+    /// that analog synthesizers tune their oscillators.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::system::*;
+    /// # use midi_2_protocol::message::system::common::*;
+    /// #
+    /// let mut packet = TuneRequest::packet();
+    /// let mut message = TuneRequest::try_init(&mut packet)?;
+    ///
+    /// assert_eq!(message.status()?, Status::TuneRequest);
+    ///
+    /// assert_eq!(packet, [0x10f60000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub TuneRequest { Status::TuneRequest, [] }
+);
+
+system::impl_message_try_init!(TuneRequest);
+
 // -----------------------------------------------------------------------------
 
 // Enumeration
@@ -171,5 +374,8 @@ system::impl_enumeration!(
     /// TODO
     pub Common, [
         MIDITimeCode,
+        SongPositionPointer,
+        SongSelect,
+        TuneRequest,
     ]
 );
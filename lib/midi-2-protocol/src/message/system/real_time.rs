@@ -229,9 +229,49 @@ system::impl_message_try_init!(Reset);
 // Enumeration
 
 system::impl_enumeration!(
-    /// TODO
+    /// System Real Time messages.
+    ///
     /// # Examples
-    /// TODO
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::system::*;
+    /// # use midi_2_protocol::message::system::real_time::*;
+    /// #
+    /// for (mut packet, status) in [
+    ///     ([0x10f80000], Status::TimingClock),
+    ///     ([0x10fa0000], Status::Start),
+    ///     ([0x10fb0000], Status::Continue),
+    ///     ([0x10fc0000], Status::Stop),
+    ///     ([0x10fe0000], Status::ActiveSensing),
+    ///     ([0x10ff0000], Status::Reset),
+    /// ] {
+    ///     match Message::try_from(&mut packet[..])? {
+    ///         Message::System(System::RealTime(RealTime::TimingClock(message))) => {
+    ///             assert_eq!(message.status()?, status)
+    ///         }
+    ///         Message::System(System::RealTime(RealTime::Start(message))) => {
+    ///             assert_eq!(message.status()?, status)
+    ///         }
+    ///         Message::System(System::RealTime(RealTime::Continue(message))) => {
+    ///             assert_eq!(message.status()?, status)
+    ///         }
+    ///         Message::System(System::RealTime(RealTime::Stop(message))) => {
+    ///             assert_eq!(message.status()?, status)
+    ///         }
+    ///         Message::System(System::RealTime(RealTime::ActiveSensing(message))) => {
+    ///             assert_eq!(message.status()?, status)
+    ///         }
+    ///         Message::System(System::RealTime(RealTime::Reset(message))) => {
+    ///             assert_eq!(message.status()?, status)
+    ///         }
+    ///         _ => panic!("Oh No!"),
+    ///     }
+    /// }
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
     pub RealTime, [
         TimingClock,
         Start,
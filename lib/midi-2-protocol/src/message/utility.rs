@@ -0,0 +1,405 @@
+// =============================================================================
+// Utility
+// =============================================================================
+
+//! Utility message and value types.
+//!
+//! The [`utility`](crate::message::utility) module contains the 32-bit Utility
+//! messages **([M2-104-UM 7.2])**, which carry no musical content, but support
+//! timing -- JR (Jitter Reduction) Clock and Timestamp messages, in ticks of
+//! 1/31250 s, and Delta Clockstamp messages, in ticks at a rate set by the
+//! Delta Clockstamp Ticks Per Quarter Note message.
+//!
+//! Utility messages have no Group field, and apply to the whole endpoint.
+
+use arbitrary_int::UInt;
+use bitvec::{
+    field::BitField,
+    order::Msb0,
+    slice::BitSlice,
+    view::BitView,
+};
+use num_enum::{
+    IntoPrimitive,
+    TryFromPrimitive,
+};
+
+use crate::{
+    field::{
+        self,
+        TryReadFromPacket,
+        WriteToPacket,
+    },
+    message::{
+        self,
+        utility,
+        MessageType,
+    },
+    packet::{
+        GetBitSlice,
+        TryReadField,
+        WriteField,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Fields
+
+// Status
+
+/// Status field type.
+///
+/// The `Status` field type accesses the 4-bit Status field of a Utility
+/// message **([M2-104-UM 7.2])**.
+#[derive(Clone, Copy, Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
+#[num_enum(error_type(name = Error, constructor = Error::conversion))]
+#[repr(u8)]
+pub enum Status {
+    Noop = 0x0,
+    JrClock = 0x1,
+    JrTimestamp = 0x2,
+    DeltaClockstampTicksPerQuarterNote = 0x3,
+    DeltaClockstamp = 0x4,
+}
+
+field::impl_field_trait_field_traits!(Status, u8, 8..=11);
+
+// JR Clock
+
+field::impl_field!(
+    /// The time of the sender's clock when the JR Clock message was sent, in
+    /// ticks of 1/31250 s (wrapping every 65536 ticks).
+    pub SenderClockTime { u16, 16..=31 }
+);
+
+// JR Timestamp
+
+field::impl_field!(
+    /// The time of the sender's clock at which the following message was (or
+    /// should be) sent, in ticks of 1/31250 s (wrapping every 65536 ticks).
+    pub SenderClockTimestamp { u16, 16..=31 }
+);
+
+// Delta Clockstamp Ticks Per Quarter Note
+
+field::impl_field!(
+    /// The number of Delta Clockstamp ticks per quarter note.
+    pub TicksPerQuarterNote { u16, 16..=31 }
+);
+
+// Delta Clockstamp
+
+field::impl_field!(
+    /// The number of Delta Clockstamp ticks since the previous event, as a
+    /// 20-bit value.
+    pub TicksSinceLastEvent { u32, 12..=31, 20 }
+);
+
+// -----------------------------------------------------------------------------
+
+// Messages
+
+// NOOP
+
+utility::impl_message!(
+    /// # NOOP
+    ///
+    /// The NOOP message **([M2-104-UM 7.2])** is a Utility message sent using a
+    /// 32-bit UMP, which carries no data, and should be ignored on receipt.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::utility::*;
+    /// #
+    /// let mut packet = Noop::packet();
+    /// let message = Noop::try_init(&mut packet)?;
+    ///
+    /// assert_eq!(message.message_type()?, MessageType::Utility);
+    /// assert_eq!(message.status()?, Status::Noop);
+    ///
+    /// assert_eq!(packet, [0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub Noop { Status::Noop, [] }
+);
+
+impl<'a> Noop<'a> {
+    /// Initializes the packet as a NOOP message.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(packet: &'a mut [u32]) -> Result<Self, Error> {
+        Self::try_init_internal(packet)
+    }
+}
+
+// JR Clock
+
+utility::impl_message!(
+    /// # JR Clock
+    ///
+    /// The JR Clock message **([M2-104-UM 7.2])** is a Utility message sent
+    /// using a 32-bit UMP, giving the time of the sender's clock, so that the
+    /// receiver can track the sender's clock (e.g. with a
+    /// [`drift::Estimator`](crate::drift::Estimator)).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::utility::*;
+    /// #
+    /// let mut packet = JrClock::packet();
+    /// let message = JrClock::try_init(&mut packet, SenderClockTime::new(0x1234))?;
+    ///
+    /// assert_eq!(message.sender_clock_time()?, SenderClockTime::new(0x1234));
+    ///
+    /// assert_eq!(packet, [0x00101234]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub JrClock { Status::JrClock, [
+        { sender_clock_time, SenderClockTime },
+    ] }
+);
+
+impl<'a> JrClock<'a> {
+    /// Initializes the packet as a JR Clock message with the given sender clock
+    /// time.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        sender_clock_time: SenderClockTime,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?.set_sender_clock_time(sender_clock_time))
+    }
+}
+
+// JR Timestamp
+
+utility::impl_message!(
+    /// # JR Timestamp
+    ///
+    /// The JR Timestamp message **([M2-104-UM 7.2])** is a Utility message sent
+    /// using a 32-bit UMP, preceding another message, and giving the time of
+    /// the sender's clock at which that message was sent.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::utility::*;
+    /// #
+    /// let mut packet = JrTimestamp::packet();
+    /// let _ = JrTimestamp::try_init(&mut packet, SenderClockTimestamp::new(0xabcd))?;
+    ///
+    /// assert_eq!(packet, [0x0020abcd]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub JrTimestamp { Status::JrTimestamp, [
+        { sender_clock_timestamp, SenderClockTimestamp },
+    ] }
+);
+
+impl<'a> JrTimestamp<'a> {
+    /// Initializes the packet as a JR Timestamp message with the given sender
+    /// clock timestamp.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        sender_clock_timestamp: SenderClockTimestamp,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?.set_sender_clock_timestamp(sender_clock_timestamp))
+    }
+}
+
+// Delta Clockstamp Ticks Per Quarter Note
+
+utility::impl_message!(
+    /// # Delta Clockstamp Ticks Per Quarter Note
+    ///
+    /// The Delta Clockstamp Ticks Per Quarter Note message **([M2-104-UM
+    /// 7.2])** is a Utility message sent using a 32-bit UMP, setting the rate
+    /// of the ticks of following Delta Clockstamp messages.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::utility::*;
+    /// #
+    /// let mut packet = DeltaClockstampTicksPerQuarterNote::packet();
+    /// let _ = DeltaClockstampTicksPerQuarterNote::try_init(
+    ///     &mut packet,
+    ///     TicksPerQuarterNote::new(96),
+    /// )?;
+    ///
+    /// assert_eq!(packet, [0x00300060]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub DeltaClockstampTicksPerQuarterNote { Status::DeltaClockstampTicksPerQuarterNote, [
+        { ticks_per_quarter_note, TicksPerQuarterNote },
+    ] }
+);
+
+impl<'a> DeltaClockstampTicksPerQuarterNote<'a> {
+    /// Initializes the packet as a Delta Clockstamp Ticks Per Quarter Note
+    /// message with the given number of ticks per quarter note.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        ticks_per_quarter_note: TicksPerQuarterNote,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?.set_ticks_per_quarter_note(ticks_per_quarter_note))
+    }
+}
+
+// Delta Clockstamp
+
+utility::impl_message!(
+    /// # Delta Clockstamp
+    ///
+    /// The Delta Clockstamp message **([M2-104-UM 7.2])** is a Utility message
+    /// sent using a 32-bit UMP, preceding another message, and giving the time
+    /// of that message as a number of ticks since the previous message.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::utility::*;
+    /// #
+    /// let mut packet = [0x004fffff];
+    ///
+    /// if let Message::Utility(Utility::DeltaClockstamp(message)) = Message::try_from(&mut packet[..])? {
+    ///     assert_eq!(message.ticks_since_last_event()?, TicksSinceLastEvent::new(0xfffff));
+    /// } else {
+    ///     panic!("Oh No!")
+    /// }
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub DeltaClockstamp { Status::DeltaClockstamp, [
+        { ticks_since_last_event, TicksSinceLastEvent },
+    ] }
+);
+
+impl<'a> DeltaClockstamp<'a> {
+    /// Initializes the packet as a Delta Clockstamp message with the given
+    /// number of ticks since the previous message.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(
+        packet: &'a mut [u32],
+        ticks_since_last_event: TicksSinceLastEvent,
+    ) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?.set_ticks_since_last_event(ticks_since_last_event))
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Enumeration
+
+message::impl_enumeration!(
+    /// Utility messages.
+    pub Utility, [
+        Noop,
+        JrClock,
+        JrTimestamp,
+        DeltaClockstampTicksPerQuarterNote,
+        DeltaClockstamp,
+    ]
+);
+
+impl<'a> Utility<'a> {
+    pub(crate) fn try_new(bits: &'a mut BitSlice<u32, Msb0>) -> Result<Self, Error> {
+        match bits.try_read_field::<Status>()? {
+            Status::Noop => Ok(Self::Noop(Noop::try_new(bits)?)),
+            Status::JrClock => Ok(Self::JrClock(JrClock::try_new(bits)?)),
+            Status::JrTimestamp => Ok(Self::JrTimestamp(JrTimestamp::try_new(bits)?)),
+            Status::DeltaClockstampTicksPerQuarterNote => {
+                Ok(Self::DeltaClockstampTicksPerQuarterNote(
+                    DeltaClockstampTicksPerQuarterNote::try_new(bits)?,
+                ))
+            }
+            Status::DeltaClockstamp => Ok(Self::DeltaClockstamp(DeltaClockstamp::try_new(bits)?)),
+        }
+    }
+}
+
+impl<'a> UtilityView<'a> {
+    pub(crate) fn try_new(bits: &'a BitSlice<u32, Msb0>) -> Result<Self, Error> {
+        match bits.try_read_field::<Status>()? {
+            Status::Noop => Ok(Self::Noop(NoopView::try_new(bits)?)),
+            Status::JrClock => Ok(Self::JrClock(JrClockView::try_new(bits)?)),
+            Status::JrTimestamp => Ok(Self::JrTimestamp(JrTimestampView::try_new(bits)?)),
+            Status::DeltaClockstampTicksPerQuarterNote => {
+                Ok(Self::DeltaClockstampTicksPerQuarterNote(
+                    DeltaClockstampTicksPerQuarterNoteView::try_new(bits)?,
+                ))
+            }
+            Status::DeltaClockstamp => {
+                Ok(Self::DeltaClockstamp(DeltaClockstampView::try_new(bits)?))
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Macros
+
+// Message
+
+macro_rules! impl_message {
+    (
+        $(#[$meta:meta])*
+        $vis:vis $message:ident { $status:expr, [
+            $({ $name:ident, $type:ty },)*
+        ] }
+    ) => {
+            message::impl_message!(
+                $(#[$meta])*
+                $vis $message { 1, ungrouped, [
+                    { status, Status },
+                  $({ $name, $type },)*
+                ] }
+            );
+
+            impl<'a> $message<'a> {
+                pub(crate) const STATUS: Status = $status;
+
+                fn try_init_internal(packet: &'a mut [u32]) -> Result<Self, Error> {
+                    Ok(Self::try_from(packet)?
+                        .reset()
+                        .set_message_type(MessageType::Utility)
+                        .set_status(Self::STATUS))
+                }
+            }
+
+            message::impl_message_builder_init!($message, try_init_internal);
+    };
+}
+
+// -----------------------------------------------------------------------------
+
+// Macro Exports
+
+pub(crate) use impl_message;
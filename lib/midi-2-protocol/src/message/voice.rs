@@ -33,9 +33,23 @@ use crate::{
         TryReadField,
         WriteField,
     },
+    scale,
     Error,
 };
 
+// The Channel field is shared with Flex Data messages, and so is defined in the
+// parent module (and re-exported here for convenience).
+
+pub use crate::message::Channel;
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+// The velocity of the Note Off given by the default translation of a MIDI 1.0
+// Note On with a velocity of zero.
+const NOTE_OFF_VELOCITY: u16 = 0x8000;
+
 // -----------------------------------------------------------------------------
 
 // Fields
@@ -76,6 +90,114 @@ pub enum Attribute {
     Manufacturer(Manufacturer),
     Profile(Profile),
     Pitch(Pitch, Fractional),
+    /// An attribute of a reserved type (0x04 to 0xff), given as the attribute
+    /// type and the 16-bit attribute data, which may be decoded as an
+    /// application-defined [`AttributeExtension`].
+    Unknown(u8, u16),
+}
+
+impl Attribute {
+    /// Creates a pitch attribute (a 7.9 fixed-point pitch, as used for
+    /// per-note tuning **([M2-104-UM 7.4.1])**) from a note number and a
+    /// deviation in cents, which may be negative, or greater than a semitone.
+    /// Pitches outside the representable range (note 0 to just below note 128)
+    /// are clamped, and cents are rounded to the nearest 1/512th semitone.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::message::voice::*;
+    /// #
+    /// let attribute = Attribute::pitch_from_note_cents(60, -50.0);
+    ///
+    /// assert_eq!(attribute, Attribute::Pitch(Pitch::new(59), Fractional::new(256)));
+    /// assert_eq!(attribute.note_cents(), Some((59, 50.0)));
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn pitch_from_note_cents(note: u8, cents: f32) -> Self {
+        let semitones = (f32::from(note) + cents / 100.0).clamp(0.0, 127.0 + 511.0 / 512.0);
+        // rounded by hand (f32::round requires std), as the value is positive
+        let pitch = (semitones * 512.0 + 0.5) as u16;
+
+        Self::Pitch(
+            Pitch::new((pitch >> 9) as u8),
+            Fractional::new(pitch & 0x1ff),
+        )
+    }
+
+    /// Creates an attribute of a reserved type from an application-defined
+    /// [`AttributeExtension`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::voice::*;
+    /// #
+    /// // an experimental attribute, giving a stereo pan position
+    /// #[derive(Debug, PartialEq)]
+    /// struct Pan(i16);
+    ///
+    /// impl AttributeExtension for Pan {
+    ///     const ATTRIBUTE_TYPE: u8 = 0x80;
+    ///
+    ///     fn from_data(data: u16) -> Self {
+    ///         Self(data as i16)
+    ///     }
+    ///
+    ///     fn to_data(&self) -> u16 {
+    ///         self.0 as u16
+    ///     }
+    /// }
+    ///
+    /// let mut packet = NoteOn::packet();
+    /// let _ = NoteOn::try_init(&mut packet, Note::new(60), Velocity::new(0xffff))?
+    ///     .set_attribute(Attribute::from_extension(&Pan(-1000)));
+    ///
+    /// let attribute = NoteOn::try_from(&mut packet[..])?.attribute()?;
+    ///
+    /// assert_eq!(attribute, Attribute::Unknown(0x80, 0xfc18));
+    /// assert_eq!(attribute.extension::<Pan>(), Some(Pan(-1000)));
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[must_use]
+    pub fn from_extension<T>(extension: &T) -> Self
+    where
+        T: AttributeExtension,
+    {
+        Self::Unknown(T::ATTRIBUTE_TYPE, extension.to_data())
+    }
+
+    /// Decodes an attribute of a reserved type as the given application-defined
+    /// [`AttributeExtension`], or returns `None` if the attribute is not of
+    /// the extension's attribute type.
+    #[must_use]
+    pub fn extension<T>(&self) -> Option<T>
+    where
+        T: AttributeExtension,
+    {
+        match self {
+            Self::Unknown(attribute_type, data) if *attribute_type == T::ATTRIBUTE_TYPE => {
+                Some(T::from_data(*data))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the note number and deviation in cents (from 0 to just below
+    /// 100) of a pitch attribute, or `None` for other attributes.
+    #[must_use]
+    pub fn note_cents(&self) -> Option<(u8, f32)> {
+        match self {
+            Self::Pitch(pitch, fractional) => Some((
+                u8::from(*pitch),
+                f32::from(u16::from(*fractional)) * 100.0 / 512.0,
+            )),
+            _ => None,
+        }
+    }
 }
 
 impl TryReadFromPacket for Attribute {
@@ -84,14 +206,20 @@ impl TryReadFromPacket for Attribute {
         Self: Sized,
         P: GetBitSlice + ?Sized,
     {
-        match packet.try_read_field::<AttributeType>()? {
-            AttributeType::None => Ok(Attribute::None),
-            AttributeType::Manufacturer => Ok(Attribute::Manufacturer(packet.try_read_field()?)),
-            AttributeType::Profile => Ok(Attribute::Profile(packet.try_read_field()?)),
-            AttributeType::Pitch => Ok(Attribute::Pitch(
+        let code = u8::from(packet.try_read_field::<AttributeCode>()?);
+
+        match AttributeType::try_from(code) {
+            Ok(AttributeType::None) => Ok(Self::None),
+            Ok(AttributeType::Manufacturer) => Ok(Self::Manufacturer(packet.try_read_field()?)),
+            Ok(AttributeType::Profile) => Ok(Self::Profile(packet.try_read_field()?)),
+            Ok(AttributeType::Pitch) => Ok(Self::Pitch(
                 packet.try_read_field()?,
                 packet.try_read_field()?,
             )),
+            Err(_) => Ok(Self::Unknown(
+                code,
+                packet.try_read_field::<AttributeData>()?.into(),
+            )),
         }
     }
 }
@@ -113,10 +241,28 @@ impl WriteToPacket for Attribute {
                 .write_field(AttributeType::Pitch)
                 .write_field(pitch)
                 .write_field(fractional),
+            Self::Unknown(code, data) => packet
+                .write_field(AttributeCode::new(code))
+                .write_field(AttributeData::new(data)),
         }
     }
 }
 
+/// An application-defined attribute type (e.g. for experimental or vendor
+/// extensions).
+///
+/// Extensions are carried in the 16-bit data of an attribute of a reserved
+/// type (see [`Attribute::from_extension`] and [`Attribute::extension`]).
+pub trait AttributeExtension: Sized {
+    /// The attribute type value, which should be in the reserved range (0x04
+    /// to 0xff).
+    const ATTRIBUTE_TYPE: u8;
+
+    fn from_data(data: u16) -> Self;
+
+    fn to_data(&self) -> u16;
+}
+
 #[derive(Debug, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
 #[num_enum(error_type(name = Error, constructor = Error::conversion))]
 #[repr(u8)]
@@ -129,6 +275,10 @@ pub(crate) enum AttributeType {
 
 field::impl_field_trait_field_traits!(AttributeType, u8, 24..=31);
 
+field::impl_field!(pub(crate) AttributeCode { u8, 24..=31 });
+
+field::impl_field!(pub(crate) AttributeData { u16, 48..=63 });
+
 field::impl_field!(
     /// TODO
     /// # Examples
@@ -157,35 +307,45 @@ field::impl_field!(
     pub Fractional { u16, 55..=63, 9 }
 );
 
-// Channel
+// Options
 
-/// TODO
-/// # Examples
-/// TODO
-#[derive(Debug, Default, Eq, IntoPrimitive, PartialEq, TryFromPrimitive)]
-#[num_enum(error_type(name = Error, constructor = Error::conversion))]
-#[repr(u8)]
-pub enum Channel {
-    #[default]
-    C1 = 0x0,
-    C2 = 0x1,
-    C3 = 0x2,
-    C4 = 0x3,
-    C5 = 0x4,
-    C6 = 0x5,
-    C7 = 0x6,
-    C8 = 0x7,
-    C9 = 0x8,
-    C10 = 0x9,
-    C11 = 0xa,
-    C12 = 0xb,
-    C13 = 0xc,
-    C14 = 0xd,
-    C15 = 0xe,
-    C16 = 0xf,
+/// Per-Note Management option flags.
+///
+/// The `Options` field type accesses the Detach (`D`) and Reset (`S`) option
+/// flags of a [`PerNoteManagement`] message **([M2-104-UM 7.4])**.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct Options {
+    pub detach: bool,
+    pub reset: bool,
+}
+
+impl TryReadFromPacket for Options {
+    fn try_read_from_packet<P>(packet: &P) -> Result<Self, Error>
+    where
+        Self: Sized,
+        P: GetBitSlice + ?Sized,
+    {
+        let bit_slice = packet.get_bit_slice();
+
+        Ok(Self {
+            detach: bit_slice[30],
+            reset: bit_slice[31],
+        })
+    }
 }
 
-field::impl_field_trait_field_traits!(Channel, u8, 12..=15);
+impl WriteToPacket for Options {
+    fn write_to_packet<P>(self, mut packet: P) -> P
+    where
+        P: GetBitSlice,
+    {
+        let bit_slice = packet.get_bit_slice_mut();
+
+        bit_slice.set(30, self.detach);
+        bit_slice.set(31, self.reset);
+        packet
+    }
+}
 
 // Other
 
@@ -210,6 +370,89 @@ field::impl_field!(
     pub Data {u32, 32..=63 }
 );
 
+impl Data {
+    /// Creates a [`Data`] value from a 7-bit MIDI 1.0 value (e.g. a Control
+    /// Change value, or Channel or Poly Pressure), using the Min-Center-Max
+    /// upscaling algorithm (so that 0, 64 and 127 map to the minimum, center and
+    /// maximum 32-bit values). Values above 127 are clamped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::message::voice::*;
+    /// #
+    /// assert_eq!(Data::from_midi1(0), Data::new(0));
+    /// assert_eq!(Data::from_midi1(64), Data::new(0x8000_0000));
+    /// assert_eq!(Data::from_midi1(127), Data::new(0xffff_ffff));
+    ///
+    /// assert_eq!(Data::from_midi1(100).to_midi1(), 100);
+    /// ```
+    #[must_use]
+    pub fn from_midi1(value: u8) -> Self {
+        Self(scale::upscale(value.min(0x7f).into(), 7, 32))
+    }
+
+    /// Returns the 7-bit MIDI 1.0 value for this [`Data`] value.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn to_midi1(self) -> u8 {
+        scale::downscale(self.0, 32, 7) as u8
+    }
+
+    /// Creates a [`Data`] value from a 14-bit MIDI 1.0 value (e.g. a Pitch Bend
+    /// value, or RPN and NRPN Data Entry), using the Min-Center-Max upscaling
+    /// algorithm (so that 0, 0x2000 and 0x3fff map to the minimum, center and
+    /// maximum 32-bit values). Values above 0x3fff are clamped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::message::voice::*;
+    /// #
+    /// assert_eq!(Data::from_midi1_14bit(0x2000), Data::new(0x8000_0000));
+    /// assert_eq!(Data::from_midi1_14bit(0x3fff), Data::new(0xffff_ffff));
+    ///
+    /// assert_eq!(Data::from_midi1_14bit(0x1234).to_midi1_14bit(), 0x1234);
+    /// ```
+    #[must_use]
+    pub fn from_midi1_14bit(value: u16) -> Self {
+        Self(scale::upscale(value.min(0x3fff).into(), 14, 32))
+    }
+
+    /// Returns the 14-bit MIDI 1.0 value for this [`Data`] value.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn to_midi1_14bit(self) -> u16 {
+        scale::downscale(self.0, 32, 14) as u16
+    }
+}
+
+impl From<Velocity> for Data {
+    /// Scales a 16-bit [`Velocity`] up to a 32-bit [`Data`] value (e.g. when
+    /// mapping note velocity to a controller), using the Min-Center-Max
+    /// upscaling algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::message::voice::*;
+    /// #
+    /// assert_eq!(Data::from(Velocity::new(0x8000)), Data::new(0x8000_0000));
+    /// assert_eq!(Data::from(Velocity::MAX), Data::new(0xffff_ffff));
+    ///
+    /// assert_eq!(Velocity::from(Data::from(Velocity::new(0x1234))), Velocity::new(0x1234));
+    /// ```
+    fn from(velocity: Velocity) -> Self {
+        Self(scale::upscale(velocity.0.into(), 16, 32))
+    }
+}
+
+field::impl_field!(
+    /// Control Change index field type (the 7-bit controller number of a
+    /// [`ControlChange`] message).
+    pub Index { u8, 16..=23, 7 }
+);
+
 field::impl_field!(
     /// TODO
     /// # Examples
@@ -231,6 +474,51 @@ field::impl_field!(
     pub Velocity { u16, 32..=47 }
 );
 
+impl Velocity {
+    pub const MIN: Self = Self(0);
+    pub const MAX: Self = Self(u16::MAX);
+
+    /// Creates a [`Velocity`] from a 7-bit MIDI 1.0 velocity, using the
+    /// Min-Center-Max upscaling algorithm defined for translation between MIDI
+    /// 1.0 and MIDI 2.0 (so that 0, 64 and 127 map to the minimum, center and
+    /// maximum 16-bit values). Values above 127 are clamped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::message::voice::*;
+    /// #
+    /// assert_eq!(Velocity::from_midi1(0), Velocity::MIN);
+    /// assert_eq!(Velocity::from_midi1(64), Velocity::new(0x8000));
+    /// assert_eq!(Velocity::from_midi1(127), Velocity::MAX);
+    ///
+    /// assert_eq!(Velocity::from_midi1(100).to_midi1(), 100);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn from_midi1(velocity: u8) -> Self {
+        Self(scale::upscale(velocity.min(0x7f).into(), 7, 16) as u16)
+    }
+
+    /// Returns the 7-bit MIDI 1.0 velocity for this [`Velocity`]. Note that a
+    /// MIDI 1.0 Note On message with a velocity of 0 is a Note Off, so a Note On
+    /// velocity which converts to 0 should be sent as 1.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn to_midi1(self) -> u8 {
+        scale::downscale(self.0 as u32, 16, 7) as u8
+    }
+}
+
+impl From<Data> for Velocity {
+    /// Scales a 32-bit [`Data`] value down to a 16-bit [`Velocity`] (e.g. when
+    /// mapping a controller to note velocity).
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(data: Data) -> Self {
+        Self(scale::downscale(data.0, 32, 16) as u16)
+    }
+}
+
 // -----------------------------------------------------------------------------
 
 // Messages
@@ -470,6 +758,166 @@ impl<'a> NoteOn<'a> {
             .set_note(note)
             .set_velocity(velocity))
     }
+
+    /// Returns `true` if the Note On has a velocity of zero, and so has the
+    /// meaning of a Note Off in MIDI 1.0.
+    ///
+    /// In MIDI 2.0, a velocity of zero does not function as a Note Off
+    /// **([M2-104-UM 7.4.2])**, and such a Note On should be played as a Note
+    /// On. However, Note On messages with a velocity of zero may be received
+    /// from senders which translate MIDI 1.0 without the default translation,
+    /// and receivers which expect such senders may normalize them with
+    /// [`try_normalize`](NoteOn::try_normalize).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the velocity of the message cannot be read.
+    pub fn is_effective_note_off(&self) -> Result<bool, Error> {
+        Ok(self.velocity()? == Velocity::MIN)
+    }
+
+    /// Normalizes a Note On with the meaning of a Note Off in MIDI 1.0 (see
+    /// [`is_effective_note_off`](NoteOn::is_effective_note_off)), rewriting
+    /// the packet as the Note Off given by the default translation of MIDI 1.0
+    /// (with the same group, channel and note, a velocity of 0x8000, and no
+    /// attribute). Any other Note On is returned unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the velocity of the message cannot be read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::voice::*;
+    /// #
+    /// let mut packet = NoteOn::packet();
+    /// let note_on = NoteOn::try_init(&mut packet, Note::new(60), Velocity::MIN)?;
+    ///
+    /// assert!(note_on.is_effective_note_off()?);
+    ///
+    /// match note_on.try_normalize()? {
+    ///     Voice::NoteOff(note_off) => assert_eq!(note_off.velocity()?, Velocity::new(0x8000)),
+    ///     _ => unreachable!(),
+    /// }
+    ///
+    /// assert_eq!(packet, [0x40803c00, 0x80000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn try_normalize(self) -> Result<Voice<'a>, Error> {
+        if !self.is_effective_note_off()? {
+            return Ok(Voice::NoteOn(self));
+        }
+
+        Ok(Voice::NoteOff(
+            NoteOff { bits: self.bits }
+                .set_opcode(NoteOff::OPCODE)
+                .set_velocity(Velocity::new(NOTE_OFF_VELOCITY))
+                .set_attribute(Attribute::None)
+                .write_field(AttributeData::new(0)),
+        ))
+    }
+}
+
+// Control Change
+
+voice::impl_message!(
+    /// # Control Change
+    ///
+    /// The Control Change message **([M2-104-UM 7.4])** is a MIDI 2.0 Channel
+    /// Voice message sent using a 64-bit UMP, carrying a 32-bit value for one
+    /// of the 128 MIDI 1.0 style controller indices.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::voice::*;
+    /// #
+    /// let mut packet = ControlChange::packet();
+    /// let message = ControlChange::try_init(&mut packet, Index::new(74))?
+    ///     .set_channel(Channel::C2)
+    ///     .set_data(Data::new(0x80000000));
+    ///
+    /// assert_eq!(message.opcode()?, Opcode::ControlChange);
+    /// assert_eq!(message.index()?, Index::new(74));
+    ///
+    /// assert_eq!(packet, [0x40b14a00, 0x80000000]);
+    ///
+    /// // fields may also be set from raw integral values, which are validated
+    /// let message = ControlChange::try_from(&mut packet[..])?
+    ///     .try_set_channel(3)?
+    ///     .try_set_index(7)?;
+    ///
+    /// assert!(matches!(message.try_set_index(128), Err(Error::Overflow(128, 7))));
+    /// assert_eq!(packet, [0x40b30700, 0x80000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub ControlChange { Opcode::ControlChange, [
+        { index, Index },
+        { data, Data },
+    ] }
+);
+
+impl<'a> ControlChange<'a> {
+    /// Initializes the packet as a Control Change message for the given
+    /// controller index (with a data value of zero).
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(packet: &'a mut [u32], index: Index) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?.set_index(index))
+    }
+}
+
+// Per-Note Management
+
+voice::impl_message!(
+    /// # Per-Note Management
+    ///
+    /// The Per-Note Management message **([M2-104-UM 7.4])** is a MIDI 2.0
+    /// Channel Voice message sent using a 64-bit UMP, used to detach and/or
+    /// reset the per-note controllers of a note.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::message::voice::*;
+    /// #
+    /// let mut packet = PerNoteManagement::packet();
+    /// let options = Options { detach: true, reset: true };
+    /// let message = PerNoteManagement::try_init(&mut packet, Note::new(60), options)?;
+    ///
+    /// assert_eq!(message.opcode()?, Opcode::PerNoteManagement);
+    /// assert_eq!(message.options()?, Options { detach: true, reset: true });
+    ///
+    /// assert_eq!(packet, [0x40f03c03, 0x00000000]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub PerNoteManagement { Opcode::PerNoteManagement, [
+        { note, Note },
+        { options, Options },
+    ] }
+);
+
+impl<'a> PerNoteManagement<'a> {
+    /// Initializes the packet as a Per-Note Management message for the given
+    /// note and option flags.
+    /// # Errors
+    /// Returns an [`Error`](crate::Error) if the packet is not of the correct
+    /// size for the message type.
+    pub fn try_init(packet: &'a mut [u32], note: Note, options: Options) -> Result<Self, Error> {
+        Ok(Self::try_init_internal(packet)?
+            .set_note(note)
+            .set_options(options))
+    }
 }
 
 // -----------------------------------------------------------------------------
@@ -491,16 +939,43 @@ voice::impl_enumeration!(
         NoteOff,
         NoteOn,
         // PolyPressure,
-        // ControlChange,
+        ControlChange,
         // ProgramChange,
         // ChannelPressure,
         // PitchBend,
-        // PerNoteManagement,
+        PerNoteManagement,
     ]
 );
 
 // -----------------------------------------------------------------------------
 
+// Functions
+
+// Returns whether the word is the first word of a Channel Voice message which
+// carries a note number (in bits 16..=23), so that word-level processors can
+// rewrite note numbers without parsing each message.
+
+#[cfg(any(feature = "alloc", feature = "flex-data"))]
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn is_per_note(word: u32) -> bool {
+    if word >> 28 != u32::from(u8::from(MessageType::Voice)) {
+        return false;
+    }
+
+    matches!(
+        Opcode::try_from((word >> 20 & 0xf) as u8),
+        Ok(Opcode::RegisteredPerNoteController
+            | Opcode::AssignablePerNoteController
+            | Opcode::PerNotePitchBend
+            | Opcode::NoteOff
+            | Opcode::NoteOn
+            | Opcode::PolyPressure
+            | Opcode::PerNoteManagement)
+    )
+}
+
+// -----------------------------------------------------------------------------
+
 // Macros
 
 // Enumeration
@@ -523,7 +998,20 @@ macro_rules! impl_enumeration {
             pub(crate) fn try_new(bits: &'a mut BitSlice<u32, Msb0>) -> Result<Self, Error> {
                 match bits.try_read_field::<Opcode>()? {
                     $(Opcode::$message => Ok(Self::$message($message::try_new(bits)?)),)*
-                    _ => unreachable!()
+                    opcode => Err(Error::conversion(opcode.into())),
+                }
+            }
+        }
+
+        ::paste::paste! {
+            impl<'a> [<$enum View>]<'a> {
+                pub(crate) fn try_new(bits: &'a BitSlice<u32, Msb0>) -> Result<Self, Error> {
+                    match bits.try_read_field::<Opcode>()? {
+                        $(Opcode::$message => {
+                            Ok(Self::$message([<$message View>]::try_new(bits)?))
+                        })*
+                        opcode => Err(Error::conversion(opcode.into())),
+                    }
                 }
             }
         }
@@ -541,9 +1029,7 @@ macro_rules! impl_message {
     ) => {
             message::impl_message!(
                 $(#[$meta])*
-                $vis $message { 2, [
-                    { message_type, MessageType },
-                    { group, Group },
+                $vis $message { 2, grouped, [
                     { opcode, Opcode },
                     { channel, Channel },
                   $({ $name, $type },)*
@@ -562,6 +1048,8 @@ macro_rules! impl_message {
                         .set_channel(Channel::default()))
                 }
             }
+
+            message::impl_message_builder_init!($message, try_init_internal);
     };
 }
 
@@ -0,0 +1,280 @@
+// =============================================================================
+// Metronome
+// =============================================================================
+
+//! Metronome click generation.
+//!
+//! The [`metronome`](crate::metronome) module converts the click configuration
+//! of a [`SetMetronome`] message into a timed sequence of clicks, or into a
+//! click track of Note On/Note Off packets on a chosen group and channel, for
+//! simple play-along features.
+//!
+//! Times are given in nanoseconds from the start of the first bar, for a tempo
+//! given (as in the Set Tempo message) in units of 10 nanoseconds per quarter
+//! note.
+
+use alloc::vec::Vec;
+
+use crate::{
+    message::{
+        flex_data::SetMetronome,
+        voice::{
+            Channel,
+            Note,
+            NoteOff,
+            NoteOn,
+            Velocity,
+        },
+        Group,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+/// The default tempo (120 beats per minute), in units of 10 nanoseconds per
+/// quarter note.
+pub const DEFAULT_TEMPO: u32 = 50_000_000;
+
+const CLOCKS_PER_QUARTER_NOTE: u64 = 24;
+
+// -----------------------------------------------------------------------------
+
+// Click
+
+/// The kind of a metronome click.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Click {
+    /// The first primary click of a part of the bar.
+    Accent,
+    /// Any other primary click.
+    Primary,
+    /// A click of the first subdivision.
+    Subdivision1,
+    /// A click of the second subdivision.
+    Subdivision2,
+}
+
+// -----------------------------------------------------------------------------
+
+// Voicing
+
+/// The notes used to sound each kind of click in a click track.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Voicing {
+    pub group: Group,
+    pub channel: Channel,
+    pub accent: Note,
+    pub primary: Note,
+    pub subdivision: [Note; 2],
+    pub velocity: Velocity,
+}
+
+impl Voicing {
+    const fn note(self, click: Click) -> Note {
+        match click {
+            Click::Accent => self.accent,
+            Click::Primary => self.primary,
+            Click::Subdivision1 => self.subdivision[0],
+            Click::Subdivision2 => self.subdivision[1],
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Metronome
+
+/// A metronome configuration, as carried by a [`SetMetronome`] message.
+///
+/// The bar is divided into up to three parts, each given as a number of primary
+/// clicks, with the first click of each part accented. When no parts are given,
+/// there are no accents, and a bar is a single primary click. Each subdivision
+/// places the given number of clicks evenly between primary clicks.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::metronome::*;
+/// # use midi_2_protocol::message::flex_data::*;
+/// #
+/// let mut packet = SetMetronome::packet();
+/// let message = SetMetronome::try_init(&mut packet, ClocksPerPrimaryClick::new(24))?
+///     .set_bar_accent_1(BarAccent1::new(2))
+///     .set_subdivision_clicks_1(SubdivisionClicks1::new(1));
+///
+/// let metronome = Metronome::try_from_message(&message)?;
+///
+/// // a bar of two quarter notes at 120 bpm, with eighth note subdivisions
+/// assert_eq!(
+///     metronome.clicks(DEFAULT_TEMPO, 1),
+///     [
+///         (0, Click::Accent),
+///         (250_000_000, Click::Subdivision1),
+///         (500_000_000, Click::Primary),
+///         (750_000_000, Click::Subdivision1),
+///     ]
+/// );
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Metronome {
+    pub clocks_per_primary_click: u8,
+    pub bar_accents: [u8; 3],
+    pub subdivision_clicks: [u8; 2],
+}
+
+impl Metronome {
+    /// Reads the metronome configuration from a [`SetMetronome`] message.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the fields of the message cannot
+    /// be read.
+    pub fn try_from_message(message: &SetMetronome<'_>) -> Result<Self, Error> {
+        Ok(Self {
+            clocks_per_primary_click: message.clocks_per_primary_click()?.into(),
+            bar_accents: [
+                message.bar_accent_1()?.into(),
+                message.bar_accent_2()?.into(),
+                message.bar_accent_3()?.into(),
+            ],
+            subdivision_clicks: [
+                message.subdivision_clicks_1()?.into(),
+                message.subdivision_clicks_2()?.into(),
+            ],
+        })
+    }
+
+    /// Returns the clicks for the given number of bars at the given tempo (in
+    /// units of 10 nanoseconds per quarter note), as times (in nanoseconds)
+    /// and kinds of click, in time order.
+    #[must_use]
+    pub fn clicks(&self, tempo: u32, bars: u32) -> Vec<(u64, Click)> {
+        let mut clicks = Vec::new();
+
+        if self.clocks_per_primary_click == 0 {
+            return clicks;
+        }
+
+        let interval = u64::from(self.clocks_per_primary_click);
+        let [part_1, part_2, part_3] = self.bar_accents.map(u64::from);
+        let accents = [(0, part_1), (part_1, part_2), (part_1 + part_2, part_3)];
+        let per_bar = self.primary_clicks_per_bar();
+
+        for primary in 0..per_bar * u64::from(bars) {
+            let position = primary % per_bar;
+            let click = if accents
+                .iter()
+                .any(|&(start, length)| start == position && length > 0)
+            {
+                Click::Accent
+            } else {
+                Click::Primary
+            };
+
+            clicks.push((time(tempo, primary * interval, 1), click));
+
+            for (subdivisions, click) in [
+                (self.subdivision_clicks[0], Click::Subdivision1),
+                (self.subdivision_clicks[1], Click::Subdivision2),
+            ] {
+                let divisions = u64::from(subdivisions) + 1;
+
+                for subdivision in 1..divisions {
+                    let position = (primary * divisions + subdivision) * interval;
+
+                    clicks.push((time(tempo, position, divisions), click));
+                }
+            }
+        }
+
+        clicks.sort_by_key(|(time, _)| *time);
+        clicks
+    }
+
+    /// Returns a click track for the given number of bars at the given tempo
+    /// (in units of 10 nanoseconds per quarter note), as times (in nanoseconds)
+    /// and Note On/Note Off packets, in time order. Each click note is ended
+    /// when the next click sounds (or at the end of the final bar).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if a message cannot be initialized.
+    pub fn try_notes(
+        &self,
+        tempo: u32,
+        bars: u32,
+        voicing: &Voicing,
+    ) -> Result<Vec<(u64, [u32; 2])>, Error> {
+        let clicks = self.clicks(tempo, bars);
+        let length = self.primary_clicks_per_bar()
+            * u64::from(bars)
+            * u64::from(self.clocks_per_primary_click);
+        let end = time(tempo, length, 1);
+
+        let mut notes = Vec::with_capacity(clicks.len() * 2);
+
+        for (i, &(time, click)) in clicks.iter().enumerate() {
+            let note = voicing.note(click);
+            let off = clicks[i + 1..]
+                .iter()
+                .map(|(time, _)| *time)
+                .find(|next| *next > time)
+                .unwrap_or(end);
+
+            let mut packet = NoteOn::packet();
+            let _ = NoteOn::try_init(&mut packet, note, voicing.velocity)?
+                .set_group(voicing.group)
+                .set_channel(voicing.channel);
+
+            notes.push((time, 1, packet));
+
+            let mut packet = NoteOff::packet();
+            let _ = NoteOff::try_init(&mut packet, note, Velocity::new(0))?
+                .set_group(voicing.group)
+                .set_channel(voicing.channel);
+
+            notes.push((off, 0, packet));
+        }
+
+        // Note Off packets are ordered before Note On packets at the same time,
+        // so that repeated notes are not cut short.
+
+        notes.sort_by_key(|(time, order, _)| (*time, *order));
+
+        Ok(notes
+            .into_iter()
+            .map(|(time, _, packet)| (time, packet))
+            .collect())
+    }
+
+    fn primary_clicks_per_bar(self) -> u64 {
+        self.bar_accents
+            .iter()
+            .copied()
+            .map(u64::from)
+            .sum::<u64>()
+            .max(1)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+// Returns the time (in nanoseconds) of a position given in fractions (of the
+// given number of divisions) of a MIDI clock, at the given tempo. The time is
+// calculated in 128 bits, as long positions at slow tempos overflow 64 bits,
+// and saturates at the largest time representable.
+
+fn time(tempo: u32, clocks: u64, divisions: u64) -> u64 {
+    let time = u128::from(clocks) * u128::from(tempo) * 10
+        / u128::from(CLOCKS_PER_QUARTER_NOTE * divisions);
+
+    u64::try_from(time).unwrap_or(u64::MAX)
+}
@@ -0,0 +1,299 @@
+// =============================================================================
+// Mono
+// =============================================================================
+
+//! Conversion of polyphonic input to monophonic output.
+//!
+//! The [`mono`](crate::mono) module provides a [`MonoConverter`], which
+//! reduces the notes of each group and channel to a single sounding note,
+//! chosen by a note [`Priority`] from the notes currently held, for driving
+//! monophonic synths from polyphonic controllers.
+
+use alloc::vec::Vec;
+
+use crate::{
+    message::voice,
+    packet,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const NOTE_OFF: u32 = 0x8;
+const NOTE_ON: u32 = 0x9;
+const PER_NOTE_PITCH_BEND: u32 = 0x6;
+
+const NOTE_OFF_VELOCITY: u32 = 0x8000_0000;
+
+// -----------------------------------------------------------------------------
+
+// Priority
+
+/// The choice of sounding note when several notes are held.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Priority {
+    /// The most recently pressed note sounds.
+    #[default]
+    Last,
+    /// The highest held note sounds.
+    High,
+    /// The lowest held note sounds.
+    Low,
+}
+
+// -----------------------------------------------------------------------------
+
+// Mono Converter
+
+/// Converts polyphonic MIDI 2.0 Channel Voice input to monophonic output.
+///
+/// For each group and channel, the converter tracks the held notes, and sends
+/// a Note On only for the note chosen by the [`Priority`]. When another note
+/// is chosen (because a note is pressed or released), the sounding note is
+/// switched -- with legato enabled, the new Note On is sent before the Note
+/// Off of the previous note, so that synths in legato mode glide between the
+/// notes without retriggering their envelopes, and otherwise the Note Off is
+/// sent first.
+///
+/// Per-note messages (Poly Pressure, Per-Note Controllers, Per-Note Pitch Bend
+/// and Per-Note Management) are passed only for the sounding note. The last
+/// Per-Note Pitch Bend of each held note is kept, and sent again before the
+/// Note On when the note becomes the sounding note, so that the note sounds at
+/// the same pitch as if it had been sounding throughout. All other messages
+/// are passed unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::mono::*;
+/// #
+/// let mut converter = MonoConverter::new(Priority::Last, true);
+/// let mut out = Vec::new();
+///
+/// // note on 60, note on 64, note off 64, note off 60
+/// converter.process(
+///     &[
+///         0x40903c00, 0xffff0000, 0x40904000, 0xffff0000,
+///         0x40804000, 0x00000000, 0x40803c00, 0x00000000,
+///     ],
+///     &mut out,
+/// );
+///
+/// assert_eq!(
+///     out,
+///     [
+///         0x40903c00, 0xffff0000, // note on 60
+///         0x40904000, 0xffff0000, // note on 64 (legato)...
+///         0x40803c00, 0x80000000, // ...then note off 60
+///         0x40903c00, 0xffff0000, // note on 60 (legato)...
+///         0x40804000, 0x00000000, // ...then note off 64
+///         0x40803c00, 0x00000000, // note off 60
+///     ]
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MonoConverter {
+    priority: Priority,
+    legato: bool,
+    voices: Vec<Voice>,
+}
+
+impl MonoConverter {
+    /// Creates a new [`MonoConverter`] with the given note priority and legato
+    /// behaviour, and no held notes.
+    #[must_use]
+    pub const fn new(priority: Priority, legato: bool) -> Self {
+        Self {
+            priority,
+            legato,
+            voices: Vec::new(),
+        }
+    }
+
+    /// Processes each message in the source buffer, appending the resulting
+    /// messages to the output buffer. A trailing incomplete message in the
+    /// source buffer is not processed.
+    pub fn process(&mut self, src: &[u32], out: &mut Vec<u32>) {
+        let mut rest = src;
+
+        while let Some(&word) = rest.first() {
+            let len = packet::word_count(word);
+
+            if len > rest.len() {
+                break;
+            }
+
+            let (message, next) = rest.split_at(len);
+
+            if voice::is_per_note(word) {
+                self.process_per_note([message[0], message[1]], out);
+            } else {
+                out.extend_from_slice(message);
+            }
+
+            rest = next;
+        }
+    }
+
+    /// Forgets all held notes (without sending any messages).
+    pub fn reset(&mut self) {
+        self.voices.clear();
+    }
+
+    fn process_per_note(&mut self, message: [u32; 2], out: &mut Vec<u32>) {
+        let key = message[0] & 0x0f0f_0000;
+        let note = note(message[0]);
+        let index = self
+            .voices
+            .iter()
+            .position(|voice| voice.key == key)
+            .unwrap_or_else(|| {
+                self.voices.push(Voice {
+                    key,
+                    held: Vec::new(),
+                    sounding: None,
+                });
+                self.voices.len() - 1
+            });
+
+        let (priority, legato) = (self.priority, self.legato);
+        let voice = &mut self.voices[index];
+
+        match message[0] >> 20 & 0xf {
+            NOTE_ON => voice.note_on(message, priority, legato, out),
+            NOTE_OFF => voice.note_off(message, priority, legato, out),
+            opcode => {
+                if voice.sounding == Some(note) {
+                    out.extend_from_slice(&message);
+                } else if opcode == PER_NOTE_PITCH_BEND {
+                    if let Some(held) = voice.held.iter_mut().find(|held| held.note == note) {
+                        held.bend = Some(message[1]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Voice
+
+#[derive(Clone, Debug)]
+struct Voice {
+    key: u32,
+    held: Vec<Held>,
+    sounding: Option<u8>,
+}
+
+impl Voice {
+    fn note_on(&mut self, message: [u32; 2], priority: Priority, legato: bool, out: &mut Vec<u32>) {
+        let note = note(message[0]);
+
+        self.held.retain(|held| held.note != note);
+        self.held.push(Held {
+            note,
+            note_on: message,
+            bend: None,
+        });
+
+        if self.select(priority) != Some(note) {
+            return;
+        }
+
+        match self.sounding {
+            Some(sounding) if sounding != note => {
+                let note_off = self.note_off_for(sounding);
+
+                self.switch(note, note_off, legato, out);
+            }
+            _ => {
+                out.extend_from_slice(&message);
+                self.sounding = Some(note);
+            }
+        }
+    }
+
+    fn note_off(
+        &mut self,
+        message: [u32; 2],
+        priority: Priority,
+        legato: bool,
+        out: &mut Vec<u32>,
+    ) {
+        let note = note(message[0]);
+
+        if !self.held.iter().any(|held| held.note == note) {
+            out.extend_from_slice(&message);
+            return;
+        }
+
+        self.held.retain(|held| held.note != note);
+
+        if self.sounding != Some(note) {
+            return;
+        }
+
+        if let Some(next) = self.select(priority) {
+            self.switch(next, message, legato, out);
+        } else {
+            out.extend_from_slice(&message);
+            self.sounding = None;
+        }
+    }
+
+    fn switch(&mut self, note: u8, note_off: [u32; 2], legato: bool, out: &mut Vec<u32>) {
+        if !legato {
+            out.extend_from_slice(&note_off);
+        }
+
+        if let Some(held) = self.held.iter().find(|held| held.note == note) {
+            if let Some(bend) = held.bend {
+                let word = held.note_on[0] & 0xff0f_ff00 | PER_NOTE_PITCH_BEND << 20;
+
+                out.extend_from_slice(&[word, bend]);
+            }
+
+            out.extend_from_slice(&held.note_on);
+        }
+
+        if legato {
+            out.extend_from_slice(&note_off);
+        }
+
+        self.sounding = Some(note);
+    }
+
+    fn note_off_for(&self, note: u8) -> [u32; 2] {
+        let word = self.key | 0x4000_0000 | NOTE_OFF << 20 | u32::from(note) << 8;
+
+        [word, NOTE_OFF_VELOCITY]
+    }
+
+    fn select(&self, priority: Priority) -> Option<u8> {
+        let notes = self.held.iter().map(|held| held.note);
+
+        match priority {
+            Priority::Last => self.held.last().map(|held| held.note),
+            Priority::High => notes.max(),
+            Priority::Low => notes.min(),
+        }
+    }
+}
+
+// Held
+
+#[derive(Clone, Copy, Debug)]
+struct Held {
+    note: u8,
+    note_on: [u32; 2],
+    bend: Option<u32>,
+}
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+#[allow(clippy::cast_possible_truncation)]
+const fn note(word: u32) -> u8 {
+    (word >> 8 & 0x7f) as u8
+}
@@ -0,0 +1,319 @@
+// =============================================================================
+// Notation
+// =============================================================================
+
+//! Key signatures and note spelling.
+//!
+//! The [`notation`](crate::notation) module provides a [`KeySignature`] type,
+//! read from a [`SetKeySignature`] message, with helpers to suggest spellings
+//! (letter names and accidentals) for notes, so that notation-oriented users
+//! can choose between enharmonic equivalents (e.g. F♯ or G♭) in context.
+
+#[cfg(feature = "fmt")]
+use core::fmt;
+
+use crate::{
+    message::{
+        flex_data::{
+            SetKeySignature,
+            Tonic,
+        },
+        voice::Note,
+    },
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const NON_STANDARD: u8 = 0x8;
+
+const SHARPS: [Letter; 7] = [
+    Letter::F,
+    Letter::C,
+    Letter::G,
+    Letter::D,
+    Letter::A,
+    Letter::E,
+    Letter::B,
+];
+
+const LETTERS: [Letter; 7] = [
+    Letter::C,
+    Letter::D,
+    Letter::E,
+    Letter::F,
+    Letter::G,
+    Letter::A,
+    Letter::B,
+];
+
+// -----------------------------------------------------------------------------
+
+// Values
+
+// Letter
+
+/// The letter name of a note.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Letter {
+    C,
+    D,
+    E,
+    F,
+    G,
+    A,
+    B,
+}
+
+impl Letter {
+    const fn pitch_class(self) -> i8 {
+        match self {
+            Self::C => 0,
+            Self::D => 2,
+            Self::E => 4,
+            Self::F => 5,
+            Self::G => 7,
+            Self::A => 9,
+            Self::B => 11,
+        }
+    }
+
+    const fn from_tonic(tonic: Tonic) -> Option<Self> {
+        match tonic {
+            Tonic::Unknown => None,
+            Tonic::A => Some(Self::A),
+            Tonic::B => Some(Self::B),
+            Tonic::C => Some(Self::C),
+            Tonic::D => Some(Self::D),
+            Tonic::E => Some(Self::E),
+            Tonic::F => Some(Self::F),
+            Tonic::G => Some(Self::G),
+        }
+    }
+}
+
+// Accidental
+
+/// The accidental of a spelled note.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Accidental {
+    Flat,
+    Natural,
+    Sharp,
+}
+
+impl Accidental {
+    const fn offset(self) -> i8 {
+        match self {
+            Self::Flat => -1,
+            Self::Natural => 0,
+            Self::Sharp => 1,
+        }
+    }
+}
+
+// Mode
+
+/// The mode of a key, as implied by the tonic note and key signature.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+    Major,
+    Minor,
+}
+
+// Spelling
+
+/// A suggested spelling of a note, as a letter name and accidental.
+///
+/// Spellings are formatted using `#` and `b` for sharps and flats, with no
+/// symbol for naturals (e.g. `F#`, `Bb`, `E`).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Spelling {
+    pub letter: Letter,
+    pub accidental: Accidental,
+}
+
+#[cfg(feature = "fmt")]
+impl fmt::Display for Spelling {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let accidental = match self.accidental {
+            Accidental::Flat => "b",
+            Accidental::Natural => "",
+            Accidental::Sharp => "#",
+        };
+
+        write!(f, "{:?}{accidental}", self.letter)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Key Signature
+
+/// A key signature, given as a number of sharps (positive) or flats (negative),
+/// and an optional tonic note letter.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::message::flex_data::*;
+/// # use midi_2_protocol::message::voice::*;
+/// # use midi_2_protocol::notation::*;
+/// #
+/// let mut packet = SetKeySignature::packet();
+/// let message = SetKeySignature::try_init(&mut packet, SharpsFlats::new(0xe), Tonic::G)?;
+///
+/// let key = KeySignature::try_from_message(&message)?.unwrap();
+///
+/// assert_eq!(key.accidentals(), -2);
+/// assert_eq!(key.mode(), Some(Mode::Minor));
+///
+/// // G minor: B and E are flattened, and chromatic notes prefer flats
+/// assert_eq!(key.spell(Note::new(70)).to_string(), "Bb");
+/// assert_eq!(key.spell(Note::new(61)).to_string(), "Db");
+/// assert_eq!(key.spell(Note::new(64)).to_string(), "E");
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct KeySignature {
+    accidentals: i8,
+    tonic: Option<Letter>,
+}
+
+impl KeySignature {
+    /// Creates a new [`KeySignature`] with the given number of sharps
+    /// (positive) or flats (negative), and tonic note letter.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the number of sharps or flats is
+    /// greater than 7.
+    pub fn try_new(accidentals: i8, tonic: Option<Letter>) -> Result<Self, Error> {
+        if accidentals.unsigned_abs() > 7 {
+            return Err(Error::overflow(accidentals.unsigned_abs(), 3));
+        }
+
+        Ok(Self { accidentals, tonic })
+    }
+
+    /// Reads the key signature from a [`SetKeySignature`] message, returning
+    /// `None` if the message gives a non-standard key signature.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the fields of the message cannot
+    /// be read.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn try_from_message(message: &SetKeySignature<'_>) -> Result<Option<Self>, Error> {
+        let sharps_flats = u8::from(message.sharps_flats()?);
+
+        if sharps_flats == NON_STANDARD {
+            return Ok(None);
+        }
+
+        let accidentals = ((sharps_flats << 4) as i8) >> 4;
+        let tonic = Letter::from_tonic(message.tonic()?);
+
+        Self::try_new(accidentals, tonic).map(Some)
+    }
+
+    /// Returns the number of sharps (positive) or flats (negative).
+    #[must_use]
+    pub const fn accidentals(&self) -> i8 {
+        self.accidentals
+    }
+
+    #[must_use]
+    pub const fn tonic(&self) -> Option<Letter> {
+        self.tonic
+    }
+
+    /// Returns the mode implied by the tonic note, or `None` if the tonic is
+    /// unknown, or is neither the major nor the relative minor tonic of the key
+    /// signature (e.g. for modal music).
+    #[must_use]
+    pub fn mode(&self) -> Option<Mode> {
+        let tonic = self.tonic?;
+        let major = LETTERS[usize::from((self.accidentals * 4).rem_euclid(7).unsigned_abs())];
+        let minor = LETTERS[usize::from((self.accidentals * 4 + 5).rem_euclid(7).unsigned_abs())];
+
+        if tonic == major {
+            Some(Mode::Major)
+        } else if tonic == minor {
+            Some(Mode::Minor)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the accidental applied to notes of the given letter by the key
+    /// signature.
+    #[must_use]
+    pub fn accidental(&self, letter: Letter) -> Accidental {
+        let count = usize::from(self.accidentals.unsigned_abs());
+
+        if self.accidentals > 0 && SHARPS[..count].contains(&letter) {
+            Accidental::Sharp
+        } else if self.accidentals < 0 && SHARPS[7 - count..].contains(&letter) {
+            Accidental::Flat
+        } else {
+            Accidental::Natural
+        }
+    }
+
+    /// Returns whether the given note is one of the seven notes of the scale
+    /// given by the key signature.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn contains(&self, note: Note) -> bool {
+        let pitch_class = (u8::from(note) % 12) as i8;
+
+        LETTERS.iter().any(|&letter| {
+            (letter.pitch_class() + self.accidental(letter).offset()).rem_euclid(12) == pitch_class
+        })
+    }
+
+    /// Suggests a spelling for the given note in this key.
+    ///
+    /// Notes in the key are spelled as given by the key signature. Other notes
+    /// are spelled as naturals where possible, and otherwise as sharps in keys
+    /// with sharps (or no accidentals), and as flats in keys with flats.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn spell(&self, note: Note) -> Spelling {
+        let pitch_class = (u8::from(note) % 12) as i8;
+        let spelling = |letter: Letter, accidental: Accidental| {
+            ((letter.pitch_class() + accidental.offset()).rem_euclid(12) == pitch_class)
+                .then_some(Spelling { letter, accidental })
+        };
+
+        let chromatic = if self.accidentals < 0 {
+            Accidental::Flat
+        } else {
+            Accidental::Sharp
+        };
+
+        LETTERS
+            .iter()
+            .find_map(|&letter| spelling(letter, self.accidental(letter)))
+            .or_else(|| {
+                LETTERS
+                    .iter()
+                    .find_map(|&letter| spelling(letter, Accidental::Natural))
+            })
+            .or_else(|| {
+                LETTERS
+                    .iter()
+                    .find_map(|&letter| spelling(letter, chromatic))
+            })
+            // every pitch class is either a natural, or one step from a natural
+            .unwrap_or(Spelling {
+                letter: Letter::C,
+                accidental: Accidental::Natural,
+            })
+    }
+}
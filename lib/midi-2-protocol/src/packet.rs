@@ -2,6 +2,20 @@
 // Packet
 // =============================================================================
 
+//! Packet buffer utilities.
+//!
+//! The [`packet`](crate::packet) module contains functions which operate on
+//! buffers of UMP words containing a sequence of packets, such as finding the
+//! message boundaries within a buffer, or viewing a byte buffer (as delivered
+//! by a driver) as UMP words, and an owned, allocation-free [`Packet`] type.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::ops::{
+    Deref,
+    DerefMut,
+};
+
 use bitvec::{
     order::Msb0,
     slice::BitSlice,
@@ -12,26 +26,37 @@ use crate::{
         TryReadFromPacket,
         WriteToPacket,
     },
+    message::{
+        self,
+        Message,
+    },
     Error,
 };
 
 // -----------------------------------------------------------------------------
 
+// Constants
+
+#[cfg(feature = "alloc")]
+pub(crate) const GROUP_MASK: u32 = 0x0f00_0000;
+
+// -----------------------------------------------------------------------------
+
 // Traits
 
-pub trait GetBitSlice {
+pub(crate) trait GetBitSlice {
     fn get_bit_slice(&self) -> &BitSlice<u32, Msb0>;
 
     fn get_bit_slice_mut(&mut self) -> &mut BitSlice<u32, Msb0>;
 }
 
-pub trait TryReadField {
+pub(crate) trait TryReadField {
     fn try_read_field<F>(&self) -> Result<F, Error>
     where
         F: TryReadFromPacket;
 }
 
-pub trait WriteField {
+pub(crate) trait WriteField {
     fn write_field<F>(self, field: F) -> Self
     where
         F: WriteToPacket;
@@ -74,3 +99,272 @@ where
         value.write_to_packet(self)
     }
 }
+
+// -----------------------------------------------------------------------------
+
+// Aligner
+
+/// Views byte buffers as buffers of UMP words.
+///
+/// Receive buffers are often delivered by drivers as `&mut [u8]`, with no
+/// guarantee of alignment for `u32`. An [`Aligner`] provides access to such a
+/// buffer as a `&mut [u32]` (from which messages may be parsed), without
+/// copying when the buffer is suitably aligned, and via an internal bounce
+/// buffer (which is reused, and written back to the byte buffer) otherwise.
+///
+/// Words are expected to be in native byte order, as delivered by platform
+/// drivers. The bounce buffer requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # #[cfg(feature = "system")]
+/// # {
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::packet::*;
+/// #
+/// let mut aligner = Aligner::new();
+/// let mut bytes = [0u8; 9];
+///
+/// // a misaligned receive buffer, containing a single system message
+/// bytes[1..5].copy_from_slice(&0x10f80000u32.to_ne_bytes());
+///
+/// let is_system = aligner.try_with_words(&mut bytes[1..5], |words| {
+///     Message::try_from(words).map(|message| matches!(message, Message::System(_)))
+/// })??;
+///
+/// assert!(is_system);
+/// # }
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default)]
+pub struct Aligner {
+    bounce: Vec<u32>,
+}
+
+#[cfg(feature = "alloc")]
+impl Aligner {
+    /// Creates a new [`Aligner`] (the bounce buffer is allocated on first use).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { bounce: Vec::new() }
+    }
+
+    /// Calls the given function with the byte buffer viewed as UMP words,
+    /// returning the result of the function. Any changes made to the words are
+    /// reflected in the byte buffer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the length of the byte buffer is
+    /// not a whole number of words.
+    pub fn try_with_words<F, R>(&mut self, bytes: &mut [u8], f: F) -> Result<R, Error>
+    where
+        F: FnOnce(&mut [u32]) -> R,
+    {
+        let remainder = u8::try_from(bytes.len() % 4).unwrap_or_default();
+
+        if remainder != 0 {
+            return Err(Error::size(32, remainder * 8));
+        }
+
+        if let Ok(words) = bytemuck::try_cast_slice_mut::<u8, u32>(bytes) {
+            return Ok(f(words));
+        }
+
+        self.bounce.clear();
+        self.bounce.extend(
+            bytes
+                .chunks_exact(4)
+                .map(|chunk| u32::from_ne_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])),
+        );
+
+        let result = f(&mut self.bounce);
+
+        for (chunk, word) in bytes.chunks_exact_mut(4).zip(&self.bounce) {
+            chunk.copy_from_slice(&word.to_ne_bytes());
+        }
+
+        Ok(result)
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Packet
+
+/// An owned packet, holding a single message of up to 128 bits.
+///
+/// A [`Packet`] stores its words inline (in a fixed buffer of four words), so
+/// owned messages can be stored, queued and copied without allocation (e.g. on
+/// an audio thread). Messages may be viewed using [`Packet::try_message`].
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # #[cfg(feature = "voice")]
+/// # {
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::packet::*;
+/// #
+/// let buffer = [0x40903c00, 0xffff0000, 0x10f80000];
+///
+/// // copy the first message out of a receive buffer...
+/// let mut packet = Packet::try_read(&buffer)?;
+///
+/// assert_eq!(&packet[..], [0x40903c00, 0xffff0000]);
+///
+/// // ...and view it as a message later
+/// assert!(matches!(packet.try_message()?, Message::Voice(_)));
+/// # }
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Packet {
+    words: [u32; 4],
+    len: usize,
+}
+
+impl Packet {
+    /// Reads the first message from the buffer into a new [`Packet`], using
+    /// the Message Type of the first word to determine the size of the
+    /// message. The length of the packet gives the number of words read.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the buffer contains fewer words
+    /// than required by the Message Type.
+    pub fn try_read(buffer: &[u32]) -> Result<Self, Error> {
+        message::try_check_length(buffer)?;
+
+        let len = word_count(buffer[0]);
+        let mut words = [0; 4];
+
+        words[..len].copy_from_slice(&buffer[..len]);
+
+        Ok(Self { words, len })
+    }
+
+    /// Views the packet as a [`Message`](crate::message::Message).
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`](crate::Error) if the packet does not contain a
+    /// valid message.
+    pub fn try_message(&mut self) -> Result<Message<'_>, Error> {
+        Message::try_from(&mut self.words[..self.len])
+    }
+}
+
+impl Deref for Packet {
+    type Target = [u32];
+
+    fn deref(&self) -> &Self::Target {
+        &self.words[..self.len]
+    }
+}
+
+impl DerefMut for Packet {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.words[..self.len]
+    }
+}
+
+macro_rules! impl_packet_trait_from {
+    ($($len:literal),*) => {
+        $(
+            impl From<[u32; $len]> for Packet {
+                fn from(value: [u32; $len]) -> Self {
+                    let mut words = [0; 4];
+
+                    words[..$len].copy_from_slice(&value);
+
+                    Self { words, len: $len }
+                }
+            }
+        )*
+    };
+}
+
+impl_packet_trait_from!(1, 2, 3, 4);
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+/// Splits a buffer of UMP words at a message boundary.
+///
+/// The buffer is split at the end of the longest sequence of complete messages
+/// which fits within the given number of words, returning the prefix of
+/// complete messages and the remainder of the buffer.
+///
+/// A message is never split between the prefix and the remainder, so the
+/// prefix may be shorter than the given number of words (and is empty if the
+/// first message does not fit). A trailing incomplete message is always left
+/// in the remainder.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::packet::*;
+/// #
+/// // a 32-bit system message, a 64-bit voice message and a 128-bit message
+/// let buffer = [0x10f80000, 0x40903c00, 0xffff0000, 0xd0100002, 0, 0, 0];
+///
+/// let (prefix, rest) = split_at_message(&buffer, 4);
+///
+/// assert_eq!(prefix.len(), 3);
+/// assert_eq!(rest.len(), 4);
+/// ```
+#[must_use]
+pub fn split_at_message(buffer: &[u32], max_words: usize) -> (&[u32], &[u32]) {
+    let limit = max_words.min(buffer.len());
+    let mut position = 0;
+
+    while position < buffer.len() {
+        let next = position + word_count(buffer[position]);
+
+        if next > limit {
+            break;
+        }
+
+        position = next;
+    }
+
+    buffer.split_at(position)
+}
+
+/// Returns the number of 32-bit words in the message starting with the given
+/// word, as determined by its Message Type (see
+/// [`WORD_COUNTS`](crate::message::WORD_COUNTS)), without parsing the message.
+///
+/// All Message Type values (including those reserved for future use) have a
+/// defined size, so this can be used to step through a stream of UMP words
+/// containing messages of unknown types.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::packet::*;
+/// #
+/// assert_eq!(word_count(0x10f80000), 1);
+/// assert_eq!(word_count(0x40903c00), 2);
+/// assert_eq!(word_count(0xd0100002), 4);
+/// ```
+#[must_use]
+pub const fn word_count(word: u32) -> usize {
+    message::WORD_COUNTS[(word >> 28) as usize]
+}
+
+// Returns whether the message starting with the given word has a Group field
+// (all Message Types other than Utility and Stream).
+
+#[cfg(feature = "alloc")]
+pub(crate) const fn is_grouped(word: u32) -> bool {
+    !matches!(word >> 28, 0x0 | 0xf)
+}
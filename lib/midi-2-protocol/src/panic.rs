@@ -0,0 +1,89 @@
+// =============================================================================
+// Panic
+// =============================================================================
+
+//! MIDI "panic" message generation.
+//!
+//! The [`panic`](crate::panic) module provides the set of messages needed to
+//! silence stuck notes on a receiver -- the familiar "panic button" of host
+//! applications. For each requested group and channel this is:
+//!
+//! - Sustain (Control Change 64) set to zero
+//! - All Notes Off (Control Change 123)
+//! - All Sound Off (Control Change 120)
+//! - A Per-Note Management reset (detaching and resetting per-note
+//!   controllers) for every note
+
+use alloc::vec::Vec;
+
+use crate::message::{
+    voice::Channel,
+    Group,
+};
+
+// -----------------------------------------------------------------------------
+
+// Controllers
+
+const SUSTAIN: u8 = 64;
+const ALL_SOUND_OFF: u8 = 120;
+const ALL_NOTES_OFF: u8 = 123;
+
+// Opcodes and Options
+
+const CONTROL_CHANGE: u8 = 0xb;
+const PER_NOTE_MANAGEMENT: u8 = 0xf;
+
+const DETACH_AND_RESET: u8 = 0x3;
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+/// Returns the complete set of packets needed to silence all notes on the
+/// given groups and channels, in group, then channel order.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::message::voice::*;
+/// #
+/// let packets = panic::all_notes_off(&[Group::G1], &[Channel::C1, Channel::C10]);
+///
+/// // 3 controller messages and 128 per-note resets for each channel...
+/// assert_eq!(packets.len(), 262);
+///
+/// // ...starting with sustain off
+/// assert_eq!(packets[0], [0x40b04000, 0x00000000]);
+///
+/// // ...and ending with a per-note management reset of note 127
+/// assert_eq!(packets[261], [0x40f97f03, 0x00000000]);
+/// ```
+#[must_use]
+pub fn all_notes_off(groups: &[Group], channels: &[Channel]) -> Vec<[u32; 2]> {
+    let mut packets = Vec::with_capacity(groups.len() * channels.len() * (3 + 128));
+
+    for &group in groups {
+        for &channel in channels {
+            let word = |opcode: u8, index_1: u8, index_2: u8| {
+                0x4000_0000
+                    | u32::from(u8::from(group)) << 24
+                    | u32::from(opcode << 4 | u8::from(channel)) << 16
+                    | u32::from(index_1) << 8
+                    | u32::from(index_2)
+            };
+
+            for controller in [SUSTAIN, ALL_NOTES_OFF, ALL_SOUND_OFF] {
+                packets.push([word(CONTROL_CHANGE, controller, 0), 0]);
+            }
+
+            for note in 0..=127 {
+                packets.push([word(PER_NOTE_MANAGEMENT, note, DETACH_AND_RESET), 0]);
+            }
+        }
+    }
+
+    packets
+}
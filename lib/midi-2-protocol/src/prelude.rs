@@ -0,0 +1,149 @@
+// =============================================================================
+// Prelude
+// =============================================================================
+
+//! Commonly used types, for glob import.
+//!
+//! The [`prelude`](crate::prelude) module re-exports the message enumerations,
+//! the message types of each enabled message family, the most commonly used
+//! field types, the parsing entry points and the [`Error`] type, so that simple
+//! programs can use a single import rather than the full module paths.
+//!
+//! Field types which share a name across message families (such as `Status`)
+//! are not re-exported, and nor is the Data message enumeration (which would
+//! clash with the Voice `Data` field type) -- use the full module paths for
+//! these.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use midi_2_protocol::prelude::*;
+//! # #[cfg(all(feature = "system", feature = "voice"))]
+//! # {
+//!
+//! let mut packet = NoteOn::packet();
+//! let _ = NoteOn::try_init(&mut packet, Note::new(60), Velocity::new(0xffff))?
+//!     .set_channel(Channel::C2);
+//!
+//! let buffer = [packet[0], packet[1], 0x10f80000];
+//!
+//! for message in MessageIter::new(&buffer) {
+//!     match message? {
+//!         MessageView::Voice(VoiceView::NoteOn(note_on)) => {
+//!             assert_eq!(note_on.note()?, Note::new(60))
+//!         }
+//!         MessageView::System(SystemView::RealTime(RealTimeView::TimingClock(_))) => {}
+//!         _ => panic!("Oh No!"),
+//!     }
+//! }
+//! # }
+//! #
+//! # Ok::<(), Error>(())
+//! ```
+
+#[cfg(feature = "data")]
+pub use crate::message::data::{
+    MixedDataSetHeader,
+    MixedDataSetPayload,
+    Sysex8Complete,
+    Sysex8Continue,
+    Sysex8End,
+    Sysex8Start,
+};
+#[cfg(feature = "flex-data")]
+pub use crate::message::flex_data::{
+    FlexData,
+    FlexDataView,
+    MetadataText,
+    PerformanceText,
+    SetChordName,
+    SetKeySignature,
+    SetMetronome,
+    SetTempo,
+    SetTimeSignature,
+};
+#[cfg(feature = "stream")]
+pub use crate::message::stream::{
+    DeviceIdentityNotification,
+    EndpointDiscovery,
+    EndpointInfoNotification,
+    EndpointNameNotification,
+    FunctionBlockDiscovery,
+    FunctionBlockInfoNotification,
+    FunctionBlockNameNotification,
+    ProductInstanceIdNotification,
+    Stream,
+    StreamConfigurationNotification,
+    StreamConfigurationRequest,
+    StreamView,
+};
+#[cfg(feature = "system")]
+pub use crate::message::system::{
+    common::{
+        Common,
+        CommonView,
+        MIDITimeCode,
+        SongPositionPointer,
+        SongSelect,
+        TuneRequest,
+    },
+    real_time::{
+        ActiveSensing,
+        Continue,
+        RealTime,
+        RealTimeView,
+        Reset,
+        Start,
+        Stop,
+        TimingClock,
+    },
+    System,
+    SystemView,
+};
+#[cfg(feature = "utility")]
+pub use crate::message::utility::{
+    DeltaClockstamp,
+    DeltaClockstampTicksPerQuarterNote,
+    JrClock,
+    JrTimestamp,
+    Noop,
+    Utility,
+    UtilityView,
+};
+#[cfg(feature = "voice")]
+pub use crate::message::voice::{
+    AssignableController,
+    AssignablePerNoteController,
+    Attribute,
+    Bank,
+    ControlChange,
+    Controller,
+    Data,
+    Index,
+    Note,
+    NoteOff,
+    NoteOn,
+    PerNoteController,
+    PerNoteManagement,
+    PerNotePitchBend,
+    RegisteredController,
+    RegisteredPerNoteController,
+    RelativeAssignableController,
+    RelativeRegisteredController,
+    Velocity,
+    Voice,
+    VoiceView,
+};
+pub use crate::{
+    message::{
+        AddressScope,
+        Channel,
+        Group,
+        Message,
+        MessageIter,
+        MessageIterMut,
+        MessageType,
+        MessageView,
+    },
+    Error,
+};
@@ -0,0 +1,140 @@
+// =============================================================================
+// Quantize
+// =============================================================================
+
+//! Quantization of notes to a key.
+//!
+//! The [`quantize`](crate::quantize) module provides a [`Quantizer`], which
+//! constrains the notes of per-note Channel Voice messages to the scale of a
+//! [`KeySignature`], either moving out-of-key notes to the nearest note in the
+//! key, or blocking them, for live performance tools.
+
+use crate::{
+    message::voice::{
+        self,
+        Note,
+    },
+    notation::KeySignature,
+    packet,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const NOOP: u32 = 0x0000_0000;
+
+// -----------------------------------------------------------------------------
+
+// Policy
+
+/// The treatment of notes which are not in the key.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Policy {
+    /// Notes are moved to the nearest note in the key (moving down where two
+    /// notes are equally near).
+    #[default]
+    Nearest,
+    /// Messages for notes which are not in the key are removed.
+    Block,
+}
+
+// -----------------------------------------------------------------------------
+
+// Quantizer
+
+/// Constrains notes to the scale of a key signature.
+///
+/// The quantizer applies to the same per-note messages as a
+/// [`DrumMap`](crate::drum_map::DrumMap). As each note is always quantized in
+/// the same way, the Note Off for a quantized (or blocked) Note On is also
+/// quantized (or blocked) to match, provided that the key is not changed while
+/// notes are held.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::message::voice::*;
+/// # use midi_2_protocol::notation::*;
+/// # use midi_2_protocol::quantize::*;
+/// #
+/// let c_major = KeySignature::try_new(0, Some(Letter::C))?;
+///
+/// // C# is moved down to C...
+/// let mut buffer = [0x40903d00, 0xffff0000, 0x40804000, 0x00000000];
+///
+/// Quantizer::new(c_major, Policy::Nearest).apply(&mut buffer);
+///
+/// assert_eq!(buffer, [0x40903c00, 0xffff0000, 0x40804000, 0x00000000]);
+///
+/// // ...or replaced with NOOP messages
+/// let mut buffer = [0x40903d00, 0xffff0000, 0x40804000, 0x00000000];
+///
+/// Quantizer::new(c_major, Policy::Block).apply(&mut buffer);
+///
+/// assert_eq!(buffer, [0x00000000, 0x00000000, 0x40804000, 0x00000000]);
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Quantizer {
+    key: KeySignature,
+    policy: Policy,
+}
+
+impl Quantizer {
+    /// Creates a new [`Quantizer`] for the given key and policy.
+    #[must_use]
+    pub const fn new(key: KeySignature, policy: Policy) -> Self {
+        Self { key, policy }
+    }
+
+    /// Returns the quantized note for the given note, or `None` if the note
+    /// is blocked.
+    #[must_use]
+    pub fn quantize(&self, note: Note) -> Option<Note> {
+        let note = u8::from(note);
+
+        match self.policy {
+            Policy::Block => Some(Note::new(note)).filter(|note| self.key.contains(*note)),
+            Policy::Nearest => (0..12)
+                .flat_map(|distance| [note.checked_sub(distance), note.checked_add(distance)])
+                .flatten()
+                .filter(|note| *note <= 0x7f)
+                .map(Note::new)
+                .find(|note| self.key.contains(*note)),
+        }
+    }
+
+    /// Applies the quantizer in place to each message in the buffer.
+    ///
+    /// Blocked messages are replaced with NOOP Utility messages (one for each
+    /// word of the message), so that the length of the buffer is unchanged. A
+    /// trailing incomplete message in the buffer is left unchanged.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn apply(&self, buffer: &mut [u32]) {
+        let mut position = 0;
+
+        while let Some(&word) = buffer.get(position) {
+            let len = packet::word_count(word);
+
+            if position + len > buffer.len() {
+                break;
+            }
+
+            if voice::is_per_note(word) {
+                let note = Note::new((word >> 8 & 0x7f) as u8);
+
+                match self.quantize(note) {
+                    Some(note) => {
+                        buffer[position] = word & !0x0000_ff00 | u32::from(u8::from(note)) << 8;
+                    }
+                    None => buffer[position..position + len].fill(NOOP),
+                }
+            }
+
+            position += len;
+        }
+    }
+}
@@ -0,0 +1,120 @@
+// =============================================================================
+// Scale
+// =============================================================================
+
+//! Scaling of values between bit widths.
+//!
+//! The [`scale`](crate::scale) module implements the min-center-max scaling
+//! method **([M2-115-U])**, used when translating values between MIDI 1.0 and
+//! MIDI 2.0 resolutions (such as 7-bit controller values and 32-bit Data
+//! values), so that the minimum, center and maximum values of each resolution
+//! correspond.
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+/// Scales a value from the given source bit width up to the given destination
+/// bit width, using the min-center-max method **([M2-115-U])**.
+///
+/// Zero, the center value and the maximum value map to zero, the center value
+/// and the maximum value of the wider range. Bits of the value above the source
+/// bit width are ignored.
+///
+/// # Panics
+///
+/// Panics if the source bit width is zero, or greater than the destination bit
+/// width, or if the destination bit width is greater than 32.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::scale::*;
+/// #
+/// assert_eq!(upscale(0x00, 7, 16), 0x0000);
+/// assert_eq!(upscale(0x40, 7, 16), 0x8000);
+/// assert_eq!(upscale(0x7f, 7, 16), 0xffff);
+/// assert_eq!(upscale(0x2000, 14, 32), 0x8000_0000);
+/// assert_eq!(upscale(0x3fff, 14, 32), 0xffff_ffff);
+///
+/// // bits above the source bit width are ignored
+/// assert_eq!(upscale(0xff, 7, 16), 0xffff);
+/// ```
+///
+/// ```rust,should_panic
+/// # use midi_2_protocol::scale::*;
+/// #
+/// let _ = upscale(5, 16, 7); // the source is wider than the destination
+/// ```
+#[must_use]
+pub const fn upscale(value: u32, src_bits: u32, dst_bits: u32) -> u32 {
+    assert!(
+        src_bits >= 1 && src_bits <= dst_bits && dst_bits <= 32,
+        "invalid bit widths for upscale"
+    );
+
+    let value = value & mask(src_bits);
+    let scale_bits = dst_bits - src_bits;
+    let mut result = value << scale_bits;
+
+    if src_bits == 1 {
+        return if value == 0 { 0 } else { mask(dst_bits) };
+    }
+
+    if value <= 1 << (src_bits - 1) {
+        return result;
+    }
+
+    // fill the bits below the original value with repeats of the bits of the
+    // value below the most significant bit
+
+    let repeat_bits = src_bits - 1;
+    let mut repeat = value & ((1 << repeat_bits) - 1);
+
+    if scale_bits > repeat_bits {
+        repeat <<= scale_bits - repeat_bits;
+    } else {
+        repeat >>= repeat_bits - scale_bits;
+    }
+
+    while repeat != 0 {
+        result |= repeat;
+        repeat >>= repeat_bits;
+    }
+
+    result
+}
+
+/// Scales a value from the given source bit width down to the given
+/// destination bit width (discarding the least significant bits). Bits of the
+/// value above the source bit width are ignored.
+///
+/// # Panics
+///
+/// Panics if the destination bit width is zero, or greater than the source bit
+/// width, or if the source bit width is greater than 32.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::scale::*;
+/// #
+/// assert_eq!(downscale(0x8000, 16, 7), 0x40);
+/// assert_eq!(downscale(upscale(0x55, 7, 32), 32, 7), 0x55);
+/// assert_eq!(downscale(0x1_8000, 16, 7), 0x40);
+/// ```
+#[must_use]
+pub const fn downscale(value: u32, src_bits: u32, dst_bits: u32) -> u32 {
+    assert!(
+        dst_bits >= 1 && dst_bits <= src_bits && src_bits <= 32,
+        "invalid bit widths for downscale"
+    );
+
+    (value & mask(src_bits)) >> (src_bits - dst_bits)
+}
+
+// Returns a mask of the given (non-zero) number of least significant bits.
+
+const fn mask(bits: u32) -> u32 {
+    u32::MAX >> (32 - bits)
+}
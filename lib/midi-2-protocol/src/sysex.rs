@@ -0,0 +1,727 @@
+// =============================================================================
+// Sysex
+// =============================================================================
+
+//! Splitting and assembly of System Exclusive payloads.
+//!
+//! The [`sysex`](crate::sysex) module converts between complete System
+//! Exclusive payloads and the sequences of UMPs which carry them -- System
+//! Exclusive (7-Bit) messages **([M2-104-UM 7.7])** and System Exclusive 8
+//! messages **([M2-104-UM 7.8])**. The [`write_sysex7`] and [`write_sysex8`]
+//! functions split a payload into Complete, or Start, Continue and End
+//! messages, and an [`Assembler`] accumulates incoming messages and yields
+//! each payload once it is complete.
+//!
+//! Mixed Data Sets **([M2-104-UM 7.9])** are sent in the same Message Type as
+//! System Exclusive 8 messages, as chunks of a Mixed Data Set Header message
+//! followed by Mixed Data Set Payload messages. A [`MixedDataSetAssembler`]
+//! accumulates incoming Mixed Data Set messages and yields each chunk once it
+//! is complete.
+
+use alloc::vec::Vec;
+
+use crate::{
+    message::{
+        Group,
+        MessageType,
+    },
+    packet,
+    Error,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+const SYSEX7_BYTES: usize = 6;
+const SYSEX8_BYTES: usize = 13;
+const MIXED_DATA_SET_BYTES: usize = 14;
+
+const COMPLETE: u8 = 0x0;
+const START: u8 = 0x1;
+const CONTINUE: u8 = 0x2;
+const END: u8 = 0x3;
+
+const MIXED_DATA_SET_HEADER: u8 = 0x8;
+const MIXED_DATA_SET_PAYLOAD: u8 = 0x9;
+
+// -----------------------------------------------------------------------------
+
+// Sysex
+
+/// A complete System Exclusive payload, with the Group (and for System
+/// Exclusive 8 messages, the Stream ID) on which it was sent.
+///
+/// The payload excludes the Start and End bytes (`0xf0` and `0xf7`) of the
+/// equivalent MIDI 1.0 message, which are not sent in UMPs.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Sysex {
+    /// The Group on which the payload was sent.
+    pub group: Group,
+    /// The Stream ID of a System Exclusive 8 payload, or `None` for a System
+    /// Exclusive (7-Bit) payload.
+    pub stream_id: Option<u8>,
+    /// The payload bytes.
+    pub data: Vec<u8>,
+}
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+/// Appends the System Exclusive (7-Bit) messages carrying the given payload
+/// to the buffer of words.
+///
+/// A payload of up to 6 bytes is sent as a single Complete message, and longer
+/// payloads as a Start message, any number of Continue messages, and an End
+/// message. An empty payload is sent as an empty Complete message.
+///
+/// # Errors
+///
+/// Returns an [`Error`] (leaving the buffer unchanged) if any byte of the
+/// payload is not a 7-bit value.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::sysex::*;
+/// #
+/// let mut words = Vec::new();
+///
+/// write_sysex7(Group::G1, &[0x7e, 0x7f, 0x06, 0x01, 0x00, 0x01, 0x02], &mut words)?;
+///
+/// assert_eq!(words, [0x30167e7f, 0x06010001, 0x30310200, 0x00000000]);
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+pub fn write_sysex7(group: Group, payload: &[u8], words: &mut Vec<u32>) -> Result<(), Error> {
+    if let Some(&byte) = payload.iter().find(|&&byte| byte > 0x7f) {
+        return Err(Error::overflow(byte, 7));
+    }
+
+    write(
+        words,
+        MessageType::SystemExclusiveData,
+        group,
+        None,
+        payload,
+    );
+
+    Ok(())
+}
+
+/// Appends the System Exclusive 8 messages carrying the given payload, with
+/// the given Stream ID, to the buffer of words.
+///
+/// A payload of up to 13 bytes is sent as a single Complete message, and
+/// longer payloads as a Start message, any number of Continue messages, and an
+/// End message. An empty payload is sent as an empty Complete message.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::sysex::*;
+/// #
+/// let mut words = Vec::new();
+///
+/// write_sysex8(Group::G1, 1, &[0x7e, 0x7f], &mut words);
+///
+/// assert_eq!(words, [0x5003017e, 0x7f000000, 0x00000000, 0x00000000]);
+/// ```
+pub fn write_sysex8(group: Group, stream_id: u8, payload: &[u8], words: &mut Vec<u32>) {
+    write(words, MessageType::Data, group, Some(stream_id), payload);
+}
+
+fn write(
+    words: &mut Vec<u32>,
+    message_type: MessageType,
+    group: Group,
+    stream_id: Option<u8>,
+    payload: &[u8],
+) {
+    let size = match stream_id {
+        Some(_) => SYSEX8_BYTES,
+        None => SYSEX7_BYTES,
+    };
+
+    let count = ((payload.len() + size - 1) / size).max(1);
+
+    for index in 0..count {
+        let chunk = &payload[index * size..((index + 1) * size).min(payload.len())];
+        let status = status(index == 0, index == count - 1);
+
+        write_chunk(words, message_type, group, stream_id, status, chunk);
+    }
+}
+
+/// Appends a single System Exclusive (7-Bit) message carrying a chunk of a
+/// payload (of at most 6 bytes), as the first and/or last chunk of the
+/// payload, to the buffer of words.
+pub(crate) fn write_sysex7_chunk(
+    group: Group,
+    first: bool,
+    last: bool,
+    chunk: &[u8],
+    words: &mut Vec<u32>,
+) {
+    write_chunk(
+        words,
+        MessageType::SystemExclusiveData,
+        group,
+        None,
+        status(first, last),
+        &chunk[..chunk.len().min(SYSEX7_BYTES)],
+    );
+}
+
+fn write_chunk(
+    words: &mut Vec<u32>,
+    message_type: MessageType,
+    group: Group,
+    stream_id: Option<u8>,
+    status: u8,
+    chunk: &[u8],
+) {
+    let len = match stream_id {
+        Some(_) => 4,
+        None => 2,
+    };
+
+    let mut bytes = [0; 16];
+    let mut offset = 2;
+
+    bytes[0] = u8::from(message_type) << 4 | u8::from(group);
+    bytes[1] = status << 4 | chunk_len(chunk, stream_id);
+
+    if let Some(stream_id) = stream_id {
+        bytes[2] = stream_id;
+        offset += 1;
+    }
+
+    bytes[offset..offset + chunk.len()].copy_from_slice(chunk);
+    words.extend(
+        bytes[..len * 4]
+            .chunks_exact(4)
+            .map(|chunk| u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])),
+    );
+}
+
+const fn status(first: bool, last: bool) -> u8 {
+    match (first, last) {
+        (true, true) => COMPLETE,
+        (true, false) => START,
+        (false, false) => CONTINUE,
+        (false, true) => END,
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn chunk_len(chunk: &[u8], stream_id: Option<u8>) -> u8 {
+    (chunk.len() + usize::from(stream_id.is_some())) as u8
+}
+
+// -----------------------------------------------------------------------------
+
+// Assembler
+
+/// Accumulates System Exclusive messages into complete payloads.
+///
+/// Messages are pushed one at a time, and each complete payload is returned
+/// when its Complete or End message is pushed. Payloads are assembled
+/// independently for each Group (and for System Exclusive 8 messages, each
+/// Stream ID), so interleaved payloads are handled correctly. Mixed Data Set
+/// messages (which share the Message Type of System Exclusive 8 messages) are
+/// assembled by a [`MixedDataSetAssembler`], and are rejected with an
+/// `Unsupported` error, rather than being silently dropped. Messages of other
+/// types are ignored.
+///
+/// By default, payloads of any size are assembled. Where messages are received
+/// from untrusted (or unreliable) senders, an assembler created with
+/// [`with_limits`](Assembler::with_limits) bounds the size of each payload, and
+/// the size of the incomplete payload held for each Group and Stream ID, so
+/// that a sender which never completes its payloads cannot exhaust memory, and
+/// a sender flooding one stream cannot starve the others.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::sysex::*;
+/// #
+/// let payload = (0..20).collect::<Vec<u8>>();
+/// let mut words = Vec::new();
+///
+/// write_sysex8(Group::G2, 3, &payload, &mut words);
+///
+/// let mut assembler = Assembler::new();
+///
+/// assert_eq!(assembler.push(&words[..4])?, None);
+///
+/// let sysex = assembler.push(&words[4..])?.unwrap();
+///
+/// assert_eq!(sysex.group, Group::G2);
+/// assert_eq!(sysex.stream_id, Some(3));
+/// assert_eq!(sysex.data, payload);
+///
+/// // an End message without a Start message is out of sequence
+/// assert!(assembler.push(&words[4..]).is_err());
+///
+/// // System Exclusive (7-Bit) data bytes must be 7-bit values
+/// assert!(matches!(
+///     assembler.push(&[0x30028000, 0x00000000]),
+///     Err(Error::Overflow(0x80, 7))
+/// ));
+///
+/// // Mixed Data Set messages are not assembled
+/// assert!(matches!(
+///     assembler.push(&[0x50800000, 0x00000000, 0x00000000, 0x00000000]),
+///     Err(Error::Unsupported("Mixed Data Set"))
+/// ));
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct Assembler {
+    pending: Vec<Sysex>,
+    max_len: usize,
+    budget: usize,
+}
+
+impl Assembler {
+    /// Creates a new [`Assembler`], with no incomplete payloads, and no limits
+    /// on the size of payloads.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_limits(usize::MAX, usize::MAX)
+    }
+
+    /// Creates a new [`Assembler`], with no incomplete payloads, which accepts
+    /// payloads of at most the given maximum length (in bytes), and holds at
+    /// most the given budget of bytes in the incomplete payload of each Group
+    /// and Stream ID.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::*;
+    /// # use midi_2_protocol::message::*;
+    /// # use midi_2_protocol::sysex::*;
+    /// #
+    /// let mut words = Vec::new();
+    ///
+    /// write_sysex7(Group::G1, &[0x01; 16], &mut words)?;
+    ///
+    /// let mut assembler = Assembler::with_limits(12, 1024);
+    ///
+    /// assert_eq!(assembler.push(&words[..2])?, None);
+    /// assert_eq!(assembler.push(&words[2..4])?, None);
+    ///
+    /// // the end of the payload would exceed the maximum length
+    /// assert!(matches!(
+    ///     assembler.push(&words[4..]),
+    ///     Err(Error::Limit { limit: 12, needed: 16 })
+    /// ));
+    ///
+    /// // each stream has its own budget, so a stream which exceeds its budget
+    /// // does not prevent payloads being assembled on other streams
+    /// let mut assembler = Assembler::with_limits(1024, 8);
+    ///
+    /// assert_eq!(assembler.push(&words[..2])?, None);
+    /// assert!(matches!(
+    ///     assembler.push(&words[2..4]),
+    ///     Err(Error::Limit { limit: 8, needed: 12 })
+    /// ));
+    ///
+    /// let mut words = Vec::new();
+    ///
+    /// write_sysex7(Group::G2, &[0x02; 8], &mut words)?;
+    ///
+    /// assert_eq!(assembler.push(&words[..2])?, None);
+    /// assert_eq!(assembler.push(&words[2..])?.unwrap().data, [0x02; 8]);
+    /// #
+    /// # Ok::<(), Error>(())
+    /// ```
+    #[must_use]
+    pub const fn with_limits(max_len: usize, budget: usize) -> Self {
+        Self {
+            pending: Vec::new(),
+            max_len,
+            budget,
+        }
+    }
+
+    /// Pushes the message at the start of the given words to the assembler,
+    /// returning the complete payload if the message completes one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the words are shorter than the message, if the
+    /// message has an invalid Status or Number Of Bytes, if a data byte of a
+    /// System Exclusive (7-Bit) message is not a 7-bit value, or if the message
+    /// is out of sequence (a Continue or End message with no Start message, or
+    /// a Start or Complete message while a payload is incomplete), or if the
+    /// message would exceed the maximum payload length or the budget of the
+    /// assembler, or if the message is a Mixed Data Set message. On error, the
+    /// message and any incomplete payload for the same Group and Stream ID are
+    /// discarded (except for a Mixed Data Set message, or words too short to
+    /// read, which leave any incomplete payloads unchanged).
+    pub fn push(&mut self, words: &[u32]) -> Result<Option<Sysex>, Error> {
+        let bytes = read(words)?;
+        let group = Group::try_from(bytes[0] & 0xf)?;
+        let status = bytes[1] >> 4;
+        let count = usize::from(bytes[1] & 0xf);
+
+        let (stream_id, data) = match MessageType::try_from(bytes[0] >> 4) {
+            Ok(MessageType::SystemExclusiveData) if status <= END => match count {
+                0..=SYSEX7_BYTES => match bytes[2..2 + count].iter().find(|&&byte| byte > 0x7f) {
+                    Some(&byte) => return Err(self.discard(group, None, Error::overflow(byte, 7))),
+                    None => (None, &bytes[2..2 + count]),
+                },
+                _ => return Err(self.discard(group, None, Error::conversion(bytes[1] & 0xf))),
+            },
+            Ok(MessageType::SystemExclusiveData) => {
+                return Err(self.discard(group, None, Error::conversion(status)))
+            }
+            Ok(MessageType::Data) if status <= END => match count {
+                1..=14 => (Some(bytes[2]), &bytes[3..2 + count]),
+                _ => {
+                    return Err(self.discard(
+                        group,
+                        Some(bytes[2]),
+                        Error::conversion(bytes[1] & 0xf),
+                    ))
+                }
+            },
+            Ok(MessageType::Data)
+                if status == MIXED_DATA_SET_HEADER || status == MIXED_DATA_SET_PAYLOAD =>
+            {
+                return Err(Error::unsupported("Mixed Data Set"))
+            }
+            _ => return Ok(None),
+        };
+
+        let pending = self
+            .pending
+            .iter()
+            .position(|sysex| sysex.group == group && sysex.stream_id == stream_id);
+
+        let len = match (status, pending) {
+            (CONTINUE | END, Some(index)) => self.pending[index].data.len() + data.len(),
+            _ => data.len(),
+        };
+
+        if len > self.max_len {
+            return Err(self.discard(group, stream_id, Error::limit(self.max_len, len)));
+        }
+
+        if matches!((status, pending), (START, None) | (CONTINUE, Some(_))) && len > self.budget {
+            return Err(self.discard(group, stream_id, Error::limit(self.budget, len)));
+        }
+
+        match (status, pending) {
+            (COMPLETE, None) => Ok(Some(Sysex {
+                group,
+                stream_id,
+                data: data.to_vec(),
+            })),
+            (START, None) => {
+                self.pending.push(Sysex {
+                    group,
+                    stream_id,
+                    data: data.to_vec(),
+                });
+
+                Ok(None)
+            }
+            (CONTINUE, Some(index)) => {
+                self.pending[index].data.extend_from_slice(data);
+
+                Ok(None)
+            }
+            (END, Some(index)) => {
+                let mut sysex = self.pending.swap_remove(index);
+
+                sysex.data.extend_from_slice(data);
+
+                Ok(Some(sysex))
+            }
+            (COMPLETE, Some(_)) => Err(self.discard(
+                group,
+                stream_id,
+                Error::sequence("System Exclusive Complete"),
+            )),
+            (START, Some(_)) => {
+                Err(self.discard(group, stream_id, Error::sequence("System Exclusive Start")))
+            }
+            (CONTINUE, None) => Err(Error::sequence("System Exclusive Continue")),
+            _ => Err(Error::sequence("System Exclusive End")),
+        }
+    }
+
+    /// Discards all incomplete payloads.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    fn discard(&mut self, group: Group, stream_id: Option<u8>, error: Error) -> Error {
+        self.pending
+            .retain(|sysex| sysex.group != group || sysex.stream_id != stream_id);
+
+        error
+    }
+}
+
+impl Default for Assembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Mixed Data Set Chunk
+
+/// A complete chunk of a Mixed Data Set, with the Group and Mixed Data Set ID
+/// on which it was sent.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct MixedDataSetChunk {
+    /// The Group on which the chunk was sent.
+    pub group: Group,
+    /// The ID of the Mixed Data Set (a 4-bit value).
+    pub mds_id: u8,
+    /// The number of chunks in the Mixed Data Set (0 if unknown).
+    pub number_of_chunks: u16,
+    /// The number of this chunk (from 1, or 0 if unknown).
+    pub chunk_number: u16,
+    /// The Manufacturer ID of the Mixed Data Set.
+    pub manufacturer_id: u16,
+    /// The Device ID of the Mixed Data Set.
+    pub device_id: u16,
+    /// The first Sub ID of the Mixed Data Set.
+    pub sub_id_1: u16,
+    /// The second Sub ID of the Mixed Data Set.
+    pub sub_id_2: u16,
+    /// The valid bytes of the chunk.
+    pub data: Vec<u8>,
+}
+
+// -----------------------------------------------------------------------------
+
+// Mixed Data Set Assembler
+
+/// Accumulates Mixed Data Set messages into complete chunks.
+///
+/// Messages are pushed one at a time, and each complete chunk is returned when
+/// the Mixed Data Set Payload message carrying its last valid byte is pushed
+/// (or when its Mixed Data Set Header message is pushed, for a chunk with no
+/// valid bytes). Chunks are assembled independently for each Group and Mixed
+/// Data Set ID, so interleaved Mixed Data Sets are handled correctly. Chunks
+/// are returned individually (with their chunk number, and the number of
+/// chunks in the Mixed Data Set), so that each can be processed as it
+/// arrives. Messages of other types are ignored.
+///
+/// By default, chunks of any size are assembled. An assembler created with
+/// [`with_limits`](MixedDataSetAssembler::with_limits) bounds the size of each
+/// chunk (rejecting a chunk as soon as its Mixed Data Set Header message gives
+/// a larger size), and the size of the incomplete chunk held for each Group
+/// and Mixed Data Set ID.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::*;
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::sysex::*;
+/// #
+/// let words = [
+///     0x50820010, 0x00010001, 0x00410000, 0x00000000, // header, 16 valid bytes
+///     0x50920102, 0x03040506, 0x0708090a, 0x0b0c0d0e, // payload
+///     0x50920f10, 0x00000000, 0x00000000, 0x00000000, // payload
+/// ];
+///
+/// let mut assembler = MixedDataSetAssembler::new();
+///
+/// assert_eq!(assembler.push(&words[..4])?, None);
+/// assert_eq!(assembler.push(&words[4..8])?, None);
+///
+/// let chunk = assembler.push(&words[8..])?.unwrap();
+///
+/// assert_eq!(chunk.group, Group::G1);
+/// assert_eq!(chunk.mds_id, 2);
+/// assert_eq!(chunk.manufacturer_id, 0x0041);
+/// assert_eq!(chunk.data, (1..=16).collect::<Vec<u8>>());
+///
+/// // a chunk larger than the maximum length is rejected by its header
+/// let mut assembler = MixedDataSetAssembler::with_limits(8, 1024);
+///
+/// assert!(matches!(
+///     assembler.push(&words[..4]),
+///     Err(Error::Limit { limit: 8, needed: 16 })
+/// ));
+///
+/// // a Payload message without a Header message is out of sequence
+/// assert!(assembler.push(&words[4..8]).is_err());
+/// #
+/// # Ok::<(), Error>(())
+/// ```
+#[derive(Clone, Debug)]
+pub struct MixedDataSetAssembler {
+    pending: Vec<(MixedDataSetChunk, usize)>,
+    max_len: usize,
+    budget: usize,
+}
+
+impl MixedDataSetAssembler {
+    /// Creates a new [`MixedDataSetAssembler`], with no incomplete chunks, and
+    /// no limits on the size of chunks.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_limits(usize::MAX, usize::MAX)
+    }
+
+    /// Creates a new [`MixedDataSetAssembler`], with no incomplete chunks,
+    /// which accepts chunks of at most the given maximum length (in bytes),
+    /// and holds at most the given budget of bytes in the incomplete chunk of
+    /// each Group and Mixed Data Set ID.
+    #[must_use]
+    pub const fn with_limits(max_len: usize, budget: usize) -> Self {
+        Self {
+            pending: Vec::new(),
+            max_len,
+            budget,
+        }
+    }
+
+    /// Pushes the message at the start of the given words to the assembler,
+    /// returning the complete chunk if the message completes one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error`] if the words are shorter than the message, if the
+    /// message is out of sequence (a Payload message with no Header message,
+    /// or a Header message while a chunk is incomplete), or if the chunk would
+    /// exceed the maximum chunk length or the budget of the assembler. On
+    /// error, the message and any incomplete chunk for the same Group and Mixed
+    /// Data Set ID are discarded.
+    pub fn push(&mut self, words: &[u32]) -> Result<Option<MixedDataSetChunk>, Error> {
+        let bytes = read(words)?;
+
+        if !matches!(MessageType::try_from(bytes[0] >> 4), Ok(MessageType::Data)) {
+            return Ok(None);
+        }
+
+        let group = Group::try_from(bytes[0] & 0xf)?;
+        let status = bytes[1] >> 4;
+        let mds_id = bytes[1] & 0xf;
+
+        let pending = self
+            .pending
+            .iter()
+            .position(|(chunk, _)| chunk.group == group && chunk.mds_id == mds_id);
+
+        match (status, pending) {
+            (MIXED_DATA_SET_HEADER, None) => {
+                let field = |index: usize| u16::from_be_bytes([bytes[index], bytes[index + 1]]);
+                let len = usize::from(field(2));
+
+                if len > self.max_len {
+                    return Err(Error::limit(self.max_len, len));
+                }
+
+                let chunk = MixedDataSetChunk {
+                    group,
+                    mds_id,
+                    number_of_chunks: field(4),
+                    chunk_number: field(6),
+                    manufacturer_id: field(8),
+                    device_id: field(10),
+                    sub_id_1: field(12),
+                    sub_id_2: field(14),
+                    data: Vec::new(),
+                };
+
+                if len == 0 {
+                    return Ok(Some(chunk));
+                }
+
+                self.pending.push((chunk, len));
+
+                Ok(None)
+            }
+            (MIXED_DATA_SET_PAYLOAD, Some(index)) => {
+                let remaining = self.pending[index].1;
+                let len = remaining.min(MIXED_DATA_SET_BYTES);
+                let held = self.pending[index].0.data.len() + len;
+
+                if remaining > len && held > self.budget {
+                    return Err(self.discard(group, mds_id, Error::limit(self.budget, held)));
+                }
+
+                let (chunk, remaining) = &mut self.pending[index];
+
+                chunk.data.extend_from_slice(&bytes[2..2 + len]);
+                *remaining -= len;
+
+                match remaining {
+                    0 => Ok(Some(self.pending.swap_remove(index).0)),
+                    _ => Ok(None),
+                }
+            }
+            (MIXED_DATA_SET_HEADER, Some(_)) => {
+                Err(self.discard(group, mds_id, Error::sequence("Mixed Data Set Header")))
+            }
+            (MIXED_DATA_SET_PAYLOAD, None) => Err(Error::sequence("Mixed Data Set Payload")),
+            _ => Ok(None),
+        }
+    }
+
+    /// Discards all incomplete chunks.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    fn discard(&mut self, group: Group, mds_id: u8, error: Error) -> Error {
+        self.pending
+            .retain(|(chunk, _)| chunk.group != group || chunk.mds_id != mds_id);
+
+        error
+    }
+}
+
+impl Default for MixedDataSetAssembler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Reading
+
+// Reads the message at the start of the given words as bytes (with any bytes
+// beyond the length of the message left as zero).
+
+fn read(words: &[u32]) -> Result<[u8; 16], Error> {
+    let word = match words.first() {
+        Some(&word) => word,
+        None => return Err(Error::truncated(1, 0)),
+    };
+
+    let len = packet::word_count(word);
+
+    if words.len() < len {
+        return Err(Error::truncated(len, words.len()));
+    }
+
+    let mut bytes = [0; 16];
+
+    for (chunk, word) in bytes.chunks_exact_mut(4).zip(&words[..len]) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+
+    Ok(bytes)
+}
@@ -0,0 +1,257 @@
+// =============================================================================
+// Timestamp
+// =============================================================================
+
+//! A unified timestamp representation.
+//!
+//! Message times appear in several units: host times (in nanoseconds, from a
+//! monotonic clock), and the times carried by Utility messages
+//! **([M2-104-UM 7.2])** -- JR (Jitter Reduction) times in wrapping 16-bit
+//! ticks of 1/31250 s, and Delta Clockstamps in ticks relative to the previous
+//! message, at a rate set by the Delta Clockstamp Ticks Per Quarter Note
+//! message.
+//!
+//! The [`timestamp`](crate::timestamp) module provides a [`Timestamp`] type
+//! which carries the unit of a time alongside its value, so that times in
+//! different units cannot be mixed accidentally, and a [`TimeContext`],
+//! required to convert any timestamp to host time.
+//!
+//! Platform MIDI APIs give host times in the units of their own clocks -- a
+//! [`Timebase`] converts such host times (for example, the mach absolute times
+//! used by Core MIDI, or the performance counter times used by Windows MIDI
+//! Services) to host times in nanoseconds, and to sample offsets within audio
+//! buffers.
+
+use crate::drift::Estimator;
+
+// -----------------------------------------------------------------------------
+
+// Timestamp
+
+/// A time, in one of the units used by MIDI 2.0 messages and hosts.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Timestamp {
+    /// A host time, in nanoseconds (from any monotonic clock).
+    Host(u64),
+    /// A remote JR time, in ticks of 1/31250 s (wrapping every 65536 ticks).
+    Jr(u16),
+    /// A Delta Clockstamp, in ticks since the previous message (a 20-bit
+    /// value).
+    Delta(u32),
+}
+
+impl Timestamp {
+    /// Converts the timestamp to a host time (in nanoseconds) using the given
+    /// time context, or returns `None` if the context does not provide the
+    /// information required to convert from the unit of the timestamp, or if
+    /// the host time would overflow.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use midi_2_protocol::drift::*;
+    /// # use midi_2_protocol::timestamp::*;
+    /// #
+    /// let mut estimator = Estimator::default();
+    ///
+    /// estimator.observe(0, 1_000_000);
+    /// estimator.observe(10_000, 321_000_000);
+    ///
+    /// let context = TimeContext::new()
+    ///     .set_estimator(&estimator)
+    ///     .set_delta(96, 50_000_000, 2_000_000_000);
+    ///
+    /// assert_eq!(Timestamp::Host(5).to_host(&context), Some(5));
+    /// assert_eq!(Timestamp::Jr(5_000).to_host(&context), Some(161_000_000));
+    ///
+    /// // 48 ticks is an eighth note (250 ms at 120 bpm) after the origin
+    /// assert_eq!(Timestamp::Delta(48).to_host(&context), Some(2_250_000_000));
+    ///
+    /// // without a delta context, Delta Clockstamps cannot be converted
+    /// assert_eq!(Timestamp::Delta(48).to_host(&TimeContext::new()), None);
+    ///
+    /// // host times beyond the range of a u64 cannot be converted
+    /// let context = TimeContext::new().set_delta(1, u32::MAX, 0);
+    ///
+    /// assert_eq!(Timestamp::Delta(u32::MAX).to_host(&context), None);
+    /// ```
+    #[must_use]
+    pub fn to_host(self, context: &TimeContext<'_>) -> Option<u64> {
+        match self {
+            Self::Host(nanos) => Some(nanos),
+            Self::Jr(ticks) => context.estimator?.to_host(ticks),
+            Self::Delta(ticks) => {
+                let delta = context.delta?;
+                let nanos = u128::from(ticks) * u128::from(delta.tempo) * 10
+                    / u128::from(delta.ticks_per_quarter_note.max(1));
+
+                delta.origin.checked_add(u64::try_from(nanos).ok()?)
+            }
+        }
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Time Context
+
+/// The context required to convert timestamps to host time.
+///
+/// A [`TimeContext`] is created empty (so that only host times may be
+/// converted), and context may be added using builder-style `set_*`
+/// functions.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TimeContext<'a> {
+    estimator: Option<&'a Estimator>,
+    delta: Option<Delta>,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Delta {
+    ticks_per_quarter_note: u16,
+    tempo: u32,
+    origin: u64,
+}
+
+impl<'a> TimeContext<'a> {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            estimator: None,
+            delta: None,
+        }
+    }
+
+    /// Sets the drift estimator used to map remote JR times to host time.
+    #[must_use]
+    pub const fn set_estimator(mut self, estimator: &'a Estimator) -> Self {
+        self.estimator = Some(estimator);
+        self
+    }
+
+    /// Sets the Delta Clockstamp rate (in ticks per quarter note), the tempo
+    /// (in units of 10 nanoseconds per quarter note), and the host time (in
+    /// nanoseconds) of the previous message, from which Delta Clockstamps are
+    /// measured.
+    #[must_use]
+    pub const fn set_delta(mut self, ticks_per_quarter_note: u16, tempo: u32, origin: u64) -> Self {
+        self.delta = Some(Delta {
+            ticks_per_quarter_note,
+            tempo,
+            origin,
+        });
+        self
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Timebase
+
+/// The timebase of a platform host clock, for converting the host times used
+/// by platform MIDI APIs to host times in nanoseconds.
+///
+/// A timebase is given as the ratio of nanoseconds to host clock ticks, and
+/// may be created from:
+///
+/// - The `numer` and `denom` fields of the `mach_timebase_info` structure, for
+///   the mach absolute times used by Core MIDI (and Core Audio) on Apple
+///   platforms, such as the timestamps of each `MIDIEventPacket` in a
+///   `MIDIEventList` (1/1 on Intel Macs, and 125/3 on Apple silicon). Note that
+///   Core MIDI uses a timestamp of zero to mean "now" -- such timestamps should
+///   be replaced by the current host time before conversion.
+/// - The frequency of a tick counter, for the 64-bit timestamps used by
+///   Windows MIDI Services, which are `QueryPerformanceCounter` ticks at the
+///   frequency given by `MidiClock::TimestampFrequency` (usually 10 MHz).
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::timestamp::*;
+/// #
+/// let timebase = Timebase::from_mach(125, 3);
+///
+/// // 24,000,000 ticks of 41.67 ns is one second
+/// assert_eq!(timebase.to_timestamp(24_000_000), Timestamp::Host(1_000_000_000));
+/// assert_eq!(timebase.from_nanos(1_000_000_000), 24_000_000);
+///
+/// // an event 2.5 ms into an audio buffer beginning at tick 24,000,000, at
+/// // 48 kHz
+/// assert_eq!(timebase.sample_offset(24_060_000, 24_000_000, 48_000), Some(120));
+/// assert_eq!(timebase.sample_offset(23_940_000, 24_000_000, 48_000), None);
+///
+/// // Windows MIDI Services timestamps, in 100 ns ticks
+/// let timebase = Timebase::from_frequency(10_000_000);
+///
+/// assert_eq!(timebase.to_timestamp(15), Timestamp::Host(1_500));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Timebase {
+    numer: u64,
+    denom: u64,
+}
+
+impl Timebase {
+    /// Creates a new [`Timebase`] from the `numer` and `denom` fields of the
+    /// `mach_timebase_info` structure (a denominator of 0 is treated as 1).
+    #[must_use]
+    pub const fn from_mach(numer: u32, denom: u32) -> Self {
+        Self {
+            numer: numer as u64,
+            denom: if denom == 0 { 1 } else { denom as u64 },
+        }
+    }
+
+    /// Creates a new [`Timebase`] from the frequency (in ticks per second) of a
+    /// tick counter (a frequency of 0 is treated as 1).
+    #[must_use]
+    pub const fn from_frequency(frequency: u64) -> Self {
+        Self {
+            numer: 1_000_000_000,
+            denom: if frequency == 0 { 1 } else { frequency },
+        }
+    }
+
+    /// Converts a platform host time to nanoseconds.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn to_nanos(self, host_time: u64) -> u64 {
+        (host_time as u128 * self.numer as u128 / self.denom as u128) as u64
+    }
+
+    /// Converts nanoseconds to a platform host time.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn from_nanos(self, nanos: u64) -> u64 {
+        match self.numer {
+            0 => 0,
+            numer => (nanos as u128 * self.denom as u128 / numer as u128) as u64,
+        }
+    }
+
+    /// Converts a platform host time to a host [`Timestamp`].
+    #[must_use]
+    pub const fn to_timestamp(self, host_time: u64) -> Timestamp {
+        Timestamp::Host(self.to_nanos(host_time))
+    }
+
+    /// Returns the offset (in samples, at the given sample rate) of an event at
+    /// the given platform host time, within an audio buffer beginning at the
+    /// given platform host time, or `None` if the event is before the start of
+    /// the buffer.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn sample_offset(
+        self,
+        host_time: u64,
+        buffer_time: u64,
+        sample_rate: u32,
+    ) -> Option<u64> {
+        match host_time.checked_sub(buffer_time) {
+            Some(ticks) => {
+                Some((self.to_nanos(ticks) as u128 * sample_rate as u128 / 1_000_000_000) as u64)
+            }
+            None => None,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+// =============================================================================
+// Transform
+// =============================================================================
+
+//! Transformations of buffers of UMP words.
+//!
+//! The [`transform`](crate::transform) module provides functions which rewrite
+//! a buffer of UMP words containing a sequence of packets into an output
+//! buffer, operating at the word level (without parsing each message), so that
+//! they are cheap enough to apply to every buffer of a stream.
+
+use alloc::vec::Vec;
+
+use crate::{
+    message::Group,
+    packet,
+};
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+/// Duplicates each grouped message in the source buffer to each of the given
+/// groups, appending the results to the output buffer (e.g. to drive several
+/// synth parts from one input).
+///
+/// Each grouped message is copied once per destination group (in the order
+/// given), with the Group field replaced. Messages without a Group field
+/// (Utility and Stream messages) are copied once, unchanged. A trailing
+/// incomplete message in the source buffer is not copied.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::transform::*;
+/// #
+/// // a 64-bit voice message on group 1, and a 128-bit stream message
+/// let src = [0x40903c00, 0xffff0000, 0xf0000101, 0, 0, 0];
+/// let mut out = Vec::new();
+///
+/// mirror_groups(&src, &[Group::G2, Group::G5], &mut out);
+///
+/// assert_eq!(
+///     out,
+///     [0x41903c00, 0xffff0000, 0x44903c00, 0xffff0000, 0xf0000101, 0, 0, 0]
+/// );
+/// ```
+pub fn mirror_groups(src: &[u32], dests: &[Group], out: &mut Vec<u32>) {
+    let mut rest = src;
+
+    while let Some(&word) = rest.first() {
+        let len = packet::word_count(word);
+
+        if len > rest.len() {
+            break;
+        }
+
+        let (message, next) = rest.split_at(len);
+
+        if packet::is_grouped(word) {
+            out.reserve(len * dests.len());
+
+            for &group in dests {
+                out.push(word & !packet::GROUP_MASK | u32::from(u8::from(group)) << 24);
+                out.extend_from_slice(&message[1..]);
+            }
+        } else {
+            out.extend_from_slice(message);
+        }
+
+        rest = next;
+    }
+}
@@ -0,0 +1,630 @@
+// =============================================================================
+// Translate
+// =============================================================================
+
+//! Translation between MIDI 1.0 byte streams and UMP.
+//!
+//! The [`translate`](crate::translate) module implements the default
+//! translation between the MIDI 1.0 Protocol and the MIDI 2.0 Protocol
+//! **([M2-104-UM D.3])**, for devices which connect MIDI 1.0 byte streams
+//! (such as DIN or USB MIDI 1.0 ports) to UMP endpoints:
+//!
+//! - A [`Midi1Translator`] reads a MIDI 1.0 byte stream (including running
+//!   status and interleaved System Real Time bytes), translating Channel Voice
+//!   messages to MIDI 2.0 Channel Voice messages, System Common and System
+//!   Real Time messages to System messages, and System Exclusive messages to
+//!   System Exclusive (7-Bit) messages.
+//! - [`write_midi1`] writes UMP messages as a MIDI 1.0 byte stream,
+//!   translating MIDI 2.0 Channel Voice messages to their MIDI 1.0
+//!   equivalents.
+//!
+//! Values are scaled between bit widths with [`upscale`] and [`downscale`],
+//! which implement the min-center-max scaling method **([M2-115-U])**.
+
+use alloc::vec::Vec;
+
+pub use crate::scale::{
+    downscale,
+    upscale,
+};
+use crate::{
+    message::{
+        Channel,
+        Group,
+    },
+    packet,
+    sysex,
+};
+
+// -----------------------------------------------------------------------------
+
+// Constants
+
+// Channel Voice opcodes (and MIDI 1.0 status nibbles)
+
+const REGISTERED_CONTROLLER: u8 = 0x2;
+const ASSIGNABLE_CONTROLLER: u8 = 0x3;
+const NOTE_OFF: u8 = 0x8;
+const NOTE_ON: u8 = 0x9;
+const POLY_PRESSURE: u8 = 0xa;
+const CONTROL_CHANGE: u8 = 0xb;
+const PROGRAM_CHANGE: u8 = 0xc;
+const CHANNEL_PRESSURE: u8 = 0xd;
+const PITCH_BEND: u8 = 0xe;
+
+// Controllers
+
+const BANK_SELECT_MSB: u8 = 0;
+const DATA_ENTRY_MSB: u8 = 6;
+const BANK_SELECT_LSB: u8 = 32;
+const DATA_ENTRY_LSB: u8 = 38;
+const NRPN_LSB: u8 = 98;
+const NRPN_MSB: u8 = 99;
+const RPN_LSB: u8 = 100;
+const RPN_MSB: u8 = 101;
+
+// System Exclusive
+
+const SYSEX_START: u8 = 0xf0;
+const SYSEX_END: u8 = 0xf7;
+const SYSEX_BYTES: usize = 6;
+
+const NOTE_OFF_VELOCITY: u16 = 0x8000;
+
+// -----------------------------------------------------------------------------
+
+// Functions
+
+// MIDI 1.0
+
+/// Appends the MIDI 1.0 byte stream equivalent of each message in the buffer
+/// of words to the buffer of bytes.
+///
+/// Running status is not used. A trailing incomplete message in the buffer of
+/// words is not written.
+///
+/// MIDI 2.0 Channel Voice messages are translated to MIDI 1.0 Channel Voice
+/// messages -- Program Change with a valid bank is preceded by Bank Select
+/// controllers, and Registered and Assignable Controllers are written as RPN
+/// and NRPN controller sequences. System messages, MIDI 1.0 Channel Voice
+/// messages and System Exclusive (7-Bit) messages are written directly. All
+/// other messages have no MIDI 1.0 equivalent, and are skipped.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::translate::*;
+/// #
+/// let mut bytes = Vec::new();
+///
+/// // note on with 16-bit velocity, timing clock, and pitch bend (center)
+/// write_midi1(&[0x40903c00, 0x80000000, 0x10f80000, 0x40e00000, 0x80000000], &mut bytes);
+///
+/// assert_eq!(bytes, [0x90, 0x3c, 0x40, 0xf8, 0xe0, 0x00, 0x40]);
+/// ```
+pub fn write_midi1(words: &[u32], bytes: &mut Vec<u8>) {
+    let mut rest = words;
+
+    while let Some(&word) = rest.first() {
+        let len = packet::word_count(word);
+
+        if len > rest.len() {
+            break;
+        }
+
+        let (message, next) = rest.split_at(len);
+
+        match word >> 28 {
+            0x1 | 0x2 => write_midi1_status(word, bytes),
+            0x3 => write_midi1_sysex(message, bytes),
+            0x4 => write_midi1_voice(message, bytes),
+            _ => {}
+        }
+
+        rest = next;
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn write_midi1_status(word: u32, bytes: &mut Vec<u8>) {
+    let [_, status, data_1, data_2] = word.to_be_bytes();
+
+    match data_len(status) {
+        Some(0) => bytes.push(status),
+        Some(1) => bytes.extend_from_slice(&[status, data_1 & 0x7f]),
+        Some(_) => bytes.extend_from_slice(&[status, data_1 & 0x7f, data_2 & 0x7f]),
+        None => {}
+    }
+}
+
+fn write_midi1_sysex(message: &[u32], bytes: &mut Vec<u8>) {
+    let [_, header, data_0, data_1] = message[0].to_be_bytes();
+    let [data_2, data_3, data_4, data_5] = message[1].to_be_bytes();
+    let data = [data_0, data_1, data_2, data_3, data_4, data_5];
+    let len = usize::from(header & 0xf).min(SYSEX_BYTES);
+
+    // status is complete (0), start (1), continue (2) or end (3)
+
+    if header >> 4 <= 1 {
+        bytes.push(SYSEX_START);
+    }
+
+    bytes.extend_from_slice(&data[..len]);
+
+    if header >> 4 == 0 || header >> 4 == 3 {
+        bytes.push(SYSEX_END);
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn write_midi1_voice(message: &[u32], bytes: &mut Vec<u8>) {
+    let [_, status, index_1, index_2] = message[0].to_be_bytes();
+    let channel = status & 0xf;
+    let data = message[1];
+    let data_7 = downscale(data, 32, 7) as u8;
+    let data_14 = downscale(data, 32, 14);
+    let control = |bytes: &mut Vec<u8>, index, value| {
+        bytes.extend_from_slice(&[CONTROL_CHANGE << 4 | channel, index, value]);
+    };
+
+    match status >> 4 {
+        NOTE_OFF => {
+            let velocity = downscale(data >> 16, 16, 7) as u8;
+
+            bytes.extend_from_slice(&[status, index_1 & 0x7f, velocity]);
+        }
+        NOTE_ON => {
+            let velocity = (downscale(data >> 16, 16, 7) as u8).max(1);
+
+            bytes.extend_from_slice(&[status, index_1 & 0x7f, velocity]);
+        }
+        POLY_PRESSURE | CONTROL_CHANGE => {
+            bytes.extend_from_slice(&[status, index_1 & 0x7f, data_7]);
+        }
+        PROGRAM_CHANGE => {
+            let [program, _, msb, lsb] = data.to_be_bytes();
+
+            if index_2 & 0x1 != 0 {
+                control(bytes, BANK_SELECT_MSB, msb & 0x7f);
+                control(bytes, BANK_SELECT_LSB, lsb & 0x7f);
+            }
+
+            bytes.extend_from_slice(&[status, program & 0x7f]);
+        }
+        CHANNEL_PRESSURE => bytes.extend_from_slice(&[status, data_7]),
+        PITCH_BEND => {
+            bytes.extend_from_slice(&[status, (data_14 & 0x7f) as u8, (data_14 >> 7) as u8]);
+        }
+        opcode @ (REGISTERED_CONTROLLER | ASSIGNABLE_CONTROLLER) => {
+            let (msb, lsb) = match opcode {
+                REGISTERED_CONTROLLER => (RPN_MSB, RPN_LSB),
+                _ => (NRPN_MSB, NRPN_LSB),
+            };
+
+            control(bytes, msb, index_1 & 0x7f);
+            control(bytes, lsb, index_2 & 0x7f);
+            control(bytes, DATA_ENTRY_MSB, (data_14 >> 7) as u8);
+            control(bytes, DATA_ENTRY_LSB, (data_14 & 0x7f) as u8);
+        }
+        _ => {}
+    }
+}
+
+// Status
+
+const fn data_len(status: u8) -> Option<usize> {
+    match status {
+        0x80..=0xbf | 0xe0..=0xef | 0xf2 => Some(2),
+        0xc0..=0xdf | 0xf1 | 0xf3 => Some(1),
+        0xf6 | 0xf8 | 0xfa..=0xfc | 0xfe | 0xff => Some(0),
+        _ => None,
+    }
+}
+
+// -----------------------------------------------------------------------------
+
+// Real Time In Sysex
+
+/// The handling of System Real Time bytes which arrive within a System
+/// Exclusive message (as MIDI 1.0 allows, and many interfaces do with timing
+/// clocks).
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RealTimeInSysex {
+    /// System Real Time bytes are translated to System messages immediately,
+    /// ahead of the System Exclusive message, which continues unaffected.
+    #[default]
+    Forward,
+    /// System Real Time bytes are discarded, and the System Exclusive message
+    /// continues unaffected.
+    Discard,
+}
+
+// -----------------------------------------------------------------------------
+
+// MIDI 1.0 Translator
+
+/// Translates a MIDI 1.0 byte stream to UMP messages on a given group.
+///
+/// Channel Voice messages are translated to MIDI 2.0 Channel Voice messages,
+/// with values upscaled to the wider MIDI 2.0 fields. Following the default
+/// translation, a Note On with a velocity of zero is translated to a Note Off,
+/// Bank Select controllers are held and applied to the following Program
+/// Change, and RPN and NRPN controller sequences (with Data Entry) are
+/// translated to Registered and Assignable Controller messages.
+///
+/// System Exclusive messages are translated to System Exclusive (7-Bit)
+/// messages as the bytes are read, without waiting for the whole message.
+/// System Real Time bytes within a System Exclusive message are forwarded
+/// immediately by default, or discarded, as given by [`RealTimeInSysex`].
+/// Bytes which are not part of a message (such as data bytes with no status,
+/// or undefined status bytes) are ignored.
+///
+/// # Examples
+///
+/// ```rust
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::translate::*;
+/// #
+/// let mut translator = Midi1Translator::new(Group::G1);
+/// let mut words = Vec::new();
+///
+/// // note on, then (with running status, and an interleaved timing clock) a
+/// // note on with zero velocity
+/// translator.push(&[0x90, 0x3c, 0x7f, 0x3c, 0xf8, 0x00], &mut words);
+///
+/// assert_eq!(
+///     words,
+///     [0x40903c00, 0xffff0000, 0x10f80000, 0x40803c00, 0x80000000]
+/// );
+///
+/// // a system exclusive message with an interleaved timing clock, which is
+/// // forwarded ahead of the (uninterrupted) system exclusive message...
+/// words.clear();
+/// translator.push(&[0xf0, 0x7e, 0x01, 0xf8, 0x02, 0xf7], &mut words);
+///
+/// assert_eq!(words, [0x10f80000, 0x30037e01, 0x02000000]);
+///
+/// // ...or discarded
+/// let mut translator = Midi1Translator::new(Group::G1)
+///     .with_real_time_in_sysex(RealTimeInSysex::Discard);
+///
+/// words.clear();
+/// translator.push(&[0xf0, 0x7e, 0x01, 0xf8, 0x02, 0xf7, 0xf8], &mut words);
+///
+/// assert_eq!(words, [0x30037e01, 0x02000000, 0x10f80000]);
+/// ```
+///
+/// The state of the translator (the running status, and the held Bank Select,
+/// RPN and NRPN state of each channel) may be inspected, and reset when the
+/// stream is interrupted (for example, when a device is reconnected):
+///
+/// ```rust
+/// # use midi_2_protocol::message::*;
+/// # use midi_2_protocol::translate::*;
+/// #
+/// let mut translator = Midi1Translator::new(Group::G1);
+/// let mut words = Vec::new();
+///
+/// // select RPN 0/0 and send a data entry MSB of 2 on channel 2
+/// translator.push(&[0xb1, 0x65, 0x00, 0x64, 0x00, 0x06, 0x02], &mut words);
+///
+/// assert_eq!(translator.running_status(), Some(0xb1));
+/// assert_eq!(
+///     translator.channel_state(Channel::C2),
+///     ChannelState {
+///         rpn: (0, 0),
+///         parameter: Some(Parameter::Registered),
+///         data: 0x100,
+///         ..ChannelState::default()
+///     }
+/// );
+///
+/// translator.reset_channel(Channel::C2);
+///
+/// assert_eq!(translator.channel_state(Channel::C2), ChannelState::default());
+///
+/// // after a reset, data bytes without a status byte are ignored
+/// translator.reset();
+/// words.clear();
+/// translator.push(&[0x3c, 0x7f], &mut words);
+///
+/// assert_eq!(translator.running_status(), None);
+/// assert!(words.is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Midi1Translator {
+    group: Group,
+    status: Option<u8>,
+    data: [u8; 2],
+    len: usize,
+    sysex: Option<Sysex>,
+    real_time_in_sysex: RealTimeInSysex,
+    channels: [ChannelState; 16],
+}
+
+impl Midi1Translator {
+    /// Creates a new [`Midi1Translator`], translating to messages on the given
+    /// group.
+    #[must_use]
+    pub fn new(group: Group) -> Self {
+        Self {
+            group,
+            status: None,
+            data: [0; 2],
+            len: 0,
+            sysex: None,
+            real_time_in_sysex: RealTimeInSysex::Forward,
+            channels: [ChannelState::default(); 16],
+        }
+    }
+
+    /// Returns the running status (the status byte which applies to following
+    /// data bytes), if any.
+    #[must_use]
+    pub const fn running_status(&self) -> Option<u8> {
+        self.status
+    }
+
+    /// Returns `true` if a System Exclusive message is in progress.
+    #[must_use]
+    pub const fn in_sysex(&self) -> bool {
+        self.sysex.is_some()
+    }
+
+    /// Returns the translation state of the given channel.
+    #[must_use]
+    pub fn channel_state(&self, channel: Channel) -> ChannelState {
+        self.channels[usize::from(u8::from(channel))]
+    }
+
+    /// Resets the translation state of the given channel, discarding any held
+    /// Bank Select, RPN and NRPN state.
+    pub fn reset_channel(&mut self, channel: Channel) {
+        self.channels[usize::from(u8::from(channel))] = ChannelState::default();
+    }
+
+    /// Resets the translator, discarding the running status, any partially
+    /// read message, and the state of every channel. A System Exclusive
+    /// message in progress is abandoned, without sending an End message.
+    pub fn reset(&mut self) {
+        self.status = None;
+        self.len = 0;
+        self.sysex = None;
+        self.channels = [ChannelState::default(); 16];
+    }
+
+    /// Sets the handling of System Real Time bytes which arrive within a
+    /// System Exclusive message.
+    #[must_use]
+    pub const fn with_real_time_in_sysex(mut self, real_time_in_sysex: RealTimeInSysex) -> Self {
+        self.real_time_in_sysex = real_time_in_sysex;
+        self
+    }
+
+    /// Reads the given bytes of the stream, appending the translated messages
+    /// to the buffer of words. Messages may be split across calls.
+    pub fn push(&mut self, bytes: &[u8], words: &mut Vec<u32>) {
+        for &byte in bytes {
+            self.push_byte(byte, words);
+        }
+    }
+
+    fn push_byte(&mut self, byte: u8, words: &mut Vec<u32>) {
+        match byte {
+            0xf8..=0xff => {
+                let discard =
+                    self.sysex.is_some() && self.real_time_in_sysex == RealTimeInSysex::Discard;
+
+                if data_len(byte) == Some(0) && !discard {
+                    words.push(self.system(byte, 0, 0));
+                }
+            }
+            0x00..=0x7f => {
+                if let Some(sysex) = &mut self.sysex {
+                    if sysex.data.len() == SYSEX_BYTES {
+                        sysex::write_sysex7_chunk(
+                            self.group,
+                            !sysex.started,
+                            false,
+                            &sysex.data,
+                            words,
+                        );
+                        sysex.started = true;
+                        sysex.data.clear();
+                    }
+
+                    sysex.data.push(byte);
+                } else if let Some(status) = self.status {
+                    self.data[self.len] = byte;
+                    self.len += 1;
+
+                    if Some(self.len) == data_len(status) {
+                        self.len = 0;
+                        self.message(status, words);
+                    }
+                }
+            }
+            _ => {
+                self.end_sysex(words);
+                self.len = 0;
+                self.status = match byte {
+                    SYSEX_START => {
+                        self.sysex = Some(Sysex::default());
+                        None
+                    }
+                    0xf6 => {
+                        words.push(self.system(byte, 0, 0));
+                        None
+                    }
+                    _ => Some(byte).filter(|&status| data_len(status).is_some()),
+                }
+            }
+        }
+    }
+
+    fn end_sysex(&mut self, words: &mut Vec<u32>) {
+        if let Some(sysex) = self.sysex.take() {
+            sysex::write_sysex7_chunk(self.group, !sysex.started, true, &sysex.data, words);
+        }
+    }
+
+    fn message(&mut self, status: u8, words: &mut Vec<u32>) {
+        let [data_1, data_2] = self.data;
+
+        if status >= 0xf0 {
+            words.push(self.system(status, data_1, data_2));
+            self.status = None;
+            return;
+        }
+
+        let group = u8::from(self.group);
+        let channel = status & 0xf;
+        let state = &mut self.channels[usize::from(channel)];
+        let word = |opcode: u8, index_1: u8, index_2: u8| {
+            0x4000_0000
+                | u32::from(group) << 24
+                | u32::from(opcode << 4 | channel) << 16
+                | u32::from(index_1) << 8
+                | u32::from(index_2)
+        };
+
+        let message = match status >> 4 {
+            NOTE_OFF => [word(NOTE_OFF, data_1, 0), velocity(data_2)],
+            NOTE_ON if data_2 == 0 => [
+                word(NOTE_OFF, data_1, 0),
+                u32::from(NOTE_OFF_VELOCITY) << 16,
+            ],
+            NOTE_ON => [word(NOTE_ON, data_1, 0), velocity(data_2)],
+            POLY_PRESSURE => [
+                word(POLY_PRESSURE, data_1, 0),
+                upscale(data_2.into(), 7, 32),
+            ],
+            PROGRAM_CHANGE => {
+                let bank = state
+                    .bank
+                    .map_or(0, |(msb, lsb)| u32::from(msb) << 8 | u32::from(lsb));
+                let options = u8::from(state.bank.is_some());
+
+                [
+                    word(PROGRAM_CHANGE, 0, options),
+                    u32::from(data_1) << 24 | bank,
+                ]
+            }
+            CHANNEL_PRESSURE => [word(CHANNEL_PRESSURE, 0, 0), upscale(data_1.into(), 7, 32)],
+            PITCH_BEND => {
+                let value = u32::from(data_2) << 7 | u32::from(data_1);
+
+                [word(PITCH_BEND, 0, 0), upscale(value, 14, 32)]
+            }
+            _ => match state.control_change(data_1, data_2) {
+                Control::Pass => [
+                    word(CONTROL_CHANGE, data_1, 0),
+                    upscale(data_2.into(), 7, 32),
+                ],
+                Control::Parameter(opcode, (bank, index), value) => {
+                    [word(opcode, bank, index), upscale(value.into(), 14, 32)]
+                }
+                Control::Consumed => return,
+            },
+        };
+
+        words.extend_from_slice(&message);
+    }
+
+    fn system(&self, status: u8, data_1: u8, data_2: u8) -> u32 {
+        0x1000_0000
+            | u32::from(u8::from(self.group)) << 24
+            | u32::from(status) << 16
+            | u32::from(data_1) << 8
+            | u32::from(data_2)
+    }
+}
+
+// Sysex
+
+#[derive(Clone, Debug, Default)]
+struct Sysex {
+    started: bool,
+    data: Vec<u8>,
+}
+
+// Channel State
+
+/// The translation state of a channel of a [`Midi1Translator`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ChannelState {
+    /// The Bank Select MSB and LSB held for the next Program Change, if any.
+    pub bank: Option<(u8, u8)>,
+    /// The current RPN (MSB and LSB).
+    pub rpn: (u8, u8),
+    /// The current NRPN (MSB and LSB).
+    pub nrpn: (u8, u8),
+    /// The kind of parameter (RPN or NRPN) to which Data Entry applies, if
+    /// any.
+    pub parameter: Option<Parameter>,
+    /// The 14-bit Data Entry value (from the latest Data Entry MSB and LSB).
+    pub data: u16,
+}
+
+/// The kind of parameter to which Data Entry applies.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Parameter {
+    /// A Registered Parameter (RPN).
+    Registered,
+    /// An Assignable (Non-Registered) Parameter (NRPN).
+    Assignable,
+}
+
+enum Control {
+    Pass,
+    Parameter(u8, (u8, u8), u16),
+    Consumed,
+}
+
+impl ChannelState {
+    fn control_change(&mut self, index: u8, value: u8) -> Control {
+        match index {
+            BANK_SELECT_MSB => self.bank = Some((value, self.bank.map_or(0, |(_, lsb)| lsb))),
+            BANK_SELECT_LSB => self.bank = Some((self.bank.map_or(0, |(msb, _)| msb), value)),
+            RPN_MSB => self.select(Parameter::Registered, |state| state.rpn.0 = value),
+            RPN_LSB => self.select(Parameter::Registered, |state| state.rpn.1 = value),
+            NRPN_MSB => self.select(Parameter::Assignable, |state| state.nrpn.0 = value),
+            NRPN_LSB => self.select(Parameter::Assignable, |state| state.nrpn.1 = value),
+            DATA_ENTRY_MSB | DATA_ENTRY_LSB => {
+                let (opcode, parameter) = match self.parameter {
+                    Some(Parameter::Registered) => (REGISTERED_CONTROLLER, self.rpn),
+                    Some(Parameter::Assignable) => (ASSIGNABLE_CONTROLLER, self.nrpn),
+                    None => return Control::Pass,
+                };
+
+                // the null parameter (0x7f, 0x7f) disables data entry
+
+                if parameter == (0x7f, 0x7f) {
+                    return Control::Pass;
+                }
+
+                self.data = match index {
+                    DATA_ENTRY_MSB => u16::from(value) << 7,
+                    _ => self.data & 0x3f80 | u16::from(value),
+                };
+
+                return Control::Parameter(opcode, parameter, self.data);
+            }
+            _ => return Control::Pass,
+        }
+
+        Control::Consumed
+    }
+
+    fn select(&mut self, parameter: Parameter, set: impl FnOnce(&mut Self)) {
+        set(self);
+        self.parameter = Some(parameter);
+        self.data = 0;
+    }
+}
+
+// Values
+
+fn velocity(value: u8) -> u32 {
+    upscale(value.into(), 7, 16) << 16
+}
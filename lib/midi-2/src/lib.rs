@@ -2,11 +2,14 @@
 // MIDI 2
 // =============================================================================
 
+#![no_std]
+
 // Re-Exports
 
-// pub mod ci {
-//     pub use midi_2_ci::*;
-// }
+#[cfg(feature = "ci")]
+pub mod ci {
+    pub use midi_2_ci::*;
+}
 
 pub mod protocol {
     pub use midi_2_protocol::*;